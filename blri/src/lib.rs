@@ -1,61 +1,326 @@
 mod isp;
+mod output;
+mod profile;
 pub use isp::{BootInfo, DeviceReset, EraseFlash, GetBootInfo, IspCommand, IspError, WriteFlash};
+pub use output::{to_ihex, to_srec, write_hex};
+pub use profile::BoardProfile;
 
+use output::{DecHex, Hex};
+
+use bouffalo_checksum::crc32;
 use byteorder::{BigEndian, LittleEndian, ReadBytesExt, WriteBytesExt};
 use object::{Object, ObjectSection, SectionFlags};
-use sha2::{Digest, Sha256};
+use sha2::Sha256;
 use std::fs::{self, File};
 use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::ops::Range;
 use std::path::Path;
 
 const HEAD_LENGTH: u64 = 0x160;
 const HEAD_MAGIC: u32 = 0x42464e50;
 const FLASH_MAGIC: u32 = 0x46434647;
 const CLOCK_MAGIC: u32 = 0x50434647;
+const HASH_IGNORE: u32 = 1 << 17;
+const BL808_XIP_BASE: u32 = 0x5800_0000;
+const FLASH_CLOCK_DIVIDER_MAX: u8 = 7;
+const METADATA_MAGIC: [u8; 4] = *b"BLMD";
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("I/O error")]
     Io(#[from] io::Error),
-    #[error("Wrong magic number")]
+    #[error("Wrong magic number 0x{wrong_magic:08x}")]
     MagicNumber { wrong_magic: u32 },
     #[error(
-        "File is too short to include an image header, should include {HEAD_LENGTH} but only {wrong_length}"
+        "File is too short to include an image header, should include {} but only {}",
+        DecHex(HEAD_LENGTH),
+        DecHex(*wrong_length)
     )]
     HeadLength { wrong_length: u64 },
-    #[error("Wrong flash config magic")]
+    #[error("Wrong flash config magic 0x{wrong_magic:08x}")]
     FlashConfigMagic { wrong_magic: u32 },
-    #[error("Wrong clock config magic")]
+    #[error("Wrong clock config magic 0x{wrong_magic:08x}")]
     ClockConfigMagic { wrong_magic: u32 },
     #[error(
-        "Image offset overflow, offset {wrong_image_offset} and length {wrong_image_length} expected, but file length is {file_length}"
+        "Image offset overflow, offset {} and length {} expected, but file length is {}",
+        DecHex(*wrong_image_offset),
+        DecHex(*wrong_image_length),
+        DecHex(*file_length)
     )]
     ImageOffsetOverflow {
         file_length: u64,
         wrong_image_offset: u32,
         wrong_image_length: u32,
     },
-    #[error("Wrong sha256 checksum")]
+    #[error("Wrong sha256 checksum {}", Hex(wrong_checksum))]
     Sha256Checksum { wrong_checksum: Vec<u8> },
+    #[error("Wrong crc32 checksum 0x{wrong_checksum:08x}")]
+    Crc32Checksum { wrong_checksum: u32 },
+    #[error(
+        "Flash clock divider {wrong_divider} is out of range, should be at most {FLASH_CLOCK_DIVIDER_MAX}"
+    )]
+    FlashClockDivider { wrong_divider: u8 },
+    #[error(
+        "Crystal frequency {wrong_frequency} Hz is not supported, should be one of 24, 26, 32, 38.4 or 40 MHz"
+    )]
+    CrystalFrequency { wrong_frequency: u32 },
+    #[error(
+        "Metadata of {} bytes is too long to fit into image body",
+        DecHex(*length)
+    )]
+    MetadataLength { length: usize },
+    #[error(
+        "Image cannot be padded to a multiple of sector size {}",
+        DecHex(*sector_size)
+    )]
+    PadSize { sector_size: u32 },
+    #[error("Flash I/O mode 0x{wrong_mode:02x} is not recognized")]
+    FlashIoMode { wrong_mode: u8 },
+    #[error(
+        "Hash range {}..{} is outside of file, file length is {}",
+        DecHex(wrong_range.start),
+        DecHex(wrong_range.end),
+        DecHex(*file_length)
+    )]
+    HashRange {
+        file_length: u64,
+        wrong_range: Range<u32>,
+    },
+    #[error("Invalid board profile")]
+    Profile(#[from] toml::de::Error),
+}
+
+/// Checksum algorithm over image body.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChecksumKind {
+    /// SHA-256 digest, stored in the 32-byte hash field.
+    Sha256,
+    /// CRC-32 (ISO-HDLC) value, stored in the first 4 bytes of hash field as little endian.
+    Crc32,
+    /// No checksum over image body; hash field is left untouched.
+    None,
+}
+
+impl ChecksumKind {
+    /// Get checksum algorithm from the flag word of image basic configuration.
+    ///
+    /// Images with hash-ignore bit set have no body checksum, otherwise SHA-256 is used.
+    pub fn from_basic_config_flag(flag: u32) -> Self {
+        if flag & HASH_IGNORE != 0 {
+            ChecksumKind::None
+        } else {
+            ChecksumKind::Sha256
+        }
+    }
+}
+
+/// Chip family an image is built for.
+///
+/// Some header fields, like processor core configurations, are laid out differently
+/// across families.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChipFamily {
+    /// BL602 series.
+    Bl602,
+    /// BL702 series.
+    Bl702,
+    /// BL616 and BL618 series.
+    Bl616,
+    /// BL808 series; header fields are parsed in this layout.
+    #[default]
+    Bl808,
+}
+
+/// Number of data lines used by flash access.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IoWidth {
+    /// Standard SPI, one data line.
+    Single,
+    /// Dual output or dual I/O, two data lines.
+    Dual,
+    /// Quad output or quad I/O, four data lines.
+    Quad,
+}
+
+impl IoWidth {
+    /// Get data width from the I/O mode byte of flash configuration.
+    ///
+    /// Returns `None` if the mode is not recognized.
+    pub fn from_flash_io_mode(io_mode: u8) -> Option<Self> {
+        // Bit 4 selects 32-bit address mode; lower bits are NIO, DO, QO, DIO, QIO.
+        match io_mode & 0x0F {
+            0 => Some(IoWidth::Single),
+            1 | 3 => Some(IoWidth::Dual),
+            2 | 4 => Some(IoWidth::Quad),
+            _ => None,
+        }
+    }
+}
+
+/// Read command and dummy cycles of flash configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FlashRead {
+    /// Read command opcode, e.g. 0x6B for quad output read.
+    pub command: u8,
+    /// Number of dummy clock cycles after address.
+    pub dummy_cycles: u8,
+}
+
+/// Offset of read command used by flash I/O mode, followed by its dummy cycles byte.
+fn flash_read_offset(io_mode: u8) -> Option<u64> {
+    match io_mode & 0x0F {
+        0 => Some(0x24),
+        1 => Some(0x28),
+        2 => Some(0x2C),
+        3 => Some(0x2A),
+        4 => Some(0x2E),
+        _ => None,
+    }
+}
+
+/// Advisory problems found in image header, which do not stop patching.
+#[derive(thiserror::Error, Clone, Debug, PartialEq, Eq)]
+pub enum Warning {
+    #[error(
+        "Flash I/O mode requires {requested:?} data width, but board only supports {supported:?}"
+    )]
+    FlashIoWidth {
+        requested: IoWidth,
+        supported: IoWidth,
+    },
+    #[error(
+        "Entry point {} is outside of mapped image range {}..{}",
+        DecHex(*entry),
+        DecHex(range.start),
+        DecHex(range.end)
+    )]
+    EntryOutOfRange { entry: u32, range: Range<u32> },
+}
+
+/// Parsed fields of image header.
+#[derive(Clone, Debug)]
+pub struct ImageHeader {
+    /// I/O mode byte of flash configuration.
+    pub flash_io_mode: u8,
+    /// Read command used by flash I/O mode, or `None` if the mode is not recognized.
+    pub flash_read: Option<FlashRead>,
+    /// Divider of flash clock in clock configuration.
+    pub flash_clock_divider: u8,
+    /// Flags of image basic configuration.
+    pub basic_config_flag: u32,
+    /// Offset of image body in file.
+    pub group_image_offset: u32,
+    /// Length of image body.
+    pub image_body_length: u32,
+    /// Processor core configurations.
+    pub cpu_configs: [CpuConfig; 3],
+}
+
+impl ImageHeader {
+    /// Check flash I/O mode of this header against data width supported by the board.
+    pub fn check_flash_caps(&self, supported: IoWidth) -> Option<Warning> {
+        let requested = IoWidth::from_flash_io_mode(self.flash_io_mode)?;
+        if requested > supported {
+            Some(Warning::FlashIoWidth {
+                requested,
+                supported,
+            })
+        } else {
+            None
+        }
+    }
+    /// Check that boot entry of each enabled core lies within its mapped image range.
+    ///
+    /// Processor core configurations are parsed as laid out by BL808 images, thus only
+    /// images of [`ChipFamily::Bl808`] are checked; other families return `None`.
+    pub fn check_boot_entries(&self, family: ChipFamily) -> Option<Warning> {
+        if family != ChipFamily::Bl808 {
+            return None;
+        }
+        self.cpu_configs
+            .iter()
+            .filter(|c| c.enabled)
+            .find_map(|cpu_config| {
+                let range = cpu_config.mapped_range(self.image_body_length);
+                (!range.contains(&cpu_config.boot_entry)).then_some(Warning::EntryOutOfRange {
+                    entry: cpu_config.boot_entry,
+                    range,
+                })
+            })
+    }
+    /// Address of the first image byte, so that image body starts where it is mapped for the
+    /// first enabled core.
+    ///
+    /// Processor core configurations are parsed as laid out by BL808 images, thus other
+    /// families, as well as images with no core enabled, return `None`.
+    pub fn load_address(&self, family: ChipFamily) -> Option<u32> {
+        if family != ChipFamily::Bl808 {
+            return None;
+        }
+        let cpu_config = self.cpu_configs.iter().find(|c| c.enabled)?;
+        let body_start = cpu_config.mapped_range(self.image_body_length).start;
+        Some(body_start.wrapping_sub(self.group_image_offset))
+    }
+}
+
+/// Processor core configuration in image header.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CpuConfig {
+    /// Whether this core is booted from this image.
+    pub enabled: bool,
+    /// Offset of this core's code from the start of image body.
+    pub image_address_offset: u32,
+    /// Entry address of this core.
+    pub boot_entry: u32,
+}
+
+impl CpuConfig {
+    /// Address range where image body is mapped for this core on BL808.
+    pub fn mapped_range(&self, image_body_length: u32) -> Range<u32> {
+        let start = BL808_XIP_BASE.wrapping_add(self.image_address_offset);
+        start..start.saturating_add(image_body_length)
+    }
 }
 
 /// Process operations.
+///
+/// Each field is one planned fix, and [`process`] applies only those that are `Some`;
+/// callers may drop fixes they don't want, or build operations by hand from `Default`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Operations {
     /// Refill hash value of image body into header, or None if not needed.
     ///
-    /// Should include 32 bytes for sha256 algorithm.
+    /// Should include 32 bytes for sha256 algorithm, or 4 bytes for crc32 algorithm.
     pub refill_hash: Option<Vec<u8>>,
     /// Refill CRC32 value of header, None if not needed.
+    ///
+    /// Hash field is covered by header CRC32; the value is computed with `refill_hash`
+    /// already applied, thus it's only valid when applied together with `refill_hash`.
     pub refill_header_crc: Option<u32>,
+    /// Advisory problems found in image header; [`process`] does not act on them.
+    pub warnings: Vec<Warning>,
+}
+
+impl Operations {
+    /// Check if no operation is needed, i.e. the image is already valid.
+    ///
+    /// Warnings are not operations and are not considered.
+    pub fn is_empty(&self) -> bool {
+        self.refill_hash.is_none() && self.refill_header_crc.is_none()
+    }
 }
 
 pub type Result<T> = core::result::Result<T, Error>;
 
-/// Check source file without modifying, returning suggested operations.
+/// Parse image header from source file, checking magic numbers and lengths.
 ///
-/// File `f` should be readable, but not writable.
-pub fn check(f: &mut File) -> Result<Operations> {
-    let file_length = f.metadata()?.len();
+/// Source `f` is any reader that can seek, such as a `File`, an `io::Cursor` over image bytes,
+/// or a reader over flash contents of a running device. Seeking to `SeekFrom::End(0)` must
+/// report the length of the image. Numeric header fields are little endian on every
+/// supported chip family.
+pub fn parse<R: Read + Seek>(f: &mut R) -> Result<ImageHeader> {
+    let file_length = f.seek(SeekFrom::End(0))?;
 
     f.seek(SeekFrom::Start(0x00))?;
     let head_magic = f.read_u32::<BigEndian>()?;
@@ -78,6 +343,17 @@ pub fn check(f: &mut File) -> Result<Operations> {
             wrong_magic: flash_magic,
         });
     }
+    let flash_io_mode = f.read_u8()?;
+    let flash_read = match flash_read_offset(flash_io_mode) {
+        Some(offset) => {
+            f.seek(SeekFrom::Start(offset))?;
+            Some(FlashRead {
+                command: f.read_u8()?,
+                dummy_cycles: f.read_u8()?,
+            })
+        }
+        None => None,
+    };
 
     f.seek(SeekFrom::Start(0x64))?;
     let clock_magic = f.read_u32::<BigEndian>()?;
@@ -87,6 +363,9 @@ pub fn check(f: &mut File) -> Result<Operations> {
         });
     }
 
+    f.seek(SeekFrom::Start(0x76))?;
+    let flash_clock_divider = f.read_u8()?;
+
     f.seek(SeekFrom::Start(0x84))?;
     let group_image_offset = f.read_u32::<LittleEndian>()?;
 
@@ -101,50 +380,133 @@ pub fn check(f: &mut File) -> Result<Operations> {
         });
     }
 
-    // read hash values from file
-    f.seek(SeekFrom::Start(0x90))?;
-    let mut actual_hash = vec![0; 32];
-    f.read_exact(&mut actual_hash)?;
+    f.seek(SeekFrom::Start(0x80))?;
+    let basic_config_flag = f.read_u32::<LittleEndian>()?;
+
+    let mut cpu_configs = [CpuConfig::default(); 3];
+    for (i, cpu_config) in cpu_configs.iter_mut().enumerate() {
+        let base = 0xB0 + 0x18 * i as u64;
+        f.seek(SeekFrom::Start(base))?;
+        cpu_config.enabled = f.read_u8()? != 0;
+        f.seek(SeekFrom::Start(base + 0x0C))?;
+        cpu_config.image_address_offset = f.read_u32::<LittleEndian>()?;
+        cpu_config.boot_entry = f.read_u32::<LittleEndian>()?;
+    }
 
-    // calculate hash
-    f.seek(SeekFrom::Start(group_image_offset as u64))?;
-    let mut hasher = Sha256::new();
-    let mut buffer = vec![0; image_body_length as usize];
-    loop {
-        let length_read = f.read(&mut buffer)?;
-        if length_read == 0 {
-            break;
+    Ok(ImageHeader {
+        flash_io_mode,
+        flash_read,
+        flash_clock_divider,
+        basic_config_flag,
+        group_image_offset,
+        image_body_length,
+        cpu_configs,
+    })
+}
+
+/// Check source file without modifying, returning suggested operations.
+///
+/// Source `f` is only read, never written, so it may be a reader over contents already
+/// written to flash; an empty result confirms the header and checksums match byte-for-byte.
+/// Bounds on `f` are the same as [`parse`]. Checksum algorithm is decided by the image header.
+/// Boot entries are checked against the layout of `family`, and problems found are returned
+/// as warnings.
+pub fn check<R: Read + Seek>(f: &mut R, family: ChipFamily) -> Result<Operations> {
+    check_with_checksum(f, family, None)
+}
+
+/// Check source file using given checksum algorithm, returning suggested operations.
+///
+/// If `checksum` is `None`, the algorithm is decided by the image header.
+pub fn check_with_checksum<R: Read + Seek>(
+    f: &mut R,
+    family: ChipFamily,
+    checksum: Option<ChecksumKind>,
+) -> Result<Operations> {
+    check_with_hash_range(f, family, checksum, None)
+}
+
+/// Check source file, computing checksum over `hash_range` instead of image body.
+///
+/// Custom layouts, e.g. with a configuration block after the application, may have their
+/// bootloader hash a different range of bytes. If `hash_range` is `None`, the range is
+/// image body as given by the header.
+pub fn check_with_hash_range<R: Read + Seek>(
+    f: &mut R,
+    family: ChipFamily,
+    checksum: Option<ChecksumKind>,
+    hash_range: Option<Range<u32>>,
+) -> Result<Operations> {
+    let header = parse(f)?;
+    let image_body_length = header.image_body_length;
+    let (hash_offset, hash_length) = match hash_range {
+        Some(range) => {
+            let file_length = f.seek(SeekFrom::End(0))?;
+            if range.start > range.end || range.end as u64 > file_length {
+                return Err(Error::HashRange {
+                    file_length,
+                    wrong_range: range,
+                });
+            }
+            (range.start, range.end - range.start)
         }
-        hasher.update(&buffer[..length_read]);
+        None => (header.group_image_offset, image_body_length),
+    };
+
+    if header.flash_clock_divider > FLASH_CLOCK_DIVIDER_MAX {
+        return Err(Error::FlashClockDivider {
+            wrong_divider: header.flash_clock_divider,
+        });
     }
 
-    let calculated_hash = &hasher.finalize()[..];
+    let checksum = checksum.unwrap_or(ChecksumKind::from_basic_config_flag(
+        header.basic_config_flag,
+    ));
+
+    // read hash values from file
+    f.seek(SeekFrom::Start(0x90))?;
+    let mut actual_hash = vec![0; 32];
+    f.read_exact(&mut actual_hash)?;
 
-    let refill_hash_operation = if calculated_hash != actual_hash {
-        let mut candidate_hash_1 = vec![0u8; 32];
-        candidate_hash_1[..4].copy_from_slice(&[0xef, 0xbe, 0xad, 0xde]);
-        let mut candidate_hash_2 = vec![0u8; 32];
-        for i in 0..8 {
-            candidate_hash_2[4 * i..4 * (i + 1)].copy_from_slice(&[0xef, 0xbe, 0xad, 0xde]);
+    let refill_hash_operation = match checksum {
+        ChecksumKind::Sha256 => {
+            let calculated_hash = body_sha256(f, hash_offset, hash_length)?;
+            if calculated_hash != actual_hash {
+                if !is_placeholder_hash(&actual_hash) {
+                    return Err(Error::Sha256Checksum {
+                        wrong_checksum: actual_hash,
+                    });
+                }
+                Some(calculated_hash)
+            } else {
+                // source image hash is correct, do not need to fill
+                None
+            }
         }
-        if actual_hash != candidate_hash_1 && actual_hash != candidate_hash_2 {
-            return Err(Error::Sha256Checksum {
-                wrong_checksum: actual_hash,
-            });
+        ChecksumKind::Crc32 => {
+            let calculated_crc = body_crc32(f, hash_offset, hash_length)?;
+            let actual_crc = u32::from_le_bytes(actual_hash[..4].try_into().unwrap());
+            if calculated_crc != actual_crc {
+                if !is_placeholder_hash(&actual_hash) {
+                    return Err(Error::Crc32Checksum {
+                        wrong_checksum: actual_crc,
+                    });
+                }
+                Some(Vec::from(calculated_crc.to_le_bytes()))
+            } else {
+                None
+            }
         }
-        Some(Vec::from(calculated_hash))
-    } else {
-        // source image hash is correct, do not need to fill
-        None
+        ChecksumKind::None => None,
     };
 
     f.seek(SeekFrom::Start(0x00))?;
     let mut buf = vec![0u8; 0x15C];
     f.read_exact(&mut buf)?;
     if let Some(ref new_hash) = refill_hash_operation {
-        buf[0x90..0xB0].copy_from_slice(new_hash);
+        buf[0x90..0x90 + new_hash.len()].copy_from_slice(new_hash);
     }
-    let calculated_header_crc = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC).checksum(&buf);
+    let calculated_header_crc = crc32(&buf);
 
     f.seek(SeekFrom::Start(0x15C))?;
     let read_head_crc = f.read_u32::<LittleEndian>()?;
@@ -159,14 +521,20 @@ pub fn check(f: &mut File) -> Result<Operations> {
     Ok(Operations {
         refill_hash: refill_hash_operation,
         refill_header_crc: refill_header_crc_operation,
+        warnings: header.check_boot_entries(family).into_iter().collect(),
     })
 }
 
 /// Process target file from operations.
+///
+/// Nothing is written if `ops` is empty, leaving modification time of the file unchanged.
 pub fn process(f: &mut File, ops: &Operations) -> Result<()> {
+    if ops.is_empty() {
+        return Ok(());
+    }
     if let Some(hash_to_fill) = &ops.refill_hash {
         f.seek(SeekFrom::Start(0x90))?;
-        f.write(&hash_to_fill[..32])?;
+        f.write_all(hash_to_fill)?;
     }
     if let Some(header_crc_to_fill) = &ops.refill_header_crc {
         f.seek(SeekFrom::Start(0x15C))?;
@@ -175,6 +543,307 @@ pub fn process(f: &mut File, ops: &Operations) -> Result<()> {
     Ok(())
 }
 
+/// A header byte changed by patching, listed by [`header_diff`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ByteChange {
+    /// Offset of the byte from start of image.
+    pub offset: u64,
+    /// Value before patching.
+    pub old: u8,
+    /// Value after patching.
+    pub new: u8,
+}
+
+impl core::fmt::Display for ByteChange {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "0x{:04x}: 0x{:02x} -> 0x{:02x}",
+            self.offset, self.old, self.new
+        )
+    }
+}
+
+/// Read raw bytes of image header, or fewer bytes if the file is shorter than a header.
+///
+/// Read header before and after patching, and compare them with [`header_diff`].
+pub fn read_header<R: Read + Seek>(f: &mut R) -> Result<Vec<u8>> {
+    f.seek(SeekFrom::Start(0))?;
+    let mut header = Vec::with_capacity(HEAD_LENGTH as usize);
+    f.take(HEAD_LENGTH).read_to_end(&mut header)?;
+    Ok(header)
+}
+
+/// List bytes that differ between header bytes `before` and `after` patching, in offset order.
+///
+/// Bytes present in only one of them are not compared.
+pub fn header_diff(before: &[u8], after: &[u8]) -> Vec<ByteChange> {
+    before
+        .iter()
+        .zip(after)
+        .enumerate()
+        .filter(|(_, (old, new))| old != new)
+        .map(|(offset, (&old, &new))| ByteChange {
+            offset: offset as u64,
+            old,
+            new,
+        })
+        .collect()
+}
+
+/// Set flash clock divider in clock configuration, refilling CRC32 value of clock configuration.
+///
+/// File `f` should be readable and writable. Other header fields are left unchanged;
+/// run `check` on the file afterwards to find out if other operations are needed.
+pub fn set_flash_clock_divider(f: &mut File, divider: u8) -> Result<()> {
+    if divider > FLASH_CLOCK_DIVIDER_MAX {
+        return Err(Error::FlashClockDivider {
+            wrong_divider: divider,
+        });
+    }
+    parse(f)?;
+    f.seek(SeekFrom::Start(0x76))?;
+    f.write_u8(divider)?;
+    refill_clock_config_crc(f)
+}
+
+/// Set crystal type in clock configuration from crystal oscillator frequency in Hertz,
+/// refilling CRC32 value of clock configuration.
+///
+/// Supported frequencies are 24, 26, 32, 38.4 and 40 MHz. File `f` should be readable
+/// and writable. Other header fields are left unchanged.
+pub fn set_crystal(f: &mut File, frequency: u32) -> Result<()> {
+    let crystal_type = match frequency {
+        24_000_000 => 1,
+        32_000_000 => 2,
+        38_400_000 => 3,
+        40_000_000 => 4,
+        26_000_000 => 5,
+        _ => {
+            return Err(Error::CrystalFrequency {
+                wrong_frequency: frequency,
+            });
+        }
+    };
+    parse(f)?;
+    f.seek(SeekFrom::Start(0x68))?;
+    f.write_u8(crystal_type)?;
+    refill_clock_config_crc(f)
+}
+
+fn refill_clock_config_crc(f: &mut File) -> Result<()> {
+    f.seek(SeekFrom::Start(0x68))?;
+    let mut clock_config = [0u8; 0x14];
+    f.read_exact(&mut clock_config)?;
+    let clock_config_crc = crc32(&clock_config);
+    f.write_u32::<LittleEndian>(clock_config_crc)?;
+    Ok(())
+}
+
+/// Override read command and dummy cycles used by current flash I/O mode, refilling
+/// CRC32 value of flash configuration.
+///
+/// Fields given as `None` are left unchanged. File `f` should be readable and writable.
+pub fn set_flash_read(f: &mut File, command: Option<u8>, dummy_cycles: Option<u8>) -> Result<()> {
+    let header = parse(f)?;
+    let offset = flash_read_offset(header.flash_io_mode).ok_or(Error::FlashIoMode {
+        wrong_mode: header.flash_io_mode,
+    })?;
+    if let Some(command) = command {
+        f.seek(SeekFrom::Start(offset))?;
+        f.write_u8(command)?;
+    }
+    if let Some(dummy_cycles) = dummy_cycles {
+        f.seek(SeekFrom::Start(offset + 1))?;
+        f.write_u8(dummy_cycles)?;
+    }
+
+    f.seek(SeekFrom::Start(0x0C))?;
+    let mut flash_config = [0u8; 0x54];
+    f.read_exact(&mut flash_config)?;
+    let flash_config_crc = crc32(&flash_config);
+    f.write_u32::<LittleEndian>(flash_config_crc)?;
+    Ok(())
+}
+
+/// Append metadata bytes to the end of image body, replacing existing metadata if any.
+///
+/// Metadata is stored as a trailer of data bytes, data length as 32-bit little endian
+/// and magic `BLMD`. Image body length is extended to include the trailer, so the body
+/// checksum covers metadata as well. Hash field is reset to placeholder value; run `check`
+/// on the file afterwards to get operations that refill the checksums.
+///
+/// File `f` should be readable and writable. Contents after the original image body are discarded.
+pub fn append_metadata(f: &mut File, metadata: &[u8]) -> Result<()> {
+    let header = parse(f)?;
+    let body_length = match read_metadata(f)? {
+        Some(old) => header.image_body_length - old.len() as u32 - 8,
+        None => header.image_body_length,
+    };
+    let new_body_length = u32::try_from(metadata.len())
+        .ok()
+        .and_then(|len| body_length.checked_add(len)?.checked_add(8))
+        .filter(|len| header.group_image_offset.checked_add(*len).is_some())
+        .ok_or(Error::MetadataLength {
+            length: metadata.len(),
+        })?;
+
+    let body_end = header.group_image_offset as u64 + body_length as u64;
+    f.set_len(body_end)?;
+    f.seek(SeekFrom::Start(body_end))?;
+    f.write_all(metadata)?;
+    f.write_u32::<LittleEndian>(metadata.len() as u32)?;
+    f.write_all(&METADATA_MAGIC)?;
+
+    f.seek(SeekFrom::Start(0x8C))?;
+    f.write_u32::<LittleEndian>(new_body_length)?;
+    let mut placeholder_hash = [0u8; 32];
+    placeholder_hash[..4].copy_from_slice(&[0xef, 0xbe, 0xad, 0xde]);
+    f.write_all(&placeholder_hash)?;
+    Ok(())
+}
+
+/// Read metadata bytes appended to the end of image body, or `None` if there is none.
+///
+/// File `f` should be readable, but not writable.
+pub fn read_metadata(f: &mut File) -> Result<Option<Vec<u8>>> {
+    let header = parse(f)?;
+    if header.image_body_length < 8 {
+        return Ok(None);
+    }
+    let body_end = header.group_image_offset as u64 + header.image_body_length as u64;
+    f.seek(SeekFrom::Start(body_end - 8))?;
+    let length = f.read_u32::<LittleEndian>()?;
+    let mut magic = [0u8; 4];
+    f.read_exact(&mut magic)?;
+    if magic != METADATA_MAGIC || length > header.image_body_length - 8 {
+        return Ok(None);
+    }
+    f.seek(SeekFrom::Start(body_end - 8 - length as u64))?;
+    let mut metadata = vec![0u8; length as usize];
+    f.read_exact(&mut metadata)?;
+    Ok(Some(metadata))
+}
+
+/// Pad image with `0xFF` bytes up to a multiple of `sector_size` bytes, returning new file length.
+///
+/// Padding matches erased flash, so flashing tools can write whole erase blocks. If
+/// `extend_body` is set, image body length is extended to the end of padding so the body
+/// checksum covers it; hash field is then reset to placeholder value, and `check` should
+/// be run afterwards to refill the checksums. Otherwise the header is left untouched and
+/// the image still verifies, with padding outside of image body.
+///
+/// Pad after [`append_metadata`], which discards contents after image body. Metadata is
+/// no longer found at the end of body once padding is included in it.
+pub fn pad_image(f: &mut File, sector_size: u32, extend_body: bool) -> Result<u64> {
+    let header = parse(f)?;
+    let file_length = f.seek(SeekFrom::End(0))?;
+    let padded_length = Some(sector_size)
+        .filter(|size| *size > 0)
+        .and_then(|size| file_length.checked_next_multiple_of(size as u64))
+        .filter(|len| !extend_body || *len <= u32::MAX as u64)
+        .ok_or(Error::PadSize { sector_size })?;
+
+    let padding = vec![0xFF; (padded_length - file_length) as usize];
+    f.write_all(&padding)?;
+
+    if extend_body {
+        let new_body_length = padded_length as u32 - header.group_image_offset;
+        f.seek(SeekFrom::Start(0x8C))?;
+        f.write_u32::<LittleEndian>(new_body_length)?;
+        let mut placeholder_hash = [0u8; 32];
+        placeholder_hash[..4].copy_from_slice(&[0xef, 0xbe, 0xad, 0xde]);
+        f.write_all(&placeholder_hash)?;
+    }
+    Ok(padded_length)
+}
+
+/// Calculate SHA-256 digest of the whole image file, e.g. to verify a reproducible build.
+pub fn image_sha256(f: &mut File) -> Result<Vec<u8>> {
+    let file_length = f.metadata()?.len();
+    f.seek(SeekFrom::Start(0))?;
+    Ok(sha256_of(f.take(file_length))?.to_vec())
+}
+
+/// Signature algorithm over image header and body.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignAlgorithm {
+    /// HMAC-SHA256 with a shared secret key, giving a 32-byte signature.
+    HmacSha256,
+}
+
+/// Sign image header and body with `key`, returning the signature.
+///
+/// The header holds the body checksum, so sign the image after checksums are refilled.
+/// Signature is detached from the image; writing it into the secure boot area is not supported yet.
+pub fn sign_image(bytes: &[u8], key: &[u8], algo: SignAlgorithm) -> Result<Vec<u8>> {
+    if (bytes.len() as u64) < HEAD_LENGTH {
+        return Err(Error::HeadLength {
+            wrong_length: bytes.len() as u64,
+        });
+    }
+    let word = |offset: usize| u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+    let image_offset = word(0x84);
+    let image_length = word(0x8C);
+    let body_end = image_offset as u64 + image_length as u64;
+    if body_end > bytes.len() as u64 {
+        return Err(Error::ImageOffsetOverflow {
+            file_length: bytes.len() as u64,
+            wrong_image_offset: image_offset,
+            wrong_image_length: image_length,
+        });
+    }
+    let header = &bytes[..HEAD_LENGTH as usize];
+    let body = &bytes[image_offset as usize..body_end as usize];
+    match algo {
+        SignAlgorithm::HmacSha256 => {
+            use hmac::{Hmac, Mac};
+            let mut mac =
+                Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+            mac.update(header);
+            mac.update(body);
+            Ok(mac.finalize().into_bytes().to_vec())
+        }
+    }
+}
+
+/// Calculate SHA-256 digest of image body.
+fn body_sha256<R: Read + Seek>(f: &mut R, offset: u32, length: u32) -> Result<Vec<u8>> {
+    f.seek(SeekFrom::Start(offset as u64))?;
+    Ok(sha256_of(f.take(length as u64))?.to_vec())
+}
+
+/// Calculate SHA-256 digest of all bytes from `r`, the same way firmware does.
+fn sha256_of(mut r: impl Read) -> io::Result<[u8; 32]> {
+    let mut hasher = bouffalo_checksum::Sha256::new();
+    let mut buf = [0u8; 4096];
+    loop {
+        match r.read(&mut buf)? {
+            0 => return Ok(hasher.finalize()),
+            n => hasher.update(&buf[..n]),
+        }
+    }
+}
+
+/// Calculate CRC-32 value of image body.
+fn body_crc32<R: Read + Seek>(f: &mut R, offset: u32, length: u32) -> Result<u32> {
+    f.seek(SeekFrom::Start(offset as u64))?;
+    let mut buffer = Vec::with_capacity(length as usize);
+    f.take(length as u64).read_to_end(&mut buffer)?;
+    Ok(crc32(&buffer))
+}
+
+/// Check if hash field is filled with placeholder values rather than a real checksum.
+fn is_placeholder_hash(hash: &[u8]) -> bool {
+    let mut candidate_hash_1 = vec![0u8; 32];
+    candidate_hash_1[..4].copy_from_slice(&[0xef, 0xbe, 0xad, 0xde]);
+    let mut candidate_hash_2 = vec![0u8; 32];
+    for i in 0..8 {
+        candidate_hash_2[4 * i..4 * (i + 1)].copy_from_slice(&[0xef, 0xbe, 0xad, 0xde]);
+    }
+    hash == candidate_hash_1 || hash == candidate_hash_2
+}
+
 // The following functions are for elf2bin module
 // Most of the code is adapted from `https://github.com/llvm/llvm-project/tree/main/llvm/lib/ObjCopy/ELF`
 
@@ -223,10 +892,10 @@ fn log_section_info(sections: &[object::Section]) {
 
     for section in sections {
         println!(
-            "Section: {} at address 0x{:x} with size 0x{:x}",
+            "Section: {} at address {} with size {}",
             section.name().unwrap_or("<unnamed>"),
-            section.address(),
-            section.size()
+            DecHex(section.address()),
+            DecHex(section.size())
         );
     }
 }
@@ -284,8 +953,10 @@ fn process_sections(sections: Vec<object::Section>) -> Result<Vec<u8>> {
         let name = section.name().unwrap_or("<unnamed>");
 
         println!(
-            "Writing section: {} at address 0x{:x} with size 0x{:x}",
-            name, addr, size
+            "Writing section: {} at address {} with size {}",
+            name,
+            DecHex(addr),
+            DecHex(size)
         );
 
         if size == 0 {
@@ -308,9 +979,9 @@ fn write_section_data(output: &mut Vec<u8>, section: &object::Section) -> Result
         // NOBITS section (like .bss) - write zeros
         let zeros = vec![0u8; section.size() as usize];
         println!(
-            "Section {} is NOBITS, writing zeros of size 0x{:x}",
+            "Section {} is NOBITS, writing zeros of size {}",
             section.name().unwrap_or("<unnamed>"),
-            section.size()
+            DecHex(section.size())
         );
         output.write_all(&zeros)?;
     }
@@ -1,12 +1,13 @@
 use blri::{
-    BootInfo, DeviceReset, EraseFlash, Error, GetBootInfo, IspCommand, IspError, WriteFlash,
-    elf_to_bin,
+    BoardProfile, BootInfo, ChecksumKind, ChipFamily, DeviceReset, EraseFlash, Error, GetBootInfo,
+    IoWidth, IspCommand, IspError, SignAlgorithm, WriteFlash, elf_to_bin,
 };
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use inquire::Select;
 use std::{
     fs::{self, File},
-    io::{Read, Write},
+    io::{Cursor, Read, Write},
+    ops::Range,
     path::{Path, PathBuf},
     thread::sleep,
     time::Duration,
@@ -37,6 +38,167 @@ struct Patch {
     input: PathBuf,
     /// The path to save the patched image file. If not provided, the input file will be overwritten.
     output: Option<PathBuf>,
+    /// Checksum algorithm over image body. If set to auto, it is decided by the image header.
+    #[arg(long, value_enum, default_value_t = Checksum::Auto)]
+    checksum: Checksum,
+    /// Chip family the image is built for, deciding which header layout checks apply.
+    #[arg(long, value_enum, default_value_t = Chip::Bl808)]
+    chip: Chip,
+    /// Output file format. For non-binary formats, output defaults to input filename with format extension.
+    #[arg(long, value_enum, default_value_t = Format::Bin)]
+    format: Format,
+    /// Address of the first image byte in hex output. Defaults to the address image body is mapped at by image header.
+    #[arg(long, value_parser = parse_u32)]
+    base_address: Option<u32>,
+    /// Load board settings, e.g. flash read command, from this TOML profile. Options given on command line take precedence.
+    #[arg(long)]
+    profile: Option<PathBuf>,
+    /// Crystal oscillator frequency of the board in Hertz, e.g. 40000000.
+    #[arg(long, value_parser = parse_u32)]
+    crystal: Option<u32>,
+    /// Maximum flash I/O width supported by the board. Warns if the image header requests more.
+    #[arg(long, value_enum)]
+    flash_caps: Option<FlashCaps>,
+    /// Override flash clock divider in clock configuration, from 0 to 7.
+    #[arg(long)]
+    flash_clock_divider: Option<u8>,
+    /// Override read command of the flash I/O mode selected in header, e.g. 0xEB.
+    #[arg(long, value_parser = parse_u8)]
+    flash_read_command: Option<u8>,
+    /// Override dummy cycles of the flash read command.
+    #[arg(long)]
+    flash_read_dummy_cycles: Option<u8>,
+    /// Compute checksum over `start:len` bytes of the file instead of image body given by header.
+    #[arg(long, value_parser = parse_hash_range)]
+    hash_range: Option<Range<u32>>,
+    /// Append contents of this file to image body as metadata, e.g. firmware version.
+    #[arg(long)]
+    metadata: Option<PathBuf>,
+    /// Pad image with 0xFF bytes up to a multiple of this flash sector size, e.g. 4096.
+    #[arg(long, value_parser = parse_u32)]
+    pad: Option<u32>,
+    /// Include padding in image body length, so the body checksum covers it.
+    #[arg(long, requires = "pad")]
+    pad_in_body: bool,
+    /// Expected SHA-256 digest of the patched binary image in hex. Exits with error if it differs.
+    #[arg(long, value_parser = parse_sha256)]
+    expect_sha256: Option<String>,
+    /// Sign the patched image with key in this file, saving signature next to the output as `.sig`.
+    #[arg(long)]
+    sign: Option<PathBuf>,
+    /// Signature algorithm used with `--sign`.
+    #[arg(long, value_enum, default_value_t = Sign::HmacSha256)]
+    sign_algorithm: Sign,
+    /// Print every header byte changed by patching, as `offset: old -> new`.
+    #[arg(long, short)]
+    verbose: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Sign {
+    HmacSha256,
+}
+
+impl Sign {
+    fn algorithm(self) -> SignAlgorithm {
+        match self {
+            Sign::HmacSha256 => SignAlgorithm::HmacSha256,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Chip {
+    Bl602,
+    Bl702,
+    Bl616,
+    Bl808,
+}
+
+impl Chip {
+    fn family(self) -> ChipFamily {
+        match self {
+            Chip::Bl602 => ChipFamily::Bl602,
+            Chip::Bl702 => ChipFamily::Bl702,
+            Chip::Bl616 => ChipFamily::Bl616,
+            Chip::Bl808 => ChipFamily::Bl808,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum FlashCaps {
+    Single,
+    Dual,
+    Quad,
+}
+
+impl FlashCaps {
+    fn width(self) -> IoWidth {
+        match self {
+            FlashCaps::Single => IoWidth::Single,
+            FlashCaps::Dual => IoWidth::Dual,
+            FlashCaps::Quad => IoWidth::Quad,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Bin,
+    Ihex,
+    Srec,
+}
+
+fn parse_u32(s: &str) -> Result<u32, std::num::ParseIntError> {
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => s.parse(),
+    }
+}
+
+fn parse_u8(s: &str) -> Result<u8, String> {
+    let value = parse_u32(s).map_err(|e| e.to_string())?;
+    u8::try_from(value).map_err(|e| e.to_string())
+}
+
+fn parse_hash_range(s: &str) -> Result<Range<u32>, String> {
+    let (start, len) = s
+        .split_once(':')
+        .ok_or_else(|| "should be in form start:len".to_string())?;
+    let start = parse_u32(start).map_err(|e| e.to_string())?;
+    let len = parse_u32(len).map_err(|e| e.to_string())?;
+    let end = start
+        .checked_add(len)
+        .ok_or_else(|| "range end overflows".to_string())?;
+    Ok(start..end)
+}
+
+fn parse_sha256(s: &str) -> Result<String, String> {
+    if s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit()) {
+        Ok(s.to_ascii_lowercase())
+    } else {
+        Err("should be 64 hexadecimal digits".to_string())
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Checksum {
+    Auto,
+    Sha256,
+    Crc32,
+    None,
+}
+
+impl Checksum {
+    fn kind(self) -> Option<ChecksumKind> {
+        match self {
+            Checksum::Auto => None,
+            Checksum::Sha256 => Some(ChecksumKind::Sha256),
+            Checksum::Crc32 => Some(ChecksumKind::Crc32),
+            Checksum::None => Some(ChecksumKind::None),
+        }
+    }
 }
 
 #[derive(Args)]
@@ -69,8 +231,91 @@ fn main() {
     match args.command {
         Commands::Patch(patch) => {
             let input_path = &patch.input;
-            let output_path = patch.output.as_ref().unwrap_or(&input_path);
-            patch_image(input_path, output_path);
+            let output_path = match (patch.output, patch.format) {
+                (Some(output), _) => output,
+                (None, Format::Bin) => input_path.clone(),
+                (None, Format::Ihex) => input_path.with_extension("hex"),
+                (None, Format::Srec) => input_path.with_extension("srec"),
+            };
+            let profile = match &patch.profile {
+                Some(path) => match BoardProfile::load(path) {
+                    Ok(profile) => profile,
+                    Err(e) => {
+                        print_patch_error(e);
+                        std::process::exit(1);
+                    }
+                },
+                None => BoardProfile::default(),
+            };
+            let metadata = match patch.metadata.map(fs::read).transpose() {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    print_patch_error(e.into());
+                    std::process::exit(1);
+                }
+            };
+            let options = PatchOptions {
+                checksum: patch.checksum.kind(),
+                family: patch.chip.family(),
+                crystal: patch.crystal.or(profile.crystal),
+                flash_caps: patch
+                    .flash_caps
+                    .map(FlashCaps::width)
+                    .or(profile.flash_caps),
+                flash_clock_divider: patch.flash_clock_divider.or(profile.flash_clock_divider),
+                flash_read_command: patch.flash_read_command.or(profile.flash_read_command),
+                flash_read_dummy_cycles: patch
+                    .flash_read_dummy_cycles
+                    .or(profile.flash_read_dummy_cycles),
+                hash_range: patch.hash_range,
+                metadata,
+                pad: patch.pad,
+                pad_in_body: patch.pad_in_body,
+                verbose: patch.verbose,
+            };
+            if let Err(e) = patch_image(input_path, &output_path, &options) {
+                print_patch_error(e);
+                std::process::exit(1);
+            }
+            // Digest is only checked once patching succeeded, so that it is never
+            // computed over an input left untouched by a failed patch.
+            if let Some(expected) = &patch.expect_sha256 {
+                match verify_sha256(&output_path, expected) {
+                    Ok(true) => {}
+                    Ok(false) => std::process::exit(1),
+                    Err(e) => {
+                        print_patch_error(e);
+                        std::process::exit(1);
+                    }
+                }
+            }
+            // Likewise, a signature is only made over a successfully patched image.
+            if let Some(key_path) = &patch.sign
+                && let Err(e) =
+                    sign_output(&output_path, key_path, patch.sign_algorithm.algorithm())
+            {
+                print_patch_error(e);
+                std::process::exit(1);
+            }
+            if patch.format != Format::Bin {
+                let bytes = fs::read(&output_path).expect("read patched image");
+                let base_address = patch.base_address.unwrap_or_else(|| {
+                    let header = blri::parse(&mut Cursor::new(&bytes));
+                    match header.map(|h| h.load_address(patch.chip.family())) {
+                        Ok(Some(address)) => address,
+                        _ => {
+                            println!("warning: no load address in image header, using 0x0.");
+                            0
+                        }
+                    }
+                });
+                let text = match patch.format {
+                    Format::Ihex => blri::to_ihex(&bytes, base_address),
+                    Format::Srec => blri::to_srec(&bytes, base_address),
+                    Format::Bin => unreachable!(),
+                };
+                fs::write(&output_path, text).expect("write converted image");
+            }
         }
         Commands::Flash(flash) => {
             let port = use_or_select_flash_port(&flash.port);
@@ -86,7 +331,10 @@ fn main() {
             if elf2bin.patch {
                 // TODO: add a inner `patch_image` for bytes to patch the output
                 // TODO: binary before saving into file system.
-                patch_image(&output_path, &output_path);
+                if let Err(e) = patch_image(&output_path, &output_path, &PatchOptions::default()) {
+                    print_patch_error(e);
+                    std::process::exit(1);
+                }
             }
         }
     }
@@ -100,23 +348,84 @@ fn main() {
     } */
 }
 
-fn patch_image(input_path: impl AsRef<Path>, output_path: impl AsRef<Path>) {
-    let mut f_in = File::open(&input_path).expect("open input file");
+/// Options on how an image is checked and patched.
+#[derive(Default)]
+struct PatchOptions {
+    /// Checksum algorithm over image body, or `None` to decide by the image header.
+    checksum: Option<ChecksumKind>,
+    /// Chip family the image is built for.
+    family: ChipFamily,
+    /// New crystal oscillator frequency to write into the image.
+    crystal: Option<u32>,
+    /// Maximum flash I/O width supported by the board.
+    flash_caps: Option<IoWidth>,
+    /// New flash clock divider to write into the image.
+    flash_clock_divider: Option<u8>,
+    /// New flash read command to write into the image.
+    flash_read_command: Option<u8>,
+    /// New dummy cycles of flash read command.
+    flash_read_dummy_cycles: Option<u8>,
+    /// Range of file bytes to compute checksum over, or `None` for image body.
+    hash_range: Option<Range<u32>>,
+    /// Metadata bytes to append to image body.
+    metadata: Option<Vec<u8>>,
+    /// Flash sector size to pad image to a multiple of.
+    pad: Option<u32>,
+    /// Extend image body length over padding.
+    pad_in_body: bool,
+    /// Print header bytes changed by patching.
+    verbose: bool,
+}
 
-    let ops = match blri::check(&mut f_in) {
-        Ok(ops) => ops,
-        Err(e) => {
-            print_patch_error(e);
-            return;
-        }
+fn patch_image(
+    input_path: impl AsRef<Path>,
+    output_path: impl AsRef<Path>,
+    options: &PatchOptions,
+) -> Result<(), Error> {
+    let mut f_in = File::open(&input_path)?;
+
+    // Header errors are reported by the check below.
+    if let Ok(header) = blri::parse(&mut f_in)
+        && let Some(warning) = options
+            .flash_caps
+            .and_then(|supported| header.check_flash_caps(supported))
+    {
+        println!("warning: {warning}.");
+    }
+
+    let header_before = if options.verbose {
+        blri::read_header(&mut f_in).ok()
+    } else {
+        None
     };
 
+    let mut ops = blri::check_with_hash_range(
+        &mut f_in,
+        options.family,
+        options.checksum,
+        options.hash_range.clone(),
+    )?;
+    for warning in &ops.warnings {
+        println!("warning: {warning}.");
+    }
+
     // Copy the input file to output file, if those files are not the same.
     // If files are the same, the following operations will reuse the input file
     // as output file, avoiding creating new files.
     let same_file = same_file::is_same_file(&output_path, &input_path).unwrap_or_else(|_| false);
+    let modify_flash_read =
+        options.flash_read_command.is_some() || options.flash_read_dummy_cycles.is_some();
+    let modify_header = options.crystal.is_some()
+        || options.flash_clock_divider.is_some()
+        || modify_flash_read
+        || options.metadata.is_some()
+        || options.pad.is_some();
+    if ops.is_empty() && !modify_header && same_file {
+        println!("image is already valid, no changes");
+        return Ok(());
+    }
     if !same_file {
-        fs::copy(&input_path, &output_path).expect("copy input to output");
+        fs::copy(&input_path, &output_path)?;
     }
 
     // release input file
@@ -124,52 +433,95 @@ fn patch_image(input_path: impl AsRef<Path>, output_path: impl AsRef<Path>) {
 
     // open output file as writeable
     let mut f_out = File::options()
+        .read(true)
         .write(true)
         .create(true)
-        .open(&output_path)
-        .expect("open output file");
-
-    blri::process(&mut f_out, &ops).expect("process file");
-    println!("patched image saved to {}", output_path.as_ref().display());
-}
+        .open(&output_path)?;
 
-fn print_patch_error(e: Error) {
-    match e {
-        Error::MagicNumber { wrong_magic } => {
-            println!("error: incorrect magic number 0x{wrong_magic:08x}!");
-        }
-        Error::HeadLength { wrong_length } => {
-            println!(
-                "File is too short to include an image header, it only includes {wrong_length} bytes"
-            );
-        }
-        Error::FlashConfigMagic { wrong_magic } => {
-            println!("error: incorrect flash config magic 0x{wrong_magic:08x}!");
-        }
-        Error::ClockConfigMagic { wrong_magic } => {
-            println!("error: incorrect clock config magic 0x{wrong_magic:08x}!");
-        }
-        Error::ImageOffsetOverflow {
-            file_length,
-            wrong_image_offset,
-            wrong_image_length,
-        } => {
+    if let Some(frequency) = options.crystal {
+        blri::set_crystal(&mut f_out, frequency)?;
+    }
+    if let Some(divider) = options.flash_clock_divider {
+        blri::set_flash_clock_divider(&mut f_out, divider)?;
+    }
+    if modify_flash_read {
+        let old = blri::parse(&mut f_out).ok().and_then(|h| h.flash_read);
+        blri::set_flash_read(
+            &mut f_out,
+            options.flash_read_command,
+            options.flash_read_dummy_cycles,
+        )?;
+        let new = blri::parse(&mut f_out).ok().and_then(|h| h.flash_read);
+        if let (Some(old), Some(new)) = (old, new) {
             println!(
-                "error: file length is only {}, but offset is {} and image length is {}",
-                file_length, wrong_image_offset, wrong_image_length
+                "flash read command 0x{:02x} with {} dummy cycles, changed to 0x{:02x} with {} dummy cycles",
+                old.command, old.dummy_cycles, new.command, new.dummy_cycles
             );
         }
-        Error::Sha256Checksum { wrong_checksum } => {
-            let mut wrong_checksum_hex = String::new();
-            for i in wrong_checksum {
-                wrong_checksum_hex.push_str(&format!("{:02x}", i));
-            }
-            println!("error: wrong sha256 verification: {}.", wrong_checksum_hex);
-        }
-        Error::Io(source) => {
-            println!("error: io error! {:?}", source);
+    }
+    if let Some(metadata) = &options.metadata {
+        blri::append_metadata(&mut f_out, metadata)?;
+    }
+    if let Some(sector_size) = options.pad {
+        let length = blri::pad_image(&mut f_out, sector_size, options.pad_in_body)?;
+        println!("image padded to {length} (0x{length:x}) bytes");
+    }
+    if modify_header {
+        // Header is modified, check the image again.
+        ops = blri::check_with_hash_range(
+            &mut f_out,
+            options.family,
+            options.checksum,
+            options.hash_range.clone(),
+        )?;
+    }
+
+    blri::process(&mut f_out, &ops)?;
+    if let Some(before) = header_before {
+        let after = blri::read_header(&mut f_out)?;
+        for change in blri::header_diff(&before, &after) {
+            println!("{change}");
         }
     }
+    println!("patched image saved to {}", output_path.as_ref().display());
+    Ok(())
+}
+
+/// Compare SHA-256 digest of the image with expected value, returning whether they match.
+fn verify_sha256(path: impl AsRef<Path>, expected: &str) -> Result<bool, Error> {
+    let actual = blri::image_sha256(&mut File::open(&path)?)?;
+    let mut actual_hex = String::with_capacity(actual.len() * 2);
+    blri::write_hex(&mut actual_hex, &actual).unwrap();
+    if actual_hex != expected {
+        println!("error: sha256 mismatch!");
+        println!("expected: {expected}");
+        println!("actual:   {actual_hex}");
+        return Ok(false);
+    }
+    println!("sha256 matches: {actual_hex}");
+    Ok(true)
+}
+
+/// Sign the image with key in `key_path`, saving signature into a file with `.sig` appended
+/// to image file name.
+fn sign_output(path: &Path, key_path: &Path, algo: SignAlgorithm) -> Result<(), Error> {
+    let key = fs::read(key_path)?;
+    let bytes = fs::read(path)?;
+    let signature = blri::sign_image(&bytes, &key, algo)?;
+    let mut sig_path = path.as_os_str().to_owned();
+    sig_path.push(".sig");
+    fs::write(&sig_path, signature)?;
+    println!("signature saved to {}", Path::new(&sig_path).display());
+    Ok(())
+}
+
+fn print_patch_error(e: Error) {
+    println!("error: {e}.");
+    let mut source = std::error::Error::source(&e);
+    while let Some(cause) = source {
+        println!("caused by: {cause}.");
+        source = cause.source();
+    }
 }
 
 fn use_or_select_flash_port(port_parameter: &Option<String>) -> String {
@@ -191,7 +543,13 @@ fn flash_image(image: impl AsRef<Path>, port: &str, device_reset: bool) {
 
     let image_data = fs::read(image).expect("read image file");
     if image_data.len() > u16::MAX as usize {
-        println!("error: image too large.");
+        println!(
+            "error: image of {} (0x{:x}) bytes is too large, at most {} (0x{:x}) bytes.",
+            image_data.len(),
+            image_data.len(),
+            u16::MAX,
+            u16::MAX
+        );
         return;
     }
 
@@ -0,0 +1,93 @@
+use core::fmt::{self, Write};
+
+const RECORD_LENGTH: usize = 16;
+
+/// Write bytes as lowercase hexadecimal digits without allocating.
+///
+/// Only depends on `core`, so the same function can print a checksum to any
+/// `fmt::Write` sink, for example a serial console on the device.
+pub fn write_hex(buf: &mut impl fmt::Write, bytes: &[u8]) -> fmt::Result {
+    for byte in bytes {
+        write!(buf, "{byte:02x}")?;
+    }
+    Ok(())
+}
+
+/// Display a number in decimal followed by hexadecimal, e.g. `4096 (0x1000)`.
+pub(crate) struct DecHex<T>(pub T);
+
+impl<T: fmt::Display + fmt::LowerHex> fmt::Display for DecHex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (0x{:x})", self.0, self.0)
+    }
+}
+
+/// Display bytes as lowercase hexadecimal digits.
+pub(crate) struct Hex<'a>(pub &'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_hex(f, self.0)
+    }
+}
+
+/// Convert image bytes into Intel HEX format, placing the first byte at `base_addr`.
+///
+/// Data records never cross a 64 KiB boundary; an extended linear address record is
+/// emitted before the first record of each 64 KiB segment.
+pub fn to_ihex(bytes: &[u8], base_addr: u32) -> String {
+    let mut ans = String::new();
+    let mut upper_addr = None;
+    let mut rest = bytes;
+    let mut addr = base_addr;
+    while !rest.is_empty() {
+        // Emit extended linear address record when upper 16 bits change.
+        if upper_addr != Some(addr >> 16) {
+            upper_addr = Some(addr >> 16);
+            write_ihex_record(&mut ans, 0, 0x04, &((addr >> 16) as u16).to_be_bytes());
+        }
+        let to_boundary = 0x1_0000 - (addr & 0xFFFF) as usize;
+        let (chunk, next) = rest.split_at(rest.len().min(RECORD_LENGTH).min(to_boundary));
+        write_ihex_record(&mut ans, addr as u16, 0x00, chunk);
+        addr = addr.wrapping_add(chunk.len() as u32);
+        rest = next;
+    }
+    write_ihex_record(&mut ans, 0, 0x01, &[]);
+    ans
+}
+
+/// Convert image bytes into Motorola S-record format, placing the first byte at `base_addr`.
+pub fn to_srec(bytes: &[u8], base_addr: u32) -> String {
+    let mut ans = String::new();
+    write_srec_record(&mut ans, '0', &[0, 0], &[]);
+    for (idx, chunk) in bytes.chunks(RECORD_LENGTH).enumerate() {
+        let addr = base_addr.wrapping_add((idx * RECORD_LENGTH) as u32);
+        write_srec_record(&mut ans, '3', &addr.to_be_bytes(), chunk);
+    }
+    write_srec_record(&mut ans, '7', &base_addr.to_be_bytes(), &[]);
+    ans
+}
+
+fn write_ihex_record(out: &mut String, addr: u16, record_type: u8, data: &[u8]) {
+    let addr = addr.to_be_bytes();
+    let head = [data.len() as u8, addr[0], addr[1], record_type];
+    let sum = head.iter().chain(data).fold(0u8, |a, b| a.wrapping_add(*b));
+    out.push(':');
+    for byte in head.iter().chain(data) {
+        write!(out, "{byte:02X}").unwrap();
+    }
+    writeln!(out, "{:02X}", sum.wrapping_neg()).unwrap();
+}
+
+fn write_srec_record(out: &mut String, record_type: char, addr: &[u8], data: &[u8]) {
+    let count = (addr.len() + data.len() + 1) as u8;
+    let sum = addr
+        .iter()
+        .chain(data)
+        .fold(count, |a, b| a.wrapping_add(*b));
+    write!(out, "S{record_type}{count:02X}").unwrap();
+    for byte in addr.iter().chain(data) {
+        write!(out, "{byte:02X}").unwrap();
+    }
+    writeln!(out, "{:02X}", !sum).unwrap();
+}
@@ -0,0 +1,41 @@
+use crate::{IoWidth, Result};
+use serde::Deserialize;
+use std::{fs, path::Path};
+
+/// Per-board image settings, loaded from a TOML board profile.
+///
+/// Every field is optional, and a field not given leaves the image unchanged.
+/// Keys are written in kebab case:
+///
+/// ```toml
+/// crystal = 40_000_000
+/// flash-caps = "quad"
+/// flash-clock-divider = 1
+/// flash-read-command = 0xEB
+/// flash-read-dummy-cycles = 6
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct BoardProfile {
+    /// Crystal oscillator frequency in Hertz, e.g. 40000000.
+    pub crystal: Option<u32>,
+    /// Maximum flash I/O width supported by the board.
+    pub flash_caps: Option<IoWidth>,
+    /// Flash clock divider in clock configuration, from 0 to 7.
+    pub flash_clock_divider: Option<u8>,
+    /// Read command of the flash I/O mode selected in header.
+    pub flash_read_command: Option<u8>,
+    /// Dummy cycles of the flash read command.
+    pub flash_read_dummy_cycles: Option<u8>,
+}
+
+impl BoardProfile {
+    /// Parse board profile from TOML text.
+    pub fn from_toml(text: &str) -> Result<Self> {
+        Ok(toml::from_str(text)?)
+    }
+    /// Read and parse board profile from file at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_toml(&fs::read_to_string(path)?)
+    }
+}
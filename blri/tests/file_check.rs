@@ -1,4 +1,4 @@
-use blri::Error;
+use blri::{ChecksumKind, ChipFamily, Error, IoWidth, Warning};
 use std::io::{Read, Seek, SeekFrom, Write};
 
 const CORRECT_IMAGE: &[u8; 4256] = include_bytes!("blinky-bl808.bin");
@@ -10,7 +10,7 @@ fn error_magic_number() {
     f.seek(SeekFrom::Start(0x00)).expect("seek to magic number");
     f.write_all(&[0x11, 0x22, 0x33, 0x44])
         .expect("prepare wrong magic number");
-    let res = blri::check(&mut f);
+    let res = blri::check(&mut f, ChipFamily::Bl808);
     if let Err(Error::MagicNumber { wrong_magic }) = res {
         assert_eq!(wrong_magic, 0x11223344);
     } else {
@@ -23,7 +23,7 @@ fn error_head_length() {
     let mut f = tempfile::tempfile().expect("create tempfile for test");
     f.write_all(CORRECT_IMAGE).expect("prepare correct image");
     f.set_len(0x123).expect("truncate file to 0x123");
-    let res = blri::check(&mut f);
+    let res = blri::check(&mut f, ChipFamily::Bl808);
     if let Err(Error::HeadLength { wrong_length }) = res {
         assert_eq!(wrong_length, 0x123)
     } else {
@@ -38,7 +38,7 @@ fn error_flash_config_magic() {
     f.seek(SeekFrom::Start(0x08)).expect("seek to flash magic");
     f.write_all(&[0x55, 0x66, 0x77, 0x88])
         .expect("prepare wrong flash magic number");
-    let res = blri::check(&mut f);
+    let res = blri::check(&mut f, ChipFamily::Bl808);
     if let Err(Error::FlashConfigMagic { wrong_magic }) = res {
         assert_eq!(wrong_magic, 0x55667788)
     } else {
@@ -53,7 +53,7 @@ fn error_clock_config_magic() {
     f.seek(SeekFrom::Start(0x64)).expect("seek to clock magic");
     f.write_all(&[0x22, 0x33, 0x10, 0x37])
         .expect("prepare wrong clock magic number");
-    let res = blri::check(&mut f);
+    let res = blri::check(&mut f, ChipFamily::Bl808);
     if let Err(Error::ClockConfigMagic { wrong_magic }) = res {
         assert_eq!(wrong_magic, 0x22331037)
     } else {
@@ -66,7 +66,7 @@ fn error_image_offset_overflow() {
     let mut f = tempfile::tempfile().expect("create tempfile for test");
     f.write_all(CORRECT_IMAGE).expect("prepare correct image");
     f.set_len(0x1037).expect("truncate file to 0x1037");
-    let res = blri::check(&mut f);
+    let res = blri::check(&mut f, ChipFamily::Bl808);
     if let Err(Error::ImageOffsetOverflow {
         file_length,
         wrong_image_offset,
@@ -95,7 +95,7 @@ fn error_sha256_checksum() {
     f.seek(SeekFrom::Start(0x90))
         .expect("seek to checksum offset after read");
     f.write_all(&buf).expect("prepare wrong sha256 sum");
-    let res = blri::check(&mut f);
+    let res = blri::check(&mut f, ChipFamily::Bl808);
     if let Err(Error::Sha256Checksum { wrong_checksum }) = res {
         assert_eq!(wrong_checksum, buf);
         assert_ne!(wrong_checksum.as_slice(), old_checksum);
@@ -103,3 +103,428 @@ fn error_sha256_checksum() {
         panic!("this test case should raise Sha256Sum error")
     }
 }
+
+#[test]
+fn checksum_kind_none() {
+    let mut f = tempfile::tempfile().expect("create tempfile for test");
+    f.write_all(CORRECT_IMAGE).expect("prepare correct image");
+    f.seek(SeekFrom::Start(0x90))
+        .expect("seek to checksum offset");
+    f.write_all(&[0x11; 32]).expect("prepare wrong sha256 sum");
+    let ops = blri::check_with_checksum(&mut f, ChipFamily::Bl808, Some(ChecksumKind::None))
+        .expect("image without body checksum should pass");
+    assert!(ops.refill_hash.is_none());
+    assert!(ops.refill_header_crc.is_some());
+}
+
+#[test]
+fn checksum_kind_crc32() {
+    let mut f = tempfile::tempfile().expect("create tempfile for test");
+    f.write_all(CORRECT_IMAGE).expect("prepare correct image");
+    let res = blri::check_with_checksum(&mut f, ChipFamily::Bl808, Some(ChecksumKind::Crc32));
+    assert!(matches!(res, Err(Error::Crc32Checksum { .. })));
+
+    f.seek(SeekFrom::Start(0x90))
+        .expect("seek to checksum offset");
+    f.write_all(&[0xef, 0xbe, 0xad, 0xde])
+        .expect("prepare placeholder");
+    f.write_all(&[0; 28]).expect("prepare placeholder");
+    let ops = blri::check_with_checksum(&mut f, ChipFamily::Bl808, Some(ChecksumKind::Crc32))
+        .expect("placeholder should be refilled");
+    let crc = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC).checksum(&CORRECT_IMAGE[0x1000..0x10a0]);
+    assert_eq!(ops.refill_hash, Some(Vec::from(crc.to_le_bytes())));
+}
+
+#[test]
+fn warning_entry_out_of_range() {
+    let mut f = tempfile::tempfile().expect("create tempfile for test");
+    f.write_all(CORRECT_IMAGE).expect("prepare correct image");
+    let header = blri::parse(&mut f).expect("parse correct image");
+    assert!(header.cpu_configs[0].enabled);
+    assert_eq!(header.cpu_configs[0].boot_entry, 0x58000000);
+    assert_eq!(header.check_boot_entries(ChipFamily::Bl808), None);
+
+    f.seek(SeekFrom::Start(0xC0)).expect("seek to boot entry");
+    f.write_all(&0x58001000u32.to_le_bytes())
+        .expect("prepare wrong boot entry");
+    let header = blri::parse(&mut f).expect("parse image header");
+    let warning = Warning::EntryOutOfRange {
+        entry: 0x58001000,
+        range: 0x58000000..0x580000a0,
+    };
+    assert_eq!(
+        header.check_boot_entries(ChipFamily::Bl808),
+        Some(warning.clone())
+    );
+    // Entry point is only a warning, the image is still patched.
+    let ops = blri::check(&mut f, ChipFamily::Bl808).expect("check image with wrong entry");
+    assert_eq!(ops.warnings, vec![warning]);
+}
+
+/// Header of a BL616 image, with one 16-byte core configuration booting from XIP base 0xA0000000.
+fn bl616_image() -> Vec<u8> {
+    let mut image = CORRECT_IMAGE.to_vec();
+    let mut cpu_config = [0u8; 0x18];
+    cpu_config[0x00] = 1;
+    cpu_config[0x08..0x0C].copy_from_slice(&0xA0000000u32.to_le_bytes());
+    image[0xB0..0xC8].copy_from_slice(&cpu_config);
+    let header_crc = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC).checksum(&image[..0x15C]);
+    image[0x15C..0x160].copy_from_slice(&header_crc.to_le_bytes());
+    image
+}
+
+#[test]
+fn bl616_image_passes() {
+    let image = bl616_image();
+    let mut reader = std::io::Cursor::new(&image);
+    let header = blri::parse(&mut reader).expect("parse BL616 image");
+    assert_eq!(header.check_boot_entries(ChipFamily::Bl616), None);
+    let ops = blri::check(&mut reader, ChipFamily::Bl616).expect("check BL616 image");
+    assert!(ops.is_empty());
+    assert!(ops.warnings.is_empty());
+}
+
+#[test]
+fn warning_flash_caps() {
+    let mut f = tempfile::tempfile().expect("create tempfile for test");
+    f.write_all(CORRECT_IMAGE).expect("prepare correct image");
+    f.seek(SeekFrom::Start(0x0C))
+        .expect("seek to flash io mode");
+    f.write_all(&[0x04]).expect("prepare quad io mode");
+    let header = blri::parse(&mut f).expect("parse image header");
+    assert_eq!(
+        header.check_flash_caps(IoWidth::Dual),
+        Some(Warning::FlashIoWidth {
+            requested: IoWidth::Quad,
+            supported: IoWidth::Dual,
+        })
+    );
+    assert_eq!(header.check_flash_caps(IoWidth::Quad), None);
+}
+
+#[test]
+fn valid_image_no_operations() {
+    let mut f = tempfile::tempfile().expect("create tempfile for test");
+    f.write_all(CORRECT_IMAGE).expect("prepare correct image");
+    let ops = blri::check(&mut f, ChipFamily::Bl808).expect("check correct image");
+    assert!(ops.is_empty());
+    assert!(ops.warnings.is_empty());
+}
+
+#[test]
+fn flash_clock_divider() {
+    let mut f = tempfile::tempfile().expect("create tempfile for test");
+    f.write_all(CORRECT_IMAGE).expect("prepare correct image");
+    assert_eq!(blri::parse(&mut f).unwrap().flash_clock_divider, 0);
+
+    blri::set_flash_clock_divider(&mut f, 3).expect("set flash clock divider");
+    assert_eq!(blri::parse(&mut f).unwrap().flash_clock_divider, 3);
+    let ops = blri::check(&mut f, ChipFamily::Bl808).expect("check patched image");
+    // Clock configuration is covered by its own CRC32 value, thus header CRC32 stays valid.
+    assert!(ops.is_empty());
+
+    let res = blri::set_flash_clock_divider(&mut f, 8);
+    assert!(matches!(
+        res,
+        Err(Error::FlashClockDivider { wrong_divider: 8 })
+    ));
+
+    f.seek(SeekFrom::Start(0x76))
+        .expect("seek to flash clock divider");
+    f.write_all(&[0x10])
+        .expect("prepare wrong flash clock divider");
+    let res = blri::check(&mut f, ChipFamily::Bl808);
+    assert!(matches!(
+        res,
+        Err(Error::FlashClockDivider {
+            wrong_divider: 0x10
+        })
+    ));
+}
+
+#[test]
+fn crystal() {
+    let mut f = tempfile::tempfile().expect("create tempfile for test");
+    f.write_all(CORRECT_IMAGE).expect("prepare correct image");
+    blri::set_crystal(&mut f, 40_000_000).expect("set crystal");
+    let mut crystal_type = [0u8];
+    f.seek(SeekFrom::Start(0x68)).expect("seek to crystal type");
+    f.read_exact(&mut crystal_type).unwrap();
+    assert_eq!(crystal_type, [4]);
+    assert!(blri::check(&mut f, ChipFamily::Bl808).unwrap().is_empty());
+
+    let res = blri::set_crystal(&mut f, 12_000_000);
+    assert!(matches!(
+        res,
+        Err(Error::CrystalFrequency {
+            wrong_frequency: 12_000_000
+        })
+    ));
+}
+
+#[test]
+fn header_diff() {
+    let mut f = tempfile::tempfile().expect("create tempfile for test");
+    f.write_all(CORRECT_IMAGE).expect("prepare correct image");
+    let before = blri::read_header(&mut f).expect("read header");
+    assert_eq!(before.len(), 0x160);
+    assert!(blri::header_diff(&before, &before).is_empty());
+
+    blri::set_flash_clock_divider(&mut f, 3).expect("set flash clock divider");
+    let after = blri::read_header(&mut f).expect("read patched header");
+    let changes = blri::header_diff(&before, &after);
+    assert_eq!(
+        changes[0],
+        blri::ByteChange {
+            offset: 0x76,
+            old: 0,
+            new: 3
+        }
+    );
+    assert_eq!(changes[0].to_string(), "0x0076: 0x00 -> 0x03");
+    // The rest are clock configuration CRC32 bytes.
+    assert!(
+        changes[1..]
+            .iter()
+            .all(|c| (0x7c..0x80).contains(&c.offset))
+    );
+}
+
+#[test]
+fn append_metadata() {
+    let mut f = tempfile::tempfile().expect("create tempfile for test");
+    f.write_all(CORRECT_IMAGE).expect("prepare correct image");
+    assert_eq!(blri::read_metadata(&mut f).unwrap(), None);
+
+    blri::append_metadata(&mut f, b"v1.0.0").expect("append metadata");
+    assert_eq!(blri::parse(&mut f).unwrap().image_body_length, 0xa0 + 6 + 8);
+    let ops = blri::check(&mut f, ChipFamily::Bl808).expect("check image with metadata");
+    assert!(ops.refill_hash.is_some());
+    blri::process(&mut f, &ops).expect("refill checksums");
+    assert!(blri::check(&mut f, ChipFamily::Bl808).unwrap().is_empty());
+    assert_eq!(
+        blri::read_metadata(&mut f).unwrap().as_deref(),
+        Some(&b"v1.0.0"[..])
+    );
+
+    // Appending again replaces the previous metadata.
+    blri::append_metadata(&mut f, b"v1.0.10").expect("replace metadata");
+    assert_eq!(blri::parse(&mut f).unwrap().image_body_length, 0xa0 + 7 + 8);
+    assert_eq!(f.metadata().unwrap().len(), 0x1000 + 0xa0 + 7 + 8);
+    assert_eq!(
+        blri::read_metadata(&mut f).unwrap().as_deref(),
+        Some(&b"v1.0.10"[..])
+    );
+}
+
+#[test]
+fn pad_image() {
+    let mut f = tempfile::tempfile().expect("create tempfile for test");
+    f.write_all(CORRECT_IMAGE).expect("prepare correct image");
+    let length = blri::pad_image(&mut f, 0x1000, false).expect("pad image");
+    assert_eq!(length, 0x2000);
+    assert_eq!(f.metadata().unwrap().len(), 0x2000);
+    // Padding is outside of image body, so the image still verifies.
+    assert_eq!(blri::parse(&mut f).unwrap().image_body_length, 0xa0);
+    assert!(blri::check(&mut f, ChipFamily::Bl808).unwrap().is_empty());
+    let mut padding = vec![0u8; 0x2000 - CORRECT_IMAGE.len()];
+    f.seek(SeekFrom::Start(CORRECT_IMAGE.len() as u64)).unwrap();
+    f.read_exact(&mut padding).unwrap();
+    assert!(padding.iter().all(|&byte| byte == 0xff));
+
+    // Already aligned image is left as is.
+    assert_eq!(blri::pad_image(&mut f, 0x400, false).unwrap(), 0x2000);
+
+    blri::pad_image(&mut f, 0x1000, true).expect("pad image into body");
+    assert_eq!(blri::parse(&mut f).unwrap().image_body_length, 0x1000);
+    let ops = blri::check(&mut f, ChipFamily::Bl808).expect("check padded image");
+    assert!(ops.refill_hash.is_some());
+    blri::process(&mut f, &ops).expect("refill checksums");
+    assert!(blri::check(&mut f, ChipFamily::Bl808).unwrap().is_empty());
+
+    let res = blri::pad_image(&mut f, 0, false);
+    assert!(matches!(res, Err(Error::PadSize { sector_size: 0 })));
+}
+
+#[test]
+fn image_sha256() {
+    use sha2::{Digest, Sha256};
+    let mut f = tempfile::tempfile().expect("create tempfile for test");
+    f.write_all(CORRECT_IMAGE).expect("prepare correct image");
+    let digest = blri::image_sha256(&mut f).expect("calculate image digest");
+    assert_eq!(digest, Sha256::digest(CORRECT_IMAGE).to_vec());
+}
+
+#[test]
+fn sign_image_hmac_sha256() {
+    use hmac::{Hmac, Mac};
+    let signature = blri::sign_image(CORRECT_IMAGE, b"secret", blri::SignAlgorithm::HmacSha256)
+        .expect("sign correct image");
+    let mut mac = Hmac::<sha2::Sha256>::new_from_slice(b"secret").unwrap();
+    mac.update(&CORRECT_IMAGE[..0x160]);
+    mac.update(&CORRECT_IMAGE[0x1000..0x10a0]);
+    assert_eq!(signature, mac.finalize().into_bytes().to_vec());
+
+    let res = blri::sign_image(
+        &CORRECT_IMAGE[..0x1037],
+        b"secret",
+        blri::SignAlgorithm::HmacSha256,
+    );
+    assert!(matches!(res, Err(Error::ImageOffsetOverflow { .. })));
+}
+
+#[test]
+fn header_field_byte_order() {
+    let mut f = tempfile::tempfile().expect("create tempfile for test");
+    f.write_all(CORRECT_IMAGE).expect("prepare correct image");
+    let header = blri::parse(&mut f).expect("parse BL808 image");
+    assert_eq!(header.group_image_offset, 0x1000);
+    assert_eq!(header.image_body_length, 0xa0);
+    assert_eq!(header.cpu_configs[0].image_address_offset, 0);
+    assert_eq!(header.cpu_configs[0].boot_entry, 0x58000000);
+}
+
+#[test]
+fn header_load_address() {
+    let mut f = tempfile::tempfile().expect("create tempfile for test");
+    f.write_all(CORRECT_IMAGE).expect("prepare correct image");
+    let mut header = blri::parse(&mut f).expect("parse BL808 image");
+    // Body is mapped at the start of flash window; header lies just below it.
+    assert_eq!(header.load_address(ChipFamily::Bl808), Some(0x57fff000));
+    assert_eq!(header.load_address(ChipFamily::Bl616), None);
+    header.cpu_configs[0].enabled = false;
+    assert_eq!(header.load_address(ChipFamily::Bl808), None);
+}
+
+#[test]
+fn flash_read_command() {
+    let mut f = tempfile::tempfile().expect("create tempfile for test");
+    f.write_all(CORRECT_IMAGE).expect("prepare correct image");
+    // I/O mode 0x11 selects dual output read with 32-bit address.
+    let header = blri::parse(&mut f).expect("parse correct image");
+    assert_eq!(
+        header.flash_read,
+        Some(blri::FlashRead {
+            command: 0x3b,
+            dummy_cycles: 1
+        })
+    );
+
+    blri::set_flash_read(&mut f, Some(0xbb), Some(2)).expect("set flash read command");
+    assert_eq!(
+        blri::parse(&mut f).unwrap().flash_read,
+        Some(blri::FlashRead {
+            command: 0xbb,
+            dummy_cycles: 2
+        })
+    );
+    // Flash configuration is covered by its own CRC32 value, thus header CRC32 stays valid.
+    assert!(blri::check(&mut f, ChipFamily::Bl808).unwrap().is_empty());
+
+    f.seek(SeekFrom::Start(0x0C))
+        .expect("seek to flash io mode");
+    f.write_all(&[0x0f]).expect("prepare unknown io mode");
+    let res = blri::set_flash_read(&mut f, None, Some(1));
+    assert!(matches!(res, Err(Error::FlashIoMode { wrong_mode: 0x0f })));
+}
+
+#[test]
+fn check_from_reader() {
+    let mut reader = std::io::Cursor::new(&CORRECT_IMAGE[..]);
+    let header = blri::parse(&mut reader).expect("parse image from reader");
+    assert_eq!(header.image_body_length, 0xa0);
+    assert!(
+        blri::check(&mut reader, ChipFamily::Bl808)
+            .unwrap()
+            .is_empty()
+    );
+
+    let mut corrupted = CORRECT_IMAGE.to_vec();
+    corrupted[0x1000] ^= 0xff;
+    let res = blri::check(&mut std::io::Cursor::new(corrupted), ChipFamily::Bl808);
+    assert!(matches!(res, Err(Error::Sha256Checksum { .. })));
+}
+
+#[test]
+fn hash_range_override() {
+    use sha2::{Digest, Sha256};
+    let mut image = CORRECT_IMAGE.to_vec();
+    // Configuration block after application, covered by a custom bootloader's hash.
+    image.extend_from_slice(&[0x5a; 0x40]);
+    let mut placeholder = [0u8; 32];
+    placeholder[..4].copy_from_slice(&[0xef, 0xbe, 0xad, 0xde]);
+    image[0x90..0xb0].copy_from_slice(&placeholder);
+
+    let mut reader = std::io::Cursor::new(&image);
+    let range = 0x1000..image.len() as u32;
+    let ops = blri::check_with_hash_range(&mut reader, ChipFamily::Bl808, None, Some(range))
+        .expect("check image with hash range");
+    let expected = Sha256::digest(&image[0x1000..]);
+    assert_eq!(ops.refill_hash.as_deref(), Some(&expected[..]));
+
+    let res =
+        blri::check_with_hash_range(&mut reader, ChipFamily::Bl808, None, Some(0x1000..0x2000));
+    assert!(matches!(
+        res,
+        Err(Error::HashRange {
+            file_length: 0x10e0,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn selective_operations() {
+    let mut f = tempfile::tempfile().expect("create tempfile for test");
+    f.write_all(CORRECT_IMAGE).expect("prepare correct image");
+    f.seek(SeekFrom::Start(0x15C))
+        .expect("seek to header crc32");
+    f.write_all(&[0; 4]).expect("prepare wrong header crc32");
+    f.seek(SeekFrom::Start(0x90)).expect("seek to hash");
+    f.write_all(&[0xef, 0xbe, 0xad, 0xde].repeat(8))
+        .expect("prepare placeholder hash");
+
+    let ops = blri::check(&mut f, ChipFamily::Bl808).expect("check broken image");
+    assert!(ops.refill_hash.is_some());
+    assert!(ops.refill_header_crc.is_some());
+
+    // Apply the hash fix only; header CRC32 stays wrong and is planned again.
+    let hash_only = blri::Operations {
+        refill_header_crc: None,
+        ..ops.clone()
+    };
+    blri::process(&mut f, &hash_only).expect("refill hash");
+    let remaining = blri::check(&mut f, ChipFamily::Bl808).expect("check partially fixed image");
+    assert_eq!(remaining.refill_hash, None);
+    assert_eq!(remaining.refill_header_crc, ops.refill_header_crc);
+
+    blri::process(&mut f, &remaining).expect("refill header crc32");
+    assert_eq!(
+        blri::check(&mut f, ChipFamily::Bl808).unwrap(),
+        blri::Operations::default()
+    );
+}
+
+#[test]
+fn board_profile() {
+    let profile = blri::BoardProfile::from_toml(
+        "flash-caps = \"dual\"\nflash-read-command = 0xBB\nflash-read-dummy-cycles = 4\n",
+    )
+    .expect("parse board profile");
+    assert_eq!(
+        profile,
+        blri::BoardProfile {
+            crystal: None,
+            flash_caps: Some(IoWidth::Dual),
+            flash_clock_divider: None,
+            flash_read_command: Some(0xBB),
+            flash_read_dummy_cycles: Some(4),
+        }
+    );
+
+    let profile = blri::BoardProfile::from_toml("crystal = 38_400_000\n").unwrap();
+    assert_eq!(profile.crystal, Some(38_400_000));
+
+    let res = blri::BoardProfile::from_toml("flash-size = 16\n");
+    assert!(matches!(res, Err(Error::Profile(_))));
+}
@@ -0,0 +1,63 @@
+#[test]
+fn ihex_records() {
+    let hex = blri::to_ihex(&[0x01, 0x02], 0);
+    assert_eq!(hex, ":020000040000FA\n:020000000102FB\n:00000001FF\n");
+
+    let bytes: Vec<u8> = (0..0x20).collect();
+    let hex = blri::to_ihex(&bytes, 0x5800_fff0);
+    let lines: Vec<&str> = hex.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[0], ":020000045800A2");
+    assert!(lines[1].starts_with(":10FFF000000102"));
+    assert_eq!(lines[2], ":020000045801A1");
+    assert!(lines[3].starts_with(":1000000010111213"));
+    assert_eq!(lines[4], ":00000001FF");
+
+    // Record starting 8 bytes below a 64 KiB boundary is split there.
+    let hex = blri::to_ihex(&bytes[..0x10], 0x5800_fff8);
+    let lines: Vec<&str> = hex.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[0], ":020000045800A2");
+    assert!(lines[1].starts_with(":08FFF8000001020304050607"));
+    assert_eq!(lines[2], ":020000045801A1");
+    assert!(lines[3].starts_with(":0800000008090A0B0C0D0E0F"));
+    assert_eq!(lines[4], ":00000001FF");
+}
+
+#[test]
+fn srec_records() {
+    let srec = blri::to_srec(&[0x01, 0x02], 0);
+    assert_eq!(srec, "S0030000FC\nS307000000000102F5\nS70500000000FA\n");
+}
+
+#[test]
+fn hex_string() {
+    let mut s = String::new();
+    blri::write_hex(&mut s, &[0x00, 0x5a, 0xef]).unwrap();
+    assert_eq!(s, "005aef");
+}
+
+#[test]
+fn error_display_decimal_and_hex() {
+    let e = blri::Error::ImageOffsetOverflow {
+        file_length: 0x1037,
+        wrong_image_offset: 0x1000,
+        wrong_image_length: 0xa0,
+    };
+    assert_eq!(
+        e.to_string(),
+        "Image offset overflow, offset 4096 (0x1000) and length 160 (0xa0) expected, but file length is 4151 (0x1037)"
+    );
+}
+
+#[test]
+fn error_io_source() {
+    use std::error::Error as _;
+    let e = blri::Error::from(std::io::Error::from(std::io::ErrorKind::UnexpectedEof));
+    assert_eq!(e.to_string(), "I/O error");
+    let source = e.source().expect("io error source");
+    assert_eq!(
+        source.downcast_ref::<std::io::Error>().map(|e| e.kind()),
+        Some(std::io::ErrorKind::UnexpectedEof)
+    );
+}
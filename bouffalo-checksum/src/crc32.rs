@@ -0,0 +1,79 @@
+/// Lookup table of reflected polynomial 0xedb88320.
+const TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Incremental CRC-32 (ISO-HDLC) calculation, as used in boot header fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Crc32 {
+    state: u32,
+}
+
+impl Crc32 {
+    /// Start a new calculation.
+    #[inline]
+    pub const fn new() -> Self {
+        Crc32 { state: 0xffff_ffff }
+    }
+    /// Feed `data` into calculation.
+    #[inline]
+    pub const fn update(&mut self, data: &[u8]) {
+        let mut i = 0;
+        while i < data.len() {
+            let index = (self.state ^ data[i] as u32) & 0xff;
+            self.state = (self.state >> 8) ^ TABLE[index as usize];
+            i += 1;
+        }
+    }
+    /// Get CRC-32 value of all data fed so far.
+    #[inline]
+    pub const fn finalize(&self) -> u32 {
+        !self.state
+    }
+}
+
+impl Default for Crc32 {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Calculate CRC-32 (ISO-HDLC) value of `data`.
+#[inline]
+pub const fn crc32(data: &[u8]) -> u32 {
+    let mut crc = Crc32::new();
+    crc.update(data);
+    crc.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Crc32, crc32};
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(&[]), 0);
+        let mut crc = Crc32::new();
+        crc.update(b"1234");
+        crc.update(b"56789");
+        assert_eq!(crc.finalize(), 0xcbf4_3926);
+    }
+}
@@ -0,0 +1,12 @@
+//! Image checksum algorithms shared by host tools and firmware.
+//!
+//! Bouffalo boot headers protect configuration and image body with CRC-32 and SHA-256.
+//! This crate computes both in pure Rust without allocation, so that `blri` on the host
+//! and over-the-air update code on the chip produce the same bytes from the same image.
+#![no_std]
+
+mod crc32;
+mod sha256;
+
+pub use crc32::{Crc32, crc32};
+pub use sha256::Sha256;
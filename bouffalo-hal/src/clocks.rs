@@ -1,5 +1,12 @@
 //! System-on-Chip clock configuration.
 
+#[cfg(feature = "bl808")]
+use crate::glb::mm::{self, CpuClockSource, CpuRootClockSource};
+#[cfg(feature = "uart")]
+use crate::uart::ConfigError;
+use core::fmt;
+#[cfg(feature = "uart")]
+use embedded_time::rate::Baud;
 use embedded_time::rate::Hertz;
 
 /// Clock settings for current chip.
@@ -7,17 +14,207 @@ use embedded_time::rate::Hertz;
 pub struct Clocks {
     // todo: clock setting fields
     pub xtal: Hertz,
+    /// Clock frequency of each UART peripheral, or `None` to use the default clock source.
+    uart_clocks: [Option<Hertz>; 5],
+    /// Processor core clock frequency set by `reconfigure`, or `None` for the boot default.
+    cpu_clock: Option<Hertz>,
+}
+
+/// Processor core clock source of BL808 multimedia (D0) core.
+#[cfg(feature = "bl808")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CpuSource {
+    /// Crystal oscillator clock.
+    Xclk,
+    /// 240-MHz multiplexer PLL.
+    MuxPll240M,
+    /// 320-MHz multiplexer PLL.
+    MuxPll320M,
+    /// 400-MHz CPU multiplexer PLL.
+    CpuPll400M,
+}
+
+/// Runtime clock configuration, used for dynamic frequency scaling.
+#[cfg(feature = "bl808")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClockConfig {
+    /// Source of processor core clock.
+    pub cpu_source: CpuSource,
+    /// Requested processor core clock frequency, divided from source clock.
+    pub cpu_frequency: Hertz,
+}
+
+/// Errors on runtime clock configuration.
+#[cfg(feature = "bl808")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClockError {
+    /// Requested frequency is not an integer division of source clock by 1 to 256.
+    CpuFrequency,
+}
+
+/// Snapshot of clock frequencies computed from [`Clocks`].
+///
+/// Its `Display` implementation prints a tree of clocks, useful in boot logs:
+///
+/// ```text
+/// xclk 40 MHz
+/// ├─ cpu 320 MHz
+/// ├─ uart0 80 MHz
+/// ...
+/// └─ mtime 1 MHz
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClockReport {
+    /// Crystal oscillator clock.
+    pub xclk: Hertz,
+    /// Processor core clock.
+    pub cpu: Hertz,
+    /// Clock of each UART peripheral, `None` if its source is unknown.
+    pub uart: [Option<Hertz>; 5],
+    /// Serial Peripheral Interface source clock.
+    pub spi: Hertz,
+    /// Inter-Integrated Circuit source clock.
+    pub i2c: Hertz,
+    /// Pulse Width Modulation source clock.
+    pub pwm: Hertz,
+    /// Machine timer tick frequency.
+    pub mtime: Hertz,
+}
+
+impl fmt::Display for ClockReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "xclk {}", DisplayHertz(self.xclk))?;
+        writeln!(f, "├─ cpu {}", DisplayHertz(self.cpu))?;
+        for (i, uart) in self.uart.iter().enumerate() {
+            match uart {
+                Some(freq) => writeln!(f, "├─ uart{} {}", i, DisplayHertz(*freq))?,
+                None => writeln!(f, "├─ uart{} unknown", i)?,
+            }
+        }
+        writeln!(f, "├─ spi {}", DisplayHertz(self.spi))?;
+        writeln!(f, "├─ i2c {}", DisplayHertz(self.i2c))?;
+        writeln!(f, "├─ pwm {}", DisplayHertz(self.pwm))?;
+        write!(f, "└─ mtime {}", DisplayHertz(self.mtime))
+    }
+}
+
+/// Prints frequency in the largest unit that keeps it an integer.
+struct DisplayHertz(Hertz);
+
+impl fmt::Display for DisplayHertz {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let freq = self.0.0;
+        if freq != 0 && freq.is_multiple_of(1_000_000) {
+            write!(f, "{} MHz", freq / 1_000_000)
+        } else if freq != 0 && freq.is_multiple_of(1_000) {
+            write!(f, "{} kHz", freq / 1_000)
+        } else {
+            write!(f, "{} Hz", freq)
+        }
+    }
 }
 
 impl Clocks {
+    /// Clock settings with crystal oscillator frequency `xtal` and default clock sources.
+    #[inline]
+    pub const fn new(xtal: Hertz) -> Self {
+        Clocks {
+            xtal,
+            uart_clocks: [None; 5],
+            cpu_clock: None,
+        }
+    }
     /// Crystal oscillator clock frequency.
     #[inline]
     pub const fn xclk(&self) -> Hertz {
         self.xtal
     }
+    /// Processor core clock frequency.
+    ///
+    /// It is not read from hardware: unless recorded by `reconfigure`, a fixed boot
+    /// default of the chip is returned, which may not match every core of the chip.
+    #[inline]
+    pub const fn cpu_clock(&self) -> Hertz {
+        if let Some(freq) = self.cpu_clock {
+            return freq;
+        }
+        // todo: calculate from Clocks structure fields
+        if cfg!(feature = "bl602") {
+            Hertz(192_000_000)
+        } else if cfg!(feature = "bl702") {
+            Hertz(144_000_000)
+        } else {
+            Hertz(320_000_000)
+        }
+    }
+    /// Switch clock of BL808 multimedia (D0) core at runtime, e.g. to save power when idle.
+    ///
+    /// Only the D0 core is affected, through multimedia GLB `mm`. Call it from code running
+    /// on D0, as `self` then records the new frequency used by `cpu_clock` and `CycleDelay`.
+    /// PLLs are not reprogrammed: the core selects one of the fixed PLL outputs started by
+    /// boot code, or crystal oscillator, and divides it to the requested frequency.
+    ///
+    /// Processor runs from crystal oscillator while the source and divider are changed.
+    /// On success the new clock frequencies are returned, and `self` holds them as well.
+    /// Peripherals whose clocks are changed should be told to recompute their dividers,
+    /// e.g. with [`BlockingSerial::clocks_changed`](crate::uart::BlockingSerial::clocks_changed).
+    ///
+    /// ```ignore
+    /// let old = clocks.clone();
+    /// let report = clocks.reconfigure(&mm_glb, config)?;
+    /// serial.clocks_changed::<0>(&old, &clocks)?;
+    /// ```
+    #[cfg(feature = "bl808")]
+    #[inline]
+    pub fn reconfigure(
+        &mut self,
+        mm: &mm::RegisterBlock,
+        config: ClockConfig,
+    ) -> Result<ClockReport, ClockError> {
+        let source = match config.cpu_source {
+            CpuSource::Xclk => self.xtal.0,
+            CpuSource::MuxPll240M => 240_000_000,
+            CpuSource::MuxPll320M => 320_000_000,
+            CpuSource::CpuPll400M => 400_000_000,
+        };
+        let target = config.cpu_frequency.0;
+        if target == 0 || source % target != 0 || source / target > 256 {
+            return Err(ClockError::CpuFrequency);
+        }
+        let divide = (source / target - 1) as u8;
+        unsafe {
+            mm.cpu_config_0
+                .modify(|val| val.set_cpu_root_clock_source(CpuRootClockSource::Xclk));
+            mm.cpu_config_1
+                .modify(|val| val.set_cpu_clock_divide(divide));
+            let pll = match config.cpu_source {
+                CpuSource::Xclk => None,
+                CpuSource::MuxPll240M => Some(CpuClockSource::MuxPll240M),
+                CpuSource::MuxPll320M => Some(CpuClockSource::MuxPll320M),
+                CpuSource::CpuPll400M => Some(CpuClockSource::CpuPll400M),
+            };
+            if let Some(pll) = pll {
+                mm.cpu_config_0.modify(|val| {
+                    val.set_cpu_clock_source(pll)
+                        .set_cpu_root_clock_source(CpuRootClockSource::Pll)
+                });
+            }
+        }
+        self.cpu_clock = Some(Hertz(target));
+        Ok(self.report())
+    }
+    /// Set clock frequency of UART peripheral `I`, when it runs from a different clock source.
+    #[inline]
+    pub const fn with_uart_clock<const I: usize>(mut self, freq: Hertz) -> Self {
+        self.uart_clocks[I] = Some(freq);
+        self
+    }
     /// Universal Asynchronous Receiver/Transmitter clock frequency.
     #[inline]
     pub const fn uart_clock<const I: usize>(&self) -> Option<Hertz> {
+        if let Some(freq) = self.uart_clocks[I] {
+            return Some(freq);
+        }
         // todo: calculate from Clocks structure fields
         match I {
             0..=2 => Some(Hertz(80_000_000)),
@@ -25,4 +222,251 @@ impl Clocks {
             _ => unreachable!(),
         }
     }
+    /// Serial Peripheral Interface source clock frequency.
+    #[inline]
+    pub const fn spi_clock(&self) -> Hertz {
+        // todo: calculate from Clocks structure fields
+        Hertz(160_000_000)
+    }
+    /// Inter-Integrated Circuit source clock frequency.
+    ///
+    /// [`I2c::new`](crate::i2c::I2c::new) selects crystal oscillator divided by 256.
+    #[inline]
+    pub const fn i2c_clock(&self) -> Hertz {
+        Hertz(self.xtal.0 / 256)
+    }
+    /// Pulse Width Modulation source clock frequency.
+    ///
+    /// Only crystal oscillator source is supported by [`Channels::set_clock`](crate::pwm::Channels::set_clock).
+    #[inline]
+    pub const fn pwm_clock(&self) -> Hertz {
+        self.xtal
+    }
+    /// Machine timer (`mtime`) tick frequency.
+    #[inline]
+    pub const fn mtime_clock(&self) -> Hertz {
+        // todo: calculate from Clocks structure fields
+        Hertz(1_000_000)
+    }
+    /// Collect all computed clock frequencies, e.g. to print them on boot.
+    #[inline]
+    pub const fn report(&self) -> ClockReport {
+        ClockReport {
+            xclk: self.xclk(),
+            cpu: self.cpu_clock(),
+            uart: [
+                self.uart_clock::<0>(),
+                self.uart_clock::<1>(),
+                self.uart_clock::<2>(),
+                self.uart_clock::<3>(),
+                self.uart_clock::<4>(),
+            ],
+            spi: self.spi_clock(),
+            i2c: self.i2c_clock(),
+            pwm: self.pwm_clock(),
+            mtime: self.mtime_clock(),
+        }
+    }
+    /// Bit interval of UART peripheral `I` in clock cycles for given baudrate.
+    ///
+    /// The interval is rounded to nearest integer, which keeps baudrate error lower
+    /// than truncating when clock frequency is not a multiple of baudrate.
+    #[cfg(feature = "uart")]
+    #[inline]
+    pub const fn baud_interval<const I: usize>(&self, baud: Baud) -> Result<u16, ConfigError> {
+        let uart_clock = match self.uart_clock::<I>() {
+            Some(freq) => freq,
+            None => return Err(ConfigError::ClockSource),
+        };
+        if baud.0 == 0 {
+            return Err(ConfigError::BaudrateTooLow);
+        }
+        let interval = (uart_clock.0 as u64 + baud.0 as u64 / 2) / baud.0 as u64;
+        if interval > 65535 {
+            Err(ConfigError::BaudrateTooLow)
+        } else if interval < 1 {
+            Err(ConfigError::BaudrateTooHigh)
+        } else {
+            Ok(interval as u16)
+        }
+    }
+    /// Achievable baudrate of UART peripheral `I` closest to `requested`, and its error in parts per million.
+    ///
+    /// Error is positive if the actual baudrate is faster than requested. Serial links usually fail
+    /// when the error exceeds about 2%, i.e. 20 000 ppm.
+    #[cfg(feature = "uart")]
+    #[inline]
+    pub const fn baud_error<const I: usize>(
+        &self,
+        requested: Baud,
+    ) -> Result<(Baud, i32), ConfigError> {
+        let interval = match self.baud_interval::<I>(requested) {
+            Ok(interval) => interval as u64,
+            Err(e) => return Err(e),
+        };
+        let uart_clock = match self.uart_clock::<I>() {
+            Some(freq) => freq.0 as u64,
+            None => return Err(ConfigError::ClockSource),
+        };
+        let actual = (uart_clock + interval / 2) / interval;
+        let error_ppm = (actual as i64 - requested.0 as i64) * 1_000_000 / requested.0 as i64;
+        Ok((Baud(actual as u32), error_ppm as i32))
+    }
+}
+
+#[cfg(all(test, feature = "uart"))]
+mod tests {
+    use super::{ClockReport, Clocks};
+    use crate::uart::ConfigError;
+    use embedded_time::rate::{Baud, Hertz};
+
+    #[test]
+    fn clocks_baud_interval() {
+        let clocks = Clocks::new(Hertz(40_000_000));
+        assert_eq!(clocks.baud_interval::<0>(Baud(2_000_000)), Ok(40));
+        // 80 MHz / 115200 Bd = 694.44, rounded down.
+        assert_eq!(clocks.baud_interval::<0>(Baud(115_200)), Ok(694));
+        // 80 MHz / 921600 Bd = 86.81, rounded up.
+        assert_eq!(clocks.baud_interval::<0>(Baud(921_600)), Ok(87));
+        assert_eq!(
+            clocks.baud_interval::<0>(Baud(1_000)),
+            Err(ConfigError::BaudrateTooLow)
+        );
+        assert_eq!(
+            clocks.baud_interval::<0>(Baud(200_000_000)),
+            Err(ConfigError::BaudrateTooHigh)
+        );
+    }
+
+    #[cfg(feature = "bl808")]
+    #[test]
+    fn clocks_reconfigure() {
+        use super::{ClockConfig, ClockError, CpuSource};
+        use crate::glb::mm::{self, CpuClockSource, CpuRootClockSource};
+
+        let mut clocks = Clocks::new(Hertz(40_000_000));
+        let registers = [0u32; 2];
+        let mm = unsafe { &*(registers.as_ptr() as *const mm::RegisterBlock) };
+        let config = ClockConfig {
+            cpu_source: CpuSource::MuxPll320M,
+            cpu_frequency: Hertz(80_000_000),
+        };
+        let report = clocks.reconfigure(mm, config).unwrap();
+        assert_eq!(report.cpu, Hertz(80_000_000));
+        assert_eq!(clocks.cpu_clock(), Hertz(80_000_000));
+        let cpu_config_0 = mm.cpu_config_0.read();
+        assert_eq!(cpu_config_0.cpu_clock_source(), CpuClockSource::MuxPll320M);
+        assert_eq!(
+            cpu_config_0.cpu_root_clock_source(),
+            CpuRootClockSource::Pll
+        );
+        assert_eq!(mm.cpu_config_1.read().cpu_clock_divide(), 3);
+
+        let config = ClockConfig {
+            cpu_source: CpuSource::Xclk,
+            cpu_frequency: Hertz(40_000_000),
+        };
+        assert_eq!(clocks.reconfigure(mm, config), Ok(clocks.report()));
+        assert_eq!(
+            mm.cpu_config_0.read().cpu_root_clock_source(),
+            CpuRootClockSource::Xclk
+        );
+        assert_eq!(mm.cpu_config_1.read().cpu_clock_divide(), 0);
+
+        let config = ClockConfig {
+            cpu_source: CpuSource::CpuPll400M,
+            cpu_frequency: Hertz(300_000_000),
+        };
+        assert_eq!(
+            clocks.reconfigure(mm, config),
+            Err(ClockError::CpuFrequency)
+        );
+        assert_eq!(clocks.cpu_clock(), Hertz(40_000_000));
+    }
+
+    #[test]
+    fn clocks_uart_clock_per_instance() {
+        let clocks = Clocks::new(Hertz(40_000_000)).with_uart_clock::<1>(Hertz(40_000_000));
+        assert_eq!(clocks.uart_clock::<0>(), Some(Hertz(80_000_000)));
+        assert_eq!(clocks.uart_clock::<1>(), Some(Hertz(40_000_000)));
+        assert_eq!(clocks.baud_interval::<0>(Baud(2_000_000)), Ok(40));
+        // 40 MHz / 9600 Bd = 4166.67, rounded up.
+        assert_eq!(clocks.baud_interval::<1>(Baud(9_600)), Ok(4167));
+    }
+
+    #[test]
+    fn clocks_baud_error() {
+        let clocks = Clocks::new(Hertz(40_000_000));
+        assert_eq!(
+            clocks.baud_error::<0>(Baud(2_000_000)),
+            Ok((Baud(2_000_000), 0))
+        );
+        // 80 MHz / 87 = 919540 Bd, 0.22% slower than requested.
+        assert_eq!(
+            clocks.baud_error::<0>(Baud(921_600)),
+            Ok((Baud(919_540), -2235))
+        );
+        // 80 MHz / 694 = 115274 Bd, 0.06% faster than requested.
+        assert_eq!(
+            clocks.baud_error::<0>(Baud(115_200)),
+            Ok((Baud(115_274), 642))
+        );
+        assert_eq!(
+            clocks.baud_error::<0>(Baud(1_000)),
+            Err(ConfigError::BaudrateTooLow)
+        );
+    }
+
+    #[test]
+    fn clocks_report() {
+        let mut clocks = Clocks::new(Hertz(40_000_000)).with_uart_clock::<1>(Hertz(40_000_000));
+        // Processor clock is given, as its boot default differs between chips.
+        clocks.cpu_clock = Some(Hertz(320_000_000));
+        let report = clocks.report();
+        assert_eq!(
+            report,
+            ClockReport {
+                xclk: Hertz(40_000_000),
+                cpu: Hertz(320_000_000),
+                uart: [
+                    Some(Hertz(80_000_000)),
+                    Some(Hertz(40_000_000)),
+                    Some(Hertz(80_000_000)),
+                    Some(Hertz(160_000_000)),
+                    Some(Hertz(160_000_000)),
+                ],
+                spi: Hertz(160_000_000),
+                i2c: Hertz(156_250),
+                pwm: Hertz(40_000_000),
+                mtime: Hertz(1_000_000),
+            }
+        );
+        let mut buf = [0u8; 256];
+        let mut writer = Writer {
+            buf: &mut buf,
+            len: 0,
+        };
+        core::fmt::write(&mut writer, format_args!("{}", report)).unwrap();
+        let text = core::str::from_utf8(&writer.buf[..writer.len]).unwrap();
+        assert_eq!(
+            text,
+            "xclk 40 MHz\n├─ cpu 320 MHz\n├─ uart0 80 MHz\n├─ uart1 40 MHz\n\
+             ├─ uart2 80 MHz\n├─ uart3 160 MHz\n├─ uart4 160 MHz\n├─ spi 160 MHz\n\
+             ├─ i2c 156250 Hz\n├─ pwm 40 MHz\n└─ mtime 1 MHz"
+        );
+    }
+
+    struct Writer<'a> {
+        buf: &'a mut [u8],
+        len: usize,
+    }
+
+    impl core::fmt::Write for Writer<'_> {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            let end = self.len + s.len();
+            self.buf[self.len..end].copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
 }
@@ -349,12 +349,13 @@ impl FifoConfig1 {
 #[cfg(test)]
 mod tests {
     use super::RegisterBlock;
-    use core::mem::offset_of;
 
     #[test]
     fn struct_register_block_offset() {
-        assert_eq!(offset_of!(RegisterBlock, config), 0x00);
-        assert_eq!(offset_of!(RegisterBlock, fifo_config_0), 0x80);
-        assert_eq!(offset_of!(RegisterBlock, fifo_config_1), 0x84);
+        assert_offsets!(RegisterBlock {
+            config: 0x00,
+            fifo_config_0: 0x80,
+            fifo_config_1: 0x84,
+        });
     }
 }
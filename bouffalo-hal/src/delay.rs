@@ -0,0 +1,86 @@
+//! Busy-waiting delay by counting processor cycles.
+
+use crate::clocks::Clocks;
+use embedded_hal::delay::DelayNs;
+
+/// Delay provider spinning the processor for computed amount of cycles.
+///
+/// Cycle counts are calculated once from [`Clocks::cpu_clock`] when this structure is
+/// created. Unless set by `Clocks::reconfigure`, that frequency is a fixed boot default
+/// of the chip, which may differ from the actual clock of the core running the code;
+/// delays are then scaled by the same ratio. Interrupts and cache misses extend the
+/// delay as well, so use a hardware timer where timing matters.
+///
+/// ```ignore
+/// let mut delay = CycleDelay::new(&c);
+/// loop {
+///     led.toggle().ok();
+///     delay.delay_ms(500);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CycleDelay {
+    cpu_clock: u32,
+}
+
+impl CycleDelay {
+    /// Create a cycle delay from processor clock frequency in `clocks`.
+    #[inline]
+    pub const fn new(clocks: &Clocks) -> Self {
+        Self {
+            cpu_clock: clocks.cpu_clock().0,
+        }
+    }
+    /// Spin for given amount of processor cycles.
+    #[inline]
+    pub fn delay_cycles(&mut self, mut cycles: u64) {
+        while cycles > 0 {
+            let chunk = cycles.min(u32::MAX as u64);
+            #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+            riscv::asm::delay(chunk as u32);
+            #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+            for _ in 0..chunk {
+                core::hint::spin_loop();
+            }
+            cycles -= chunk;
+        }
+    }
+}
+
+/// Processor cycles for `time` in units of `1 / scale` seconds, rounded up.
+#[inline]
+const fn cycles_for(cpu_clock: u32, time: u32, scale: u64) -> u64 {
+    (cpu_clock as u64 * time as u64).div_ceil(scale)
+}
+
+impl DelayNs for CycleDelay {
+    #[inline]
+    fn delay_ns(&mut self, ns: u32) {
+        self.delay_cycles(cycles_for(self.cpu_clock, ns, 1_000_000_000));
+    }
+    #[inline]
+    fn delay_us(&mut self, us: u32) {
+        self.delay_cycles(cycles_for(self.cpu_clock, us, 1_000_000));
+    }
+    #[inline]
+    fn delay_ms(&mut self, ms: u32) {
+        self.delay_cycles(cycles_for(self.cpu_clock, ms, 1_000));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cycles_for;
+
+    #[test]
+    fn cycle_delay_cycles_for() {
+        assert_eq!(cycles_for(320_000_000, 1, 1_000), 320_000);
+        assert_eq!(cycles_for(320_000_000, 1, 1_000_000_000), 1);
+        assert_eq!(cycles_for(320_000_000, 3, 1_000_000_000), 1);
+        assert_eq!(cycles_for(320_000_000, 4, 1_000_000_000), 2);
+        assert_eq!(
+            cycles_for(320_000_000, u32::MAX, 1_000),
+            320_000 * u32::MAX as u64
+        );
+    }
+}
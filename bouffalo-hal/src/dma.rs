@@ -4,7 +4,10 @@ use core::ops::Deref;
 
 use volatile_register::{RO, RW, WO};
 
-use crate::glb;
+use crate::glb::{
+    self,
+    v2::{ClockGate, DmaClock},
+};
 
 /// Direct Memory Access peripheral registers.
 #[repr(C)]
@@ -855,10 +858,8 @@ impl<DMA: Deref<Target = RegisterBlock>> Dma<DMA> {
         channel_config: DmaChannelConfig,
         glb: &glb::v2::RegisterBlock,
     ) -> Self {
+        DmaClock::<I>::enable_clock(glb);
         unsafe {
-            glb.clock_config_0.modify(|val| val.enable_dma());
-            // TODO: more proper usage.
-            glb.clock_config_1.modify(|val| val.enable_dma::<I>());
             dma.global_config.modify(|val| val.enable_dma());
             dma.channels[channel as usize]
                 .config
@@ -1022,6 +1023,26 @@ impl<DMA: Deref<Target = RegisterBlock>> Dma<DMA> {
         }
         lli_count_used_offset as i32
     }
+    /// Link the last used linked list item back to the first one, making the transfer circular.
+    ///
+    /// `count` is the number of items used, as returned by `lli_reload`. Circular transfer
+    /// never ends by itself; call `stop` to end it.
+    #[inline]
+    pub fn lli_circular(&self, lli_pool: &mut [LliPool], count: usize) {
+        lli_pool[count - 1].next_lli = (&lli_pool[0] as *const LliPool) as u32;
+        unsafe {
+            self.dma.channels[self.channel as usize]
+                .linked_list_item
+                .write(lli_pool[0].next_lli);
+        }
+    }
+    /// Get current destination address, which is advanced by hardware during the transfer.
+    #[inline]
+    pub fn destination_address(&self) -> u32 {
+        self.dma.channels[self.channel as usize]
+            .destination_address
+            .read()
+    }
     /// Start DMA transfer.
     #[inline]
     pub fn start(&self) {
@@ -1049,6 +1070,13 @@ impl<DMA: Deref<Target = RegisterBlock>> Dma<DMA> {
             .read()
             .if_cplt_int_occurs(self.channel)
     }
+    /// Check if this channel is still enabled.
+    ///
+    /// Hardware disables the channel after the last linked list item is transferred.
+    #[inline]
+    pub fn is_busy(&self) -> bool {
+        self.dma.enabled_channels.read().is_ch_enabled(self.channel)
+    }
 }
 
 #[cfg(test)]
@@ -1059,39 +1087,44 @@ mod tests {
         Periph4Dma01, Periph4Dma2, RawError, RawTransferComplete, RegisterBlock,
         TransferCompleteClear, TransferCompleteState, TransferWidth,
     };
-    use core::mem::offset_of;
 
     #[test]
     fn struct_register_block_offset() {
-        assert_eq!(offset_of!(RegisterBlock, interrupts), 0x00);
-        assert_eq!(offset_of!(RegisterBlock, enabled_channels), 0x1c);
-        assert_eq!(offset_of!(RegisterBlock, soft_burst_request), 0x20);
-        assert_eq!(offset_of!(RegisterBlock, soft_single_request), 0x24);
-        assert_eq!(offset_of!(RegisterBlock, soft_last_burst_request), 0x28);
-        assert_eq!(offset_of!(RegisterBlock, soft_last_single_request), 0x2c);
-        assert_eq!(offset_of!(RegisterBlock, global_config), 0x30);
-        assert_eq!(offset_of!(RegisterBlock, channels), 0x100);
+        assert_offsets!(RegisterBlock {
+            interrupts: 0x00,
+            enabled_channels: 0x1c,
+            soft_burst_request: 0x20,
+            soft_single_request: 0x24,
+            soft_last_burst_request: 0x28,
+            soft_last_single_request: 0x2c,
+            global_config: 0x30,
+            channels: 0x100,
+        });
     }
 
     #[rustfmt::skip]
     #[test]
     fn struct_interrupt_registers_offset() {
-        assert_eq!(offset_of!(InterruptRegisters, global_state), 0x00);
-        assert_eq!(offset_of!(InterruptRegisters, transfer_complete_state), 0x04);
-        assert_eq!(offset_of!(InterruptRegisters, transfer_complete_clear), 0x08);
-        assert_eq!(offset_of!(InterruptRegisters, error_state), 0x0c);
-        assert_eq!(offset_of!(InterruptRegisters, error_clear), 0x10);
-        assert_eq!(offset_of!(InterruptRegisters, raw_transfer_complete), 0x14);
-        assert_eq!(offset_of!(InterruptRegisters, raw_error), 0x18);
+        assert_offsets!(InterruptRegisters {
+            global_state: 0x00,
+            transfer_complete_state: 0x04,
+            transfer_complete_clear: 0x08,
+            error_state: 0x0c,
+            error_clear: 0x10,
+            raw_transfer_complete: 0x14,
+            raw_error: 0x18,
+        });
     }
 
     #[test]
     fn struct_channel_registers_offset_size() {
-        assert_eq!(offset_of!(ChannelRegisters, source_address), 0x00);
-        assert_eq!(offset_of!(ChannelRegisters, destination_address), 0x04);
-        assert_eq!(offset_of!(ChannelRegisters, linked_list_item), 0x08);
-        assert_eq!(offset_of!(ChannelRegisters, control), 0x0c);
-        assert_eq!(offset_of!(ChannelRegisters, config), 0x10);
+        assert_offsets!(ChannelRegisters {
+            source_address: 0x00,
+            destination_address: 0x04,
+            linked_list_item: 0x08,
+            control: 0x0c,
+            config: 0x10,
+        });
         assert_eq!(core::mem::size_of::<ChannelRegisters>(), 0x100);
     }
 
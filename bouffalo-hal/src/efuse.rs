@@ -0,0 +1,154 @@
+//! Electronic fuse (eFuse) data, including chip identification.
+use volatile_register::RO;
+
+/// Electronic fuse data registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    _reserved0: [u8; 0x18],
+    /// Device information programmed in factory.
+    pub device_info: RO<DeviceInfo>,
+}
+
+/// Device information register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[repr(transparent)]
+pub struct DeviceInfo(u32);
+
+impl DeviceInfo {
+    const PACKAGE: u32 = 0x3 << 22;
+    const PSRAM: u32 = 0x3 << 24;
+    const FLASH: u32 = 0x7 << 26;
+    const REVISION: u32 = 0x7 << 29;
+
+    /// Get package variant code.
+    #[inline]
+    pub const fn package(self) -> u8 {
+        ((self.0 & Self::PACKAGE) >> 22) as u8
+    }
+    /// Get in-package pseudo static memory code, 0 if there is none.
+    #[inline]
+    pub const fn psram(self) -> u8 {
+        ((self.0 & Self::PSRAM) >> 24) as u8
+    }
+    /// Get in-package flash code, 0 if flash is external.
+    #[inline]
+    pub const fn flash(self) -> u8 {
+        ((self.0 & Self::FLASH) >> 26) as u8
+    }
+    /// Get silicon revision, 0 for the first revision.
+    #[inline]
+    pub const fn revision(self) -> u8 {
+        ((self.0 & Self::REVISION) >> 29) as u8
+    }
+}
+
+/// Chip family.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChipFamily {
+    /// BL602 and BL604 chip series.
+    Bl602,
+    /// BL702, BL704 and BL706 chip series.
+    Bl702,
+    /// BL616 and BL618 chip series.
+    Bl616,
+    /// BL808 chip.
+    Bl808,
+    /// No chip feature is selected when building this crate.
+    Unknown,
+}
+
+impl ChipFamily {
+    /// Chip family this crate is built for, selected by chip feature.
+    pub const CURRENT: ChipFamily = if cfg!(feature = "bl602") {
+        ChipFamily::Bl602
+    } else if cfg!(feature = "bl702") {
+        ChipFamily::Bl702
+    } else if cfg!(feature = "bl616") {
+        ChipFamily::Bl616
+    } else if cfg!(feature = "bl808") {
+        ChipFamily::Bl808
+    } else {
+        ChipFamily::Unknown
+    };
+}
+
+/// Identification of the running chip.
+///
+/// Meaning of package, flash and pseudo static memory codes differs across chip part
+/// numbers; refer to datasheet of the chip family.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChipInfo {
+    /// Chip family.
+    pub family: ChipFamily,
+    /// Silicon revision, 0 for the first revision.
+    pub revision: u8,
+    /// Package variant code.
+    pub package: u8,
+    /// In-package flash code, 0 if flash is external.
+    pub flash: u8,
+    /// In-package pseudo static memory code, 0 if there is none.
+    pub psram: u8,
+}
+
+/// Read identification of the running chip from eFuse device information.
+///
+/// Chip family is not recorded in eFuse device information; it is the family selected
+/// by chip feature, as code built for one family cannot start on another. A bootloader
+/// shared across families reads `family` to branch, and the other fields to tell part
+/// numbers of that family apart.
+#[inline]
+pub fn chip_info(efuse: &RegisterBlock) -> ChipInfo {
+    let info = efuse.device_info.read();
+    ChipInfo {
+        family: ChipFamily::CURRENT,
+        revision: info.revision(),
+        package: info.package(),
+        flash: info.flash(),
+        psram: info.psram(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChipFamily, ChipInfo, DeviceInfo, RegisterBlock, chip_info};
+    use crate::mock::MockRegisters;
+
+    #[test]
+    fn struct_register_block_offset() {
+        assert_offsets!(RegisterBlock { device_info: 0x18 });
+    }
+
+    #[test]
+    fn struct_device_info_functions() {
+        let val = DeviceInfo(0x0040_0000);
+        assert_eq!(val.package(), 1);
+        let val = DeviceInfo(0x0200_0000);
+        assert_eq!(val.psram(), 2);
+        let val = DeviceInfo(0x1c00_0000);
+        assert_eq!(val.flash(), 7);
+        let val = DeviceInfo(0x2000_0000);
+        assert_eq!(val.revision(), 1);
+        let val = DeviceInfo(0x003f_ffff);
+        assert_eq!(val.package(), 0);
+        assert_eq!(val.psram(), 0);
+        assert_eq!(val.flash(), 0);
+        assert_eq!(val.revision(), 0);
+    }
+
+    #[test]
+    fn read_chip_info() {
+        let mock = MockRegisters::<RegisterBlock>::new();
+        mock.write(|efuse| &efuse.device_info, 0x4580_0000);
+        let efuse = mock.registers();
+        assert_eq!(
+            chip_info(efuse),
+            ChipInfo {
+                family: ChipFamily::CURRENT,
+                revision: 2,
+                package: 2,
+                flash: 1,
+                psram: 1,
+            }
+        );
+    }
+}
@@ -126,25 +126,26 @@ pub struct TransmitControl(u32);
 #[cfg(test)]
 mod tests {
     use super::RegisterBlock;
-    use core::mem::offset_of;
 
     #[test]
     fn struct_register_block_offset() {
-        assert_eq!(offset_of!(RegisterBlock, mode), 0x00);
-        assert_eq!(offset_of!(RegisterBlock, interrupt_source), 0x04);
-        assert_eq!(offset_of!(RegisterBlock, interrupt_mask), 0x08);
-        assert_eq!(offset_of!(RegisterBlock, backed_gap), 0x0c);
-        assert_eq!(offset_of!(RegisterBlock, frame_length), 0x18);
-        assert_eq!(offset_of!(RegisterBlock, collision), 0x1c);
-        assert_eq!(offset_of!(RegisterBlock, transmit_buffer), 0x20);
-        assert_eq!(offset_of!(RegisterBlock, mii_mode), 0x28);
-        assert_eq!(offset_of!(RegisterBlock, mii_command), 0x2c);
-        assert_eq!(offset_of!(RegisterBlock, mii_address), 0x30);
-        assert_eq!(offset_of!(RegisterBlock, control_write), 0x34);
-        assert_eq!(offset_of!(RegisterBlock, control_read), 0x38);
-        assert_eq!(offset_of!(RegisterBlock, mii_state), 0x3c);
-        assert_eq!(offset_of!(RegisterBlock, mac_address), 0x40);
-        assert_eq!(offset_of!(RegisterBlock, hash), 0x48);
-        assert_eq!(offset_of!(RegisterBlock, transmit_control), 0x50);
+        assert_offsets!(RegisterBlock {
+            mode: 0x00,
+            interrupt_source: 0x04,
+            interrupt_mask: 0x08,
+            backed_gap: 0x0c,
+            frame_length: 0x18,
+            collision: 0x1c,
+            transmit_buffer: 0x20,
+            mii_mode: 0x28,
+            mii_command: 0x2c,
+            mii_address: 0x30,
+            control_write: 0x34,
+            control_read: 0x38,
+            mii_state: 0x3c,
+            mac_address: 0x40,
+            hash: 0x48,
+            transmit_control: 0x50,
+        });
     }
 }
@@ -66,7 +66,7 @@ impl CpuConfig0 {
     /// Get clock source for CPU.
     #[inline]
     pub const fn cpu_clock_source(self) -> CpuClockSource {
-        match (self.0 & Self::CPU_CLOCK_SELECT) >> 25 {
+        match (self.0 & Self::CPU_CLOCK_SELECT) >> 8 {
             0 => CpuClockSource::MuxPll240M,
             1 => CpuClockSource::MuxPll320M,
             _ => CpuClockSource::CpuPll400M,
@@ -75,12 +75,12 @@ impl CpuConfig0 {
     /// Set source for CPU root clock.
     #[inline]
     pub const fn set_cpu_root_clock_source(self, val: CpuRootClockSource) -> Self {
-        Self((self.0 & !Self::CPU_ROOT_CLOCK_SELECT) | ((val as u32) << 8))
+        Self((self.0 & !Self::CPU_ROOT_CLOCK_SELECT) | ((val as u32) << 11))
     }
     /// Get source for CPU root clock.
     #[inline]
     pub const fn cpu_root_clock_source(self) -> CpuRootClockSource {
-        match (self.0 & Self::CPU_ROOT_CLOCK_SELECT) >> 8 {
+        match (self.0 & Self::CPU_ROOT_CLOCK_SELECT) >> 11 {
             0 => CpuRootClockSource::Xclk,
             1 => CpuRootClockSource::Pll,
             _ => unreachable!(),
@@ -112,7 +112,15 @@ impl CpuConfig1 {
 mod tests {
     use crate::glb::mm::{CpuClockSource, CpuRootClockSource};
 
-    use super::{CpuConfig0, CpuConfig1};
+    use super::{CpuConfig0, CpuConfig1, RegisterBlock};
+
+    #[test]
+    fn struct_register_block_offset() {
+        assert_offsets!(RegisterBlock {
+            cpu_config_0: 0x00,
+            cpu_config_1: 0x04,
+        });
+    }
 
     #[test]
     fn struct_cpu_config0_functions() {
@@ -133,12 +141,12 @@ mod tests {
         config = CpuConfig0(0x0);
         config = config.set_cpu_clock_source(CpuClockSource::MuxPll320M);
         assert_eq!(config.0, 0x00000100);
-        assert_eq!(config.cpu_clock_source(), CpuClockSource::MuxPll240M);
+        assert_eq!(config.cpu_clock_source(), CpuClockSource::MuxPll320M);
 
         config = CpuConfig0(0x0);
         config = config.set_cpu_clock_source(CpuClockSource::CpuPll400M);
         assert_eq!(config.0, 0x00000200);
-        assert_eq!(config.cpu_clock_source(), CpuClockSource::MuxPll240M);
+        assert_eq!(config.cpu_clock_source(), CpuClockSource::CpuPll400M);
 
         config = CpuConfig0(0x0);
         config = config.set_cpu_root_clock_source(CpuRootClockSource::Xclk);
@@ -147,8 +155,8 @@ mod tests {
 
         config = CpuConfig0(0x0);
         config = config.set_cpu_root_clock_source(CpuRootClockSource::Pll);
-        assert_eq!(config.0, 0x00000100);
-        assert_eq!(config.cpu_root_clock_source(), CpuRootClockSource::Xclk);
+        assert_eq!(config.0, 0x00000800);
+        assert_eq!(config.cpu_root_clock_source(), CpuRootClockSource::Pll);
     }
 
     #[test]
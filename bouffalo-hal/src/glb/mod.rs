@@ -17,6 +17,8 @@ pub enum Pull {
 }
 
 /// Pin drive strength.
+///
+/// Higher strength gives faster edges; `Drive0` has the slowest edge rate.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u8)]
 pub enum Drive {
@@ -42,6 +42,12 @@ impl GpioConfig {
     const PULL: u32 = 0x3 << 4;
     const FUNCTION: u32 = 0x1f << 8;
 
+    /// Reset value of GPIO_CFGCTL register.
+    ///
+    /// Both pads have input and Schmitt trigger enabled, with software GPIO function.
+    #[allow(unused)]
+    pub(crate) const RESET_VALUE: Self = Self(0x0b03_0b03);
+
     /// Enable input function of current pin.
     #[inline]
     pub const fn enable_input(self, idx: usize) -> Self {
@@ -211,3 +217,22 @@ pub enum InterruptMode {
     AsyncLowLevel = 6,
     AsyncHighLevel = 7,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::RegisterBlock;
+
+    #[test]
+    fn struct_register_block_offset() {
+        assert_offsets!(RegisterBlock {
+            gpio_config: 0x100,
+            gpio_input_value: 0x180,
+            gpio_output_value: 0x188,
+            gpio_output_enable: 0x190,
+            gpio_interrupt_mask: 0x194,
+            gpio_interrupt_state: 0x1a8,
+            gpio_interrupt_clear: 0x1b0,
+            gpio_interrupt_mode: 0x1c0,
+        });
+    }
+}
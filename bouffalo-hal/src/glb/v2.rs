@@ -24,7 +24,10 @@ pub struct RegisterBlock {
     pub sdh_config: RW<SdhConfig>,
     _reserved5: [u8; 0xdd],
     pub param_config: RW<ParamConfig>,
-    _reserved6: [u8; 0x6c],
+    _reserved6: [u8; 0x30],
+    /// Peripheral software reset configuration 1.
+    pub software_reset_1: RW<SoftwareReset1>,
+    _reserved7: [u8; 0x38],
     /// Clock generation configuration 0.
     pub clock_config_0: RW<ClockConfig0>,
     /// Clock generation configuration 1.
@@ -33,16 +36,16 @@ pub struct RegisterBlock {
     pub clock_config_2: RW<ClockConfig2>,
     /// Clock generation configuration 3.
     pub clock_config_3: RW<ClockConfig3>,
-    _reserved7: [u8; 0x140],
+    _reserved8: [u8; 0x140],
     /// LDO12UHS config.
     pub ldo12uhs_config: RW<Ldo12uhsConfig>,
-    _reserved8: [u8; 0x1f0],
+    _reserved9: [u8; 0x1f0],
     /// Generic Purpose Input/Output config.
     pub gpio_config: [RW<GpioConfig>; 46],
-    _reserved9: [u8; 0x148],
+    _reserved10: [u8; 0x148],
     /// Read value from Generic Purpose Input/Output pads.
     pub gpio_input: [RO<u32>; 2],
-    _reserved10: [u8; 0x18],
+    _reserved11: [u8; 0x18],
     /// Write value to Generic Purpose Input/Output pads.
     pub gpio_output: [RW<u32>; 2],
     /// Set pin output value to high.
@@ -388,6 +391,116 @@ impl ParamConfig {
     }
 }
 
+/// Peripheral software reset configuration register 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[repr(transparent)]
+pub struct SoftwareReset1(u32);
+
+impl SoftwareReset1 {
+    const UART0: u32 = 0x1 << 16;
+    const UART1: u32 = 0x1 << 17;
+    const SPI0: u32 = 0x1 << 18;
+    const I2C: u32 = 0x1 << 19;
+    const PWM: u32 = 0x1 << 20;
+    const UART2: u32 = 0x1 << 26;
+
+    #[inline]
+    const fn uart_mask<const I: usize>() -> u32 {
+        match I {
+            0 => Self::UART0,
+            1 => Self::UART1,
+            2 => Self::UART2,
+            _ => unreachable!(),
+        }
+    }
+    /// Hold Universal Asynchronous Receiver/Transmitter `I` in reset.
+    #[inline]
+    pub const fn assert_uart<const I: usize>(self) -> Self {
+        Self(self.0 | Self::uart_mask::<I>())
+    }
+    /// Release Universal Asynchronous Receiver/Transmitter `I` from reset.
+    #[inline]
+    pub const fn deassert_uart<const I: usize>(self) -> Self {
+        Self(self.0 & !Self::uart_mask::<I>())
+    }
+    /// Check if Universal Asynchronous Receiver/Transmitter `I` is held in reset.
+    #[inline]
+    pub const fn is_uart_asserted<const I: usize>(self) -> bool {
+        self.0 & Self::uart_mask::<I>() != 0
+    }
+    /// Hold Serial Peripheral Interface peripheral 0 in reset.
+    #[inline]
+    pub const fn assert_spi(self) -> Self {
+        Self(self.0 | Self::SPI0)
+    }
+    /// Release Serial Peripheral Interface peripheral 0 from reset.
+    #[inline]
+    pub const fn deassert_spi(self) -> Self {
+        Self(self.0 & !Self::SPI0)
+    }
+    /// Check if Serial Peripheral Interface peripheral 0 is held in reset.
+    #[inline]
+    pub const fn is_spi_asserted(self) -> bool {
+        self.0 & Self::SPI0 != 0
+    }
+    /// Hold Inter-Integrated Circuit peripheral in reset.
+    #[inline]
+    pub const fn assert_i2c(self) -> Self {
+        Self(self.0 | Self::I2C)
+    }
+    /// Release Inter-Integrated Circuit peripheral from reset.
+    #[inline]
+    pub const fn deassert_i2c(self) -> Self {
+        Self(self.0 & !Self::I2C)
+    }
+    /// Check if Inter-Integrated Circuit peripheral is held in reset.
+    #[inline]
+    pub const fn is_i2c_asserted(self) -> bool {
+        self.0 & Self::I2C != 0
+    }
+    /// Hold Pulse Width Modulation peripheral in reset.
+    #[inline]
+    pub const fn assert_pwm(self) -> Self {
+        Self(self.0 | Self::PWM)
+    }
+    /// Release Pulse Width Modulation peripheral from reset.
+    #[inline]
+    pub const fn deassert_pwm(self) -> Self {
+        Self(self.0 & !Self::PWM)
+    }
+    /// Check if Pulse Width Modulation peripheral is held in reset.
+    #[inline]
+    pub const fn is_pwm_asserted(self) -> bool {
+        self.0 & Self::PWM != 0
+    }
+}
+
+impl RegisterBlock {
+    /// Reset Universal Asynchronous Receiver/Transmitter `I` by pulsing its software reset bit.
+    ///
+    /// All registers of the peripheral return to reset values; it should be configured again.
+    #[inline]
+    pub fn reset_uart<const I: usize>(&self) {
+        self.pulse_software_reset_1(
+            SoftwareReset1::assert_uart::<I>,
+            SoftwareReset1::deassert_uart::<I>,
+        );
+    }
+    /// Pulse one software reset bit: release it, hold the peripheral in reset, then release it again.
+    #[inline]
+    fn pulse_software_reset_1(
+        &self,
+        assert: fn(SoftwareReset1) -> SoftwareReset1,
+        deassert: fn(SoftwareReset1) -> SoftwareReset1,
+    ) {
+        unsafe {
+            self.software_reset_1.modify(deassert);
+            self.software_reset_1.modify(assert);
+            self.software_reset_1.modify(deassert);
+        }
+    }
+}
+
 /// SDH configuration register.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 #[repr(transparent)]
@@ -482,6 +595,7 @@ impl ClockConfig1 {
     const DMA0: u32 = 0x1 << 12;
     const UART0: u32 = 0x1 << 16;
     const UART1: u32 = 0x1 << 17;
+    const SPI0: u32 = 0x1 << 18;
     const I2C: u32 = 0x1 << 19;
     const PWM: u32 = 0x1 << 20;
     const DMA2: u32 = 0x1 << 24;
@@ -551,6 +665,39 @@ impl ClockConfig1 {
         };
         self.0 & val != 0
     }
+    /// Enable clock gate for Serial Peripheral Interface peripheral `I`.
+    ///
+    /// Only peripheral 0 is gated in this register; other peripherals are left unchanged.
+    #[inline]
+    pub const fn enable_spi<const I: usize>(self) -> Self {
+        match I {
+            0 => Self(self.0 | Self::SPI0),
+            1 => self,
+            _ => unreachable!(),
+        }
+    }
+    /// Disable clock gate for Serial Peripheral Interface peripheral `I`.
+    #[inline]
+    pub const fn disable_spi<const I: usize>(self) -> Self {
+        match I {
+            0 => Self(self.0 & !Self::SPI0),
+            1 => self,
+            _ => unreachable!(),
+        }
+    }
+    /// Check if clock gate for Serial Peripheral Interface peripheral 0 is enabled.
+    ///
+    /// Other peripherals are gated outside of this register, thus their state is not available.
+    #[inline]
+    pub const fn is_spi_enabled<const I: usize>(self) -> bool {
+        const {
+            assert!(
+                I == 0,
+                "only SPI 0 is gated in clock configuration register 1"
+            )
+        };
+        (self.0 & Self::SPI0) != 0
+    }
     /// Enable clock gate for Inter-Integrated Circuit peripheral.
     #[inline]
     pub const fn enable_i2c(self) -> Self {
@@ -598,6 +745,180 @@ impl ClockConfig1 {
     }
 }
 
+/// Peripheral block whose clock is gated in global configuration registers.
+///
+/// Drivers enable the clock when they are created and disable it when freed.
+pub trait ClockGate {
+    /// Enable clock gate for this peripheral.
+    fn enable_clock(glb: &RegisterBlock);
+    /// Disable clock gate for this peripheral.
+    fn disable_clock(glb: &RegisterBlock);
+    /// Check if clock gate for this peripheral is enabled.
+    ///
+    /// Returns `None` if the gate is outside of global configuration registers.
+    fn is_clock_enabled(glb: &RegisterBlock) -> Option<bool>;
+}
+
+/// Peripheral block that can also be reset through global configuration registers.
+pub trait PeripheralReset: ClockGate {
+    /// Reset this peripheral by pulsing its software reset bit.
+    ///
+    /// All registers of the peripheral return to reset values; it should be configured again.
+    fn reset(glb: &RegisterBlock);
+}
+
+/// Clock gate of Direct Memory Access controller `I`.
+pub struct DmaClock<const I: usize>;
+
+/// Clock gate of Universal Asynchronous Receiver/Transmitter peripheral `I`.
+///
+/// `BlockingSerial::freerun_gated` and `free_gated` switch the clock through it, and
+/// `BlockingSerial::hard_reset` resets the peripheral; `freerun` leaves the clock as
+/// enabled by boot code.
+pub struct UartClock<const I: usize>;
+
+/// Clock gate of Serial Peripheral Interface peripheral `I`.
+///
+/// Peripheral 1 is gated outside of this register block, thus its gate state is not reported.
+pub struct SpiClock<const I: usize>;
+
+/// Clock gate of Inter-Integrated Circuit peripheral.
+pub struct I2cClock;
+
+/// Clock gate of Pulse Width Modulation peripheral.
+pub struct PwmClock;
+
+/// Clock gate of hardware LZ4 decompressor peripheral.
+pub struct Lz4dClock;
+
+impl<const I: usize> ClockGate for DmaClock<I> {
+    #[inline]
+    fn enable_clock(glb: &RegisterBlock) {
+        unsafe {
+            glb.clock_config_0.modify(|val| val.enable_dma());
+            glb.clock_config_1.modify(|val| val.enable_dma::<I>());
+        }
+    }
+    #[inline]
+    fn disable_clock(glb: &RegisterBlock) {
+        // Clock gate in configuration register 0 is shared by all controllers.
+        unsafe { glb.clock_config_1.modify(|val| val.disable_dma::<I>()) };
+    }
+    #[inline]
+    fn is_clock_enabled(glb: &RegisterBlock) -> Option<bool> {
+        Some(
+            glb.clock_config_0.read().is_dma_enabled()
+                && glb.clock_config_1.read().is_dma_enabled::<I>(),
+        )
+    }
+}
+
+impl<const I: usize> ClockGate for UartClock<I> {
+    #[inline]
+    fn enable_clock(glb: &RegisterBlock) {
+        unsafe { glb.clock_config_1.modify(|val| val.enable_uart::<I>()) };
+    }
+    #[inline]
+    fn disable_clock(glb: &RegisterBlock) {
+        unsafe { glb.clock_config_1.modify(|val| val.disable_uart::<I>()) };
+    }
+    #[inline]
+    fn is_clock_enabled(glb: &RegisterBlock) -> Option<bool> {
+        Some(glb.clock_config_1.read().is_uart_enabled::<I>())
+    }
+}
+
+impl<const I: usize> PeripheralReset for UartClock<I> {
+    #[inline]
+    fn reset(glb: &RegisterBlock) {
+        glb.reset_uart::<I>();
+    }
+}
+
+impl<const I: usize> ClockGate for SpiClock<I> {
+    #[inline]
+    fn enable_clock(glb: &RegisterBlock) {
+        unsafe { glb.clock_config_1.modify(|val| val.enable_spi::<I>()) };
+    }
+    #[inline]
+    fn disable_clock(glb: &RegisterBlock) {
+        unsafe { glb.clock_config_1.modify(|val| val.disable_spi::<I>()) };
+    }
+    #[inline]
+    fn is_clock_enabled(glb: &RegisterBlock) -> Option<bool> {
+        match I {
+            0 => Some(glb.clock_config_1.read().is_spi_enabled::<0>()),
+            _ => None,
+        }
+    }
+}
+
+impl PeripheralReset for SpiClock<0> {
+    #[inline]
+    fn reset(glb: &RegisterBlock) {
+        glb.pulse_software_reset_1(SoftwareReset1::assert_spi, SoftwareReset1::deassert_spi);
+    }
+}
+
+impl ClockGate for I2cClock {
+    #[inline]
+    fn enable_clock(glb: &RegisterBlock) {
+        unsafe { glb.clock_config_1.modify(|val| val.enable_i2c()) };
+    }
+    #[inline]
+    fn disable_clock(glb: &RegisterBlock) {
+        unsafe { glb.clock_config_1.modify(|val| val.disable_i2c()) };
+    }
+    #[inline]
+    fn is_clock_enabled(glb: &RegisterBlock) -> Option<bool> {
+        Some(glb.clock_config_1.read().is_i2c_enabled())
+    }
+}
+
+impl PeripheralReset for I2cClock {
+    #[inline]
+    fn reset(glb: &RegisterBlock) {
+        glb.pulse_software_reset_1(SoftwareReset1::assert_i2c, SoftwareReset1::deassert_i2c);
+    }
+}
+
+impl ClockGate for PwmClock {
+    #[inline]
+    fn enable_clock(glb: &RegisterBlock) {
+        unsafe { glb.clock_config_1.modify(|val| val.enable_pwm()) };
+    }
+    #[inline]
+    fn disable_clock(glb: &RegisterBlock) {
+        unsafe { glb.clock_config_1.modify(|val| val.disable_pwm()) };
+    }
+    #[inline]
+    fn is_clock_enabled(glb: &RegisterBlock) -> Option<bool> {
+        Some(glb.clock_config_1.read().is_pwm_enabled())
+    }
+}
+
+impl PeripheralReset for PwmClock {
+    #[inline]
+    fn reset(glb: &RegisterBlock) {
+        glb.pulse_software_reset_1(SoftwareReset1::assert_pwm, SoftwareReset1::deassert_pwm);
+    }
+}
+
+impl ClockGate for Lz4dClock {
+    #[inline]
+    fn enable_clock(glb: &RegisterBlock) {
+        unsafe { glb.clock_config_1.modify(|val| val.enable_lz4d()) };
+    }
+    #[inline]
+    fn disable_clock(glb: &RegisterBlock) {
+        unsafe { glb.clock_config_1.modify(|val| val.disable_lz4d()) };
+    }
+    #[inline]
+    fn is_clock_enabled(glb: &RegisterBlock) -> Option<bool> {
+        Some(glb.clock_config_1.read().is_lz4d_enabled())
+    }
+}
+
 /// Clock generation configuration register 2.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 #[repr(transparent)]
@@ -934,30 +1255,34 @@ mod tests {
 
     use super::{
         ClockConfig1, Drive, Function, GpioConfig, I2cClockSource, I2cConfig, InterruptMode, Mode,
-        ParamConfig, Pull, PwmConfig, PwmSignal0, PwmSignal1, RegisterBlock, SdhConfig, SpiConfig,
-        UartConfig, UartMuxGroup, UartSignal,
+        ParamConfig, Pull, PwmConfig, PwmSignal0, PwmSignal1, RegisterBlock, SdhConfig,
+        SoftwareReset1, SpiConfig, UartConfig, UartMuxGroup, UartSignal,
     };
-    use core::mem::offset_of;
+    use super::{ClockGate, SpiClock};
+    use crate::mock::MockRegisters;
 
     #[test]
     fn struct_register_block_offset() {
-        assert_eq!(offset_of!(RegisterBlock, uart_config), 0x150);
-        assert_eq!(offset_of!(RegisterBlock, uart_mux_group), 0x154);
-        assert_eq!(offset_of!(RegisterBlock, i2c_config), 0x180);
-        assert_eq!(offset_of!(RegisterBlock, spi_config), 0x1b0);
-        assert_eq!(offset_of!(RegisterBlock, pwm_config), 0x1d0);
-        assert_eq!(offset_of!(RegisterBlock, sdh_config), 0x430);
-        assert_eq!(offset_of!(RegisterBlock, param_config), 0x510);
-        assert_eq!(offset_of!(RegisterBlock, clock_config_0), 0x580);
-        assert_eq!(offset_of!(RegisterBlock, clock_config_1), 0x584);
-        assert_eq!(offset_of!(RegisterBlock, clock_config_2), 0x588);
-        assert_eq!(offset_of!(RegisterBlock, clock_config_3), 0x58c);
-        assert_eq!(offset_of!(RegisterBlock, ldo12uhs_config), 0x6d0);
-        assert_eq!(offset_of!(RegisterBlock, gpio_config), 0x8c4);
-        assert_eq!(offset_of!(RegisterBlock, gpio_input), 0xac4);
-        assert_eq!(offset_of!(RegisterBlock, gpio_output), 0xae4);
-        assert_eq!(offset_of!(RegisterBlock, gpio_set), 0xaec);
-        assert_eq!(offset_of!(RegisterBlock, gpio_clear), 0xaf4);
+        assert_offsets!(RegisterBlock {
+            uart_config: 0x150,
+            uart_mux_group: 0x154,
+            i2c_config: 0x180,
+            spi_config: 0x1b0,
+            pwm_config: 0x1d0,
+            sdh_config: 0x430,
+            param_config: 0x510,
+            software_reset_1: 0x544,
+            clock_config_0: 0x580,
+            clock_config_1: 0x584,
+            clock_config_2: 0x588,
+            clock_config_3: 0x58c,
+            ldo12uhs_config: 0x6d0,
+            gpio_config: 0x8c4,
+            gpio_input: 0xac4,
+            gpio_output: 0xae4,
+            gpio_set: 0xaec,
+            gpio_clear: 0xaf4,
+        });
     }
 
     #[test]
@@ -1024,6 +1349,20 @@ mod tests {
         val = val.set_function(Function::Gpio);
         assert_eq!(val.0, 0x00000b00);
         assert_eq!(val.function(), Function::Gpio);
+        for function in [
+            Function::Spi0,
+            Function::Spi1,
+            Function::I2c0,
+            Function::I2c1,
+            Function::I2c2,
+            Function::I2c3,
+            Function::Pwm0,
+            Function::Pwm1,
+        ] {
+            let val = GpioConfig(0x0).set_function(function);
+            assert_eq!(val.0, (function as u32) << 8);
+            assert_eq!(val.function(), function);
+        }
 
         let mut val = GpioConfig(0x0);
         val = val.set_interrupt_mode(InterruptMode::AsyncFallingEdge);
@@ -1232,6 +1571,42 @@ mod tests {
         assert_eq!(config.spi_mode::<1>(), SpiMode::Slave);
     }
 
+    #[test]
+    fn struct_software_reset_1_functions() {
+        let mut val = SoftwareReset1(0x0);
+
+        val = val.assert_uart::<0>();
+        assert_eq!(val.0, 0x0001_0000);
+        assert!(val.is_uart_asserted::<0>());
+        val = val.assert_uart::<2>();
+        assert_eq!(val.0, 0x0401_0000);
+        val = val.deassert_uart::<0>();
+        assert_eq!(val.0, 0x0400_0000);
+        assert!(!val.is_uart_asserted::<0>());
+
+        val = val.assert_uart::<1>();
+        assert_eq!(val.0, 0x0402_0000);
+        assert!(val.is_uart_asserted::<1>());
+        val = val.deassert_uart::<1>().deassert_uart::<2>();
+        assert_eq!(val.0, 0x0);
+        assert!(!val.is_uart_asserted::<2>());
+
+        val = val.assert_spi();
+        assert_eq!(val.0, 0x0004_0000);
+        assert!(val.is_spi_asserted());
+        val = val.assert_i2c();
+        assert_eq!(val.0, 0x000C_0000);
+        assert!(val.is_i2c_asserted());
+        val = val.assert_pwm();
+        assert_eq!(val.0, 0x001C_0000);
+        assert!(val.is_pwm_asserted());
+        val = val.deassert_spi().deassert_i2c().deassert_pwm();
+        assert_eq!(val.0, 0x0);
+        assert!(!val.is_spi_asserted());
+        assert!(!val.is_i2c_asserted());
+        assert!(!val.is_pwm_asserted());
+    }
+
     #[test]
     fn struct_sdh_config_functions() {
         let mut val = SdhConfig(0x0);
@@ -1280,6 +1655,17 @@ mod tests {
         assert_eq!(config.0, 0x0000000);
         assert!(!config.is_uart_enabled::<2>());
 
+        config = config.enable_spi::<0>();
+        assert_eq!(config.0, 0x40000);
+        assert!(config.is_spi_enabled::<0>());
+
+        config = config.disable_spi::<0>();
+        assert_eq!(config.0, 0x00000);
+        assert!(!config.is_spi_enabled::<0>());
+
+        config = config.enable_spi::<1>();
+        assert_eq!(config.0, 0x00000);
+
         config = config.enable_i2c();
         assert_eq!(config.0, 0x80000);
         assert!(config.is_i2c_enabled());
@@ -1304,6 +1690,20 @@ mod tests {
         assert_eq!(config.0, 0x00000000);
         assert!(!config.is_lz4d_enabled());
     }
+
+    #[test]
+    fn spi_clock_gate_state() {
+        let mock = MockRegisters::<RegisterBlock>::new();
+        let glb = mock.registers();
+        SpiClock::<0>::enable_clock(glb);
+        assert_eq!(SpiClock::<0>::is_clock_enabled(glb), Some(true));
+        SpiClock::<0>::disable_clock(glb);
+        assert_eq!(SpiClock::<0>::is_clock_enabled(glb), Some(false));
+        // Gate of peripheral 1 is not in global configuration registers.
+        SpiClock::<1>::enable_clock(glb);
+        assert_eq!(SpiClock::<1>::is_clock_enabled(glb), None);
+        assert_eq!(mock.read(|glb| &glb.clock_config_1), 0);
+    }
 }
 
 #[test]
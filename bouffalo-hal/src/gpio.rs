@@ -87,7 +87,7 @@
 //! # fn main() {
 //! # let glb: &bouffalo_hal::glb::RegisterBlock = unsafe { &*core::ptr::null() };
 //! # let p: Peripherals = Peripherals { gpio: Pads::__pads_from_glb(glb), glb: (), uart0: UART0 };
-//! # let clocks = Clocks { xtal: Hertz(40_000_000) };
+//! # let clocks = Clocks::new(Hertz(40_000_000));
 //! // Prepare UART transmit and receive pads by converting io14 and io15 into
 //! // UART signal alternate mode.
 //! # #[cfg(feature = "glb-v2")]
@@ -118,21 +118,44 @@
 
 mod alternate;
 mod convert;
+mod debounce;
 mod disabled;
+#[cfg(any(doc, feature = "glb-v2"))]
+mod dispatcher;
+mod error;
 mod gpio_group;
 mod input;
+#[cfg(any(doc, feature = "glb-v2"))]
+mod open_drain;
 mod output;
 mod pad_dummy;
 mod pad_v1;
 mod pad_v2;
+mod pin_manager;
 mod typestate;
+mod watcher;
 
 pub use convert::{IntoPad, IntoPadv2};
+pub use debounce::{DebouncedInput, Edge};
+#[cfg(any(doc, feature = "glb-v2"))]
+pub use dispatcher::{GpioInterruptDispatcher, InterruptHandler};
+pub use error::Error;
 pub use gpio_group::Pads;
+#[cfg(any(doc, feature = "glb-v2"))]
+pub use open_drain::OpenDrain;
+pub use pin_manager::PinManager;
 pub use typestate::*;
+pub use watcher::{InputChanges, InputWatcher};
 pub use {alternate::Alternate, disabled::Disabled, input::Input, output::Output};
 pub use {pad_v1::Padv1, pad_v2::Padv2};
 
+/// Global record of pads claimed by the runtime, see [`PinManager`].
+#[cfg(feature = "pin-manager")]
+#[inline]
+pub fn pin_manager() -> &'static PinManager {
+    &pin_manager::PIN_MANAGER
+}
+
 cfg_if::cfg_if! {
     if #[cfg(feature = "glb-v1")] {
         pub(crate) use pad_v1::Padv1 as Inner;
@@ -5,7 +5,7 @@ use super::{
     typestate::{Floating, PullDown, PullUp},
 };
 #[cfg(any(doc, feature = "glb-v2"))]
-use super::{convert::IntoPadv2, typestate};
+use super::{convert::IntoPadv2, open_drain::OpenDrain, typestate};
 
 /// GPIO pad with alternate mode.
 pub struct Alternate<'a, const N: usize, M> {
@@ -26,6 +26,14 @@ impl<'a, const N: usize, M> IntoPad<'a, N> for Alternate<'a, N, M> {
         self.inner.into_floating_output().into()
     }
     #[inline]
+    fn into_output_high(self) -> Output<'a, N, Floating> {
+        self.inner.into_output_high().into()
+    }
+    #[inline]
+    fn into_output_low(self) -> Output<'a, N, Floating> {
+        self.inner.into_output_low().into()
+    }
+    #[inline]
     fn into_pull_up_input(self) -> Input<'a, N, PullUp> {
         self.inner.into_pull_up_input().into()
     }
@@ -85,6 +93,14 @@ impl<'a, const N: usize, M> IntoPadv2<'a, N> for Alternate<'a, N, M> {
     fn into_jtag_lp(self) -> Alternate<'a, N, typestate::JtagLp> {
         self.inner.into_jtag_lp().into()
     }
+    #[inline]
+    fn into_analog(self) -> Alternate<'a, N, typestate::Analog> {
+        self.inner.into_analog().into()
+    }
+    #[inline]
+    fn into_open_drain_output(self) -> OpenDrain<'a, N> {
+        self.inner.into_open_drain_output().into()
+    }
 }
 
 impl<'a, const N: usize, M> From<super::Inner<'a, N, M>> for Alternate<'a, N, M> {
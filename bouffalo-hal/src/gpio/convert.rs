@@ -1,3 +1,5 @@
+#[cfg(any(doc, feature = "glb-v2"))]
+use super::open_drain::OpenDrain;
 use super::{
     alternate::Alternate,
     input::Input,
@@ -13,6 +15,16 @@ pub trait IntoPad<'a, const N: usize> {
     fn into_pull_down_output(self) -> Output<'a, N, PullDown>;
     /// Configures the pad to operate as a floating output pad.
     fn into_floating_output(self) -> Output<'a, N, Floating>;
+    /// Configures the pad to operate as a floating output pad driving high level.
+    ///
+    /// Output value is set before the output driver is enabled, so that chip select
+    /// or reset lines see no glitch of the previous level.
+    fn into_output_high(self) -> Output<'a, N, Floating>;
+    /// Configures the pad to operate as a floating output pad driving low level.
+    ///
+    /// Output value is set before the output driver is enabled, so that chip select
+    /// or reset lines see no glitch of the previous level.
+    fn into_output_low(self) -> Output<'a, N, Floating>;
     /// Configures the pad to operate as a pull up input pad.
     fn into_pull_up_input(self) -> Input<'a, N, PullUp>;
     /// Configures the pad to operate as a pull down input pad.
@@ -45,4 +57,9 @@ pub trait IntoPadv2<'a, const N: usize> {
     fn into_jtag_m0(self) -> Alternate<'a, N, typestate::JtagM0>;
     /// Configures the pin to operate as LP core JTAG.
     fn into_jtag_lp(self) -> Alternate<'a, N, typestate::JtagLp>;
+    /// Configures the pin to operate as an analog signal pin, e.g. ADC input or DAC output.
+    fn into_analog(self) -> Alternate<'a, N, typestate::Analog>;
+    /// Configures the pin to operate as an open drain output pin.
+    #[cfg(any(doc, feature = "glb-v2"))]
+    fn into_open_drain_output(self) -> OpenDrain<'a, N>;
 }
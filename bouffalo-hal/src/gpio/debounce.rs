@@ -0,0 +1,144 @@
+use embedded_hal::digital::InputPin;
+
+/// Edge event reported by a debounced input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edge {
+    /// Input became active after being stable for the sample count.
+    Pressed,
+    /// Input became inactive after being stable for the sample count.
+    Released,
+}
+
+/// Debounced wrapper over an input pin.
+///
+/// Each call to `poll` takes one sample of the pin. A change of state is reported
+/// only after `N` consecutive samples agree, thus `N` times the polling period should
+/// be longer than the bouncing time of the button. It can be polled from a main loop
+/// or from a periodic timer interrupt.
+pub struct DebouncedInput<P, const N: usize> {
+    pin: P,
+    active_low: bool,
+    pressed: bool,
+    count: usize,
+}
+
+impl<P: InputPin, const N: usize> DebouncedInput<P, N> {
+    /// Wrap an input pin which reads high when pressed.
+    #[inline]
+    pub const fn new(pin: P) -> Self {
+        Self {
+            pin,
+            active_low: false,
+            pressed: false,
+            count: 0,
+        }
+    }
+    /// Wrap an input pin which reads low when pressed, e.g. a button with pull-up.
+    #[inline]
+    pub const fn new_active_low(pin: P) -> Self {
+        Self {
+            pin,
+            active_low: true,
+            pressed: false,
+            count: 0,
+        }
+    }
+    /// Take one sample, returning an edge event if the stable state changed.
+    #[inline]
+    pub fn poll(&mut self) -> Result<Option<Edge>, P::Error> {
+        let sample = self.pin.is_high()? != self.active_low;
+        if sample == self.pressed {
+            self.count = 0;
+            return Ok(None);
+        }
+        self.count += 1;
+        if self.count < N {
+            return Ok(None);
+        }
+        self.count = 0;
+        self.pressed = sample;
+        let edge = if sample {
+            Edge::Pressed
+        } else {
+            Edge::Released
+        };
+        Ok(Some(edge))
+    }
+    /// Check if the input is in stable pressed state.
+    #[inline]
+    pub const fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+    /// Release the wrapper and return the inner pin.
+    #[inline]
+    pub fn free(self) -> P {
+        self.pin
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DebouncedInput, Edge};
+    use core::convert::Infallible;
+    use embedded_hal::digital::{ErrorType, InputPin};
+
+    struct MockPin<'a> {
+        samples: core::slice::Iter<'a, bool>,
+    }
+
+    impl ErrorType for MockPin<'_> {
+        type Error = Infallible;
+    }
+
+    impl InputPin for MockPin<'_> {
+        fn is_high(&mut self) -> Result<bool, Infallible> {
+            Ok(*self.samples.next().unwrap())
+        }
+        fn is_low(&mut self) -> Result<bool, Infallible> {
+            self.is_high().map(|v| !v)
+        }
+    }
+
+    #[test]
+    fn debounced_input_poll() {
+        let samples = [
+            true, false, true, true, true, true, false, true, false, false, false,
+        ];
+        let pin = MockPin {
+            samples: samples.iter(),
+        };
+        let mut input = DebouncedInput::<_, 3>::new(pin);
+        let events: [Option<Edge>; 11] = core::array::from_fn(|_| input.poll().unwrap());
+        assert_eq!(
+            events,
+            [
+                None,
+                None,
+                None,
+                None,
+                Some(Edge::Pressed),
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(Edge::Released)
+            ]
+        );
+        assert!(!input.is_pressed());
+    }
+
+    #[test]
+    fn debounced_input_active_low() {
+        let samples = [false, false, true, true];
+        let pin = MockPin {
+            samples: samples.iter(),
+        };
+        let mut input = DebouncedInput::<_, 2>::new_active_low(pin);
+        assert_eq!(input.poll().unwrap(), None);
+        assert_eq!(input.poll().unwrap(), Some(Edge::Pressed));
+        assert!(input.is_pressed());
+        assert_eq!(input.poll().unwrap(), None);
+        assert_eq!(input.poll().unwrap(), Some(Edge::Released));
+    }
+}
@@ -0,0 +1,126 @@
+use crate::glb::v2;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Number of pads with interrupt state in GLB v2.
+const PAD_COUNT: usize = 46;
+
+/// GPIO interrupt handler, called with the number of the pad that raised the interrupt.
+pub type InterruptHandler = fn(usize);
+
+/// Dispatch table from pads to interrupt handlers, served by a single GPIO interrupt handler.
+///
+/// Place it in a `static`, register a handler for each pad with interrupt configured and
+/// unmasked, and call [`dispatch`](Self::dispatch) from the GPIO interrupt handler.
+/// Handlers may be registered or removed at any time, including from other interrupts.
+///
+/// ```ignore
+/// static GPIO_HANDLERS: GpioInterruptDispatcher = GpioInterruptDispatcher::new();
+///
+/// fn on_button(pad: usize) {
+///     // handle button press on `pad`
+/// }
+///
+/// GPIO_HANDLERS.register(22, on_button);
+///
+/// #[interrupt]
+/// fn gpio() {
+///     GPIO_HANDLERS.dispatch(&glb);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct GpioInterruptDispatcher {
+    handlers: [AtomicUsize; PAD_COUNT],
+}
+
+impl GpioInterruptDispatcher {
+    /// Creates a dispatch table without any handlers.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            handlers: [const { AtomicUsize::new(0) }; PAD_COUNT],
+        }
+    }
+    /// Call `handler` when pad `pad` raises an interrupt, replacing the previous handler.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pad` is not below 46.
+    #[inline]
+    pub fn register(&self, pad: usize, handler: InterruptHandler) {
+        self.handlers[pad].store(handler as usize, Ordering::Release);
+    }
+    /// Remove handler of pad `pad`; its interrupt flag is no longer served by `dispatch`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pad` is not below 46.
+    #[inline]
+    pub fn unregister(&self, pad: usize) {
+        self.handlers[pad].store(0, Ordering::Release);
+    }
+    /// Serve pending pad interrupts, returning number of pads served.
+    ///
+    /// Interrupt flag of each pad with a handler is cleared before the handler is called,
+    /// so that a new edge during the handler raises another interrupt. Pads without a
+    /// handler are left untouched.
+    #[inline]
+    pub fn dispatch(&self, glb: &v2::RegisterBlock) -> usize {
+        let mut served = 0;
+        for (pad, handler) in self.handlers.iter().enumerate() {
+            let handler = handler.load(Ordering::Acquire);
+            if handler == 0 {
+                continue;
+            }
+            let config = glb.gpio_config[pad].read();
+            if !config.has_interrupt() {
+                continue;
+            }
+            unsafe { glb.gpio_config[pad].write(config.clear_interrupt()) };
+            let handler = unsafe { core::mem::transmute::<usize, InterruptHandler>(handler) };
+            handler(pad);
+            served += 1;
+        }
+        served
+    }
+}
+
+impl Default for GpioInterruptDispatcher {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GpioInterruptDispatcher;
+    use crate::glb::v2;
+    use crate::mock::MockRegisters;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static LAST_PAD: AtomicUsize = AtomicUsize::new(0);
+
+    fn on_interrupt(pad: usize) {
+        LAST_PAD.store(pad, Ordering::Relaxed);
+    }
+
+    #[test]
+    fn dispatch_pending_pads() {
+        let mock = MockRegisters::<v2::RegisterBlock>::new();
+        let glb = mock.registers();
+        let dispatcher = GpioInterruptDispatcher::new();
+        dispatcher.register(7, on_interrupt);
+        assert_eq!(dispatcher.dispatch(glb), 0);
+
+        // Pad 7 has a handler and pad 8 has none; both are pending.
+        mock.write(|glb| &glb.gpio_config[7], 1 << 21);
+        mock.write(|glb| &glb.gpio_config[8], 1 << 21);
+        assert_eq!(dispatcher.dispatch(glb), 1);
+        assert_eq!(LAST_PAD.load(Ordering::Relaxed), 7);
+        assert_eq!(mock.read(|glb| &glb.gpio_config[7]), 0b11 << 20);
+        assert_eq!(mock.read(|glb| &glb.gpio_config[8]), 1 << 21);
+
+        dispatcher.unregister(7);
+        assert_eq!(dispatcher.dispatch(glb), 0);
+    }
+}
@@ -0,0 +1,26 @@
+/// GPIO error.
+///
+/// Pads in a fixed typestate cannot fail and use [`core::convert::Infallible`] as error type;
+/// this type is for operations that check pad state at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// Pad is not in the mode required by this operation.
+    WrongMode,
+    /// Pad has already been claimed by another owner.
+    AlreadyClaimed,
+}
+
+impl embedded_hal::digital::Error for Error {
+    #[inline(always)]
+    fn kind(&self) -> embedded_hal::digital::ErrorKind {
+        embedded_hal::digital::ErrorKind::Other
+    }
+}
+
+impl From<core::convert::Infallible> for Error {
+    #[inline(always)]
+    fn from(value: core::convert::Infallible) -> Self {
+        match value {}
+    }
+}
@@ -1,6 +1,10 @@
 use super::disabled::Disabled;
 
 /// Available GPIO pads.
+///
+/// Only pads bonded out on the selected chip are present, so using a pad that the package
+/// does not have, for example `gpio.io40` on BL616, fails to compile. When no chip feature
+/// is selected, all 46 pads are available.
 pub struct Pads<'a> {
     /// GPIO I/O 0.
     pub io0: Disabled<'a, 0>,
@@ -48,52 +52,114 @@ pub struct Pads<'a> {
     pub io21: Disabled<'a, 21>,
     /// GPIO I/O 22.
     pub io22: Disabled<'a, 22>,
+    #[cfg(not(feature = "bl602"))]
     /// GPIO I/O 23.
     pub io23: Disabled<'a, 23>,
+    #[cfg(not(feature = "bl602"))]
     /// GPIO I/O 24.
     pub io24: Disabled<'a, 24>,
+    #[cfg(not(feature = "bl602"))]
     /// GPIO I/O 25.
     pub io25: Disabled<'a, 25>,
+    #[cfg(not(feature = "bl602"))]
     /// GPIO I/O 26.
     pub io26: Disabled<'a, 26>,
+    #[cfg(not(feature = "bl602"))]
     /// GPIO I/O 27.
     pub io27: Disabled<'a, 27>,
+    #[cfg(not(feature = "bl602"))]
     /// GPIO I/O 28.
     pub io28: Disabled<'a, 28>,
+    #[cfg(not(feature = "bl602"))]
     /// GPIO I/O 29.
     pub io29: Disabled<'a, 29>,
+    #[cfg(not(feature = "bl602"))]
     /// GPIO I/O 30.
     pub io30: Disabled<'a, 30>,
+    #[cfg(not(feature = "bl602"))]
     /// GPIO I/O 31.
     pub io31: Disabled<'a, 31>,
+    #[cfg(not(any(feature = "bl602", feature = "bl702")))]
     /// GPIO I/O 32.
     pub io32: Disabled<'a, 32>,
+    #[cfg(not(any(feature = "bl602", feature = "bl702")))]
     /// GPIO I/O 33.
     pub io33: Disabled<'a, 33>,
+    #[cfg(not(any(feature = "bl602", feature = "bl702")))]
     /// GPIO I/O 34.
     pub io34: Disabled<'a, 34>,
+    #[cfg(not(any(feature = "bl602", feature = "bl616", feature = "bl702")))]
     /// GPIO I/O 35.
     pub io35: Disabled<'a, 35>,
+    #[cfg(not(any(feature = "bl602", feature = "bl616", feature = "bl702")))]
     /// GPIO I/O 36.
     pub io36: Disabled<'a, 36>,
+    #[cfg(not(any(feature = "bl602", feature = "bl616", feature = "bl702")))]
     /// GPIO I/O 37.
     pub io37: Disabled<'a, 37>,
+    #[cfg(not(any(feature = "bl602", feature = "bl616", feature = "bl702")))]
     /// GPIO I/O 38.
     pub io38: Disabled<'a, 38>,
+    #[cfg(not(any(feature = "bl602", feature = "bl616", feature = "bl702")))]
     /// GPIO I/O 39.
     pub io39: Disabled<'a, 39>,
+    #[cfg(not(any(feature = "bl602", feature = "bl616", feature = "bl702")))]
     /// GPIO I/O 40.
     pub io40: Disabled<'a, 40>,
+    #[cfg(not(any(feature = "bl602", feature = "bl616", feature = "bl702")))]
     /// GPIO I/O 41.
     pub io41: Disabled<'a, 41>,
+    #[cfg(not(any(feature = "bl602", feature = "bl616", feature = "bl702")))]
     /// GPIO I/O 42.
     pub io42: Disabled<'a, 42>,
+    #[cfg(not(any(feature = "bl602", feature = "bl616", feature = "bl702")))]
     /// GPIO I/O 43.
     pub io43: Disabled<'a, 43>,
+    #[cfg(not(any(feature = "bl602", feature = "bl616", feature = "bl702")))]
     /// GPIO I/O 44.
     pub io44: Disabled<'a, 44>,
+    #[cfg(not(any(feature = "bl602", feature = "bl616", feature = "bl702")))]
     /// GPIO I/O 45.
     pub io45: Disabled<'a, 45>,
+    #[allow(unused)]
+    base: &'a crate::glb::RegisterBlock,
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "bl602")] {
+        const PAD_COUNT: usize = 23;
+    } else if #[cfg(feature = "bl702")] {
+        const PAD_COUNT: usize = 32;
+    } else if #[cfg(feature = "bl616")] {
+        const PAD_COUNT: usize = 35;
+    } else {
+        #[allow(unused)]
+        const PAD_COUNT: usize = 46;
+    }
+}
+
+impl<'a> Pads<'a> {
+    /// Write reset configuration to every pad of the chip.
+    ///
+    /// Bootroms may leave pads driven or configured as peripheral functions; call this at startup
+    /// before taking any pad out of `Pads`, so that all pads start from a known state.
+    #[inline]
+    pub fn reset_all(&mut self) {
+        #[cfg(feature = "glb-v1")]
+        unsafe {
+            // Each configuration register covers two pads.
+            for config in &self.base.gpio_config[..PAD_COUNT.div_ceil(2)] {
+                config.write(crate::glb::v1::GpioConfig::RESET_VALUE);
+            }
+            self.base.gpio_output_enable.write(0);
+        }
+        #[cfg(feature = "glb-v2")]
+        unsafe {
+            for config in &self.base.gpio_config[..PAD_COUNT] {
+                config.write(crate::glb::v2::GpioConfig::RESET_VALUE);
+            }
+        }
+    }
 }
 
 // Internal function for macros, do not use.
@@ -125,29 +191,53 @@ impl<'a> Pads<'a> {
             io20: super::Inner::__from_glb(base).into(),
             io21: super::Inner::__from_glb(base).into(),
             io22: super::Inner::__from_glb(base).into(),
+            #[cfg(not(feature = "bl602"))]
             io23: super::Inner::__from_glb(base).into(),
+            #[cfg(not(feature = "bl602"))]
             io24: super::Inner::__from_glb(base).into(),
+            #[cfg(not(feature = "bl602"))]
             io25: super::Inner::__from_glb(base).into(),
+            #[cfg(not(feature = "bl602"))]
             io26: super::Inner::__from_glb(base).into(),
+            #[cfg(not(feature = "bl602"))]
             io27: super::Inner::__from_glb(base).into(),
+            #[cfg(not(feature = "bl602"))]
             io28: super::Inner::__from_glb(base).into(),
+            #[cfg(not(feature = "bl602"))]
             io29: super::Inner::__from_glb(base).into(),
+            #[cfg(not(feature = "bl602"))]
             io30: super::Inner::__from_glb(base).into(),
+            #[cfg(not(feature = "bl602"))]
             io31: super::Inner::__from_glb(base).into(),
+            #[cfg(not(any(feature = "bl602", feature = "bl702")))]
             io32: super::Inner::__from_glb(base).into(),
+            #[cfg(not(any(feature = "bl602", feature = "bl702")))]
             io33: super::Inner::__from_glb(base).into(),
+            #[cfg(not(any(feature = "bl602", feature = "bl702")))]
             io34: super::Inner::__from_glb(base).into(),
+            #[cfg(not(any(feature = "bl602", feature = "bl616", feature = "bl702")))]
             io35: super::Inner::__from_glb(base).into(),
+            #[cfg(not(any(feature = "bl602", feature = "bl616", feature = "bl702")))]
             io36: super::Inner::__from_glb(base).into(),
+            #[cfg(not(any(feature = "bl602", feature = "bl616", feature = "bl702")))]
             io37: super::Inner::__from_glb(base).into(),
+            #[cfg(not(any(feature = "bl602", feature = "bl616", feature = "bl702")))]
             io38: super::Inner::__from_glb(base).into(),
+            #[cfg(not(any(feature = "bl602", feature = "bl616", feature = "bl702")))]
             io39: super::Inner::__from_glb(base).into(),
+            #[cfg(not(any(feature = "bl602", feature = "bl616", feature = "bl702")))]
             io40: super::Inner::__from_glb(base).into(),
+            #[cfg(not(any(feature = "bl602", feature = "bl616", feature = "bl702")))]
             io41: super::Inner::__from_glb(base).into(),
+            #[cfg(not(any(feature = "bl602", feature = "bl616", feature = "bl702")))]
             io42: super::Inner::__from_glb(base).into(),
+            #[cfg(not(any(feature = "bl602", feature = "bl616", feature = "bl702")))]
             io43: super::Inner::__from_glb(base).into(),
+            #[cfg(not(any(feature = "bl602", feature = "bl616", feature = "bl702")))]
             io44: super::Inner::__from_glb(base).into(),
+            #[cfg(not(any(feature = "bl602", feature = "bl616", feature = "bl702")))]
             io45: super::Inner::__from_glb(base).into(),
+            base,
         }
     }
 }
@@ -1,5 +1,5 @@
 #[cfg(any(doc, feature = "glb-v2"))]
-use super::{alternate::Alternate, convert::IntoPadv2};
+use super::{alternate::Alternate, convert::IntoPadv2, open_drain::OpenDrain};
 use super::{
     convert::IntoPad,
     output::Output,
@@ -59,6 +59,14 @@ impl<'a, const N: usize, M> IntoPad<'a, N> for Input<'a, N, M> {
         self.inner.into_floating_output().into()
     }
     #[inline]
+    fn into_output_high(self) -> Output<'a, N, Floating> {
+        self.inner.into_output_high().into()
+    }
+    #[inline]
+    fn into_output_low(self) -> Output<'a, N, Floating> {
+        self.inner.into_output_low().into()
+    }
+    #[inline]
     fn into_pull_up_input(self) -> Input<'a, N, PullUp> {
         self.inner.into_pull_up_input().into()
     }
@@ -118,6 +126,14 @@ impl<'a, const N: usize, M> IntoPadv2<'a, N> for Input<'a, N, M> {
     fn into_jtag_lp(self) -> Alternate<'a, N, typestate::JtagLp> {
         self.inner.into_jtag_lp().into()
     }
+    #[inline]
+    fn into_analog(self) -> Alternate<'a, N, typestate::Analog> {
+        self.inner.into_analog().into()
+    }
+    #[inline]
+    fn into_open_drain_output(self) -> OpenDrain<'a, N> {
+        self.inner.into_open_drain_output().into()
+    }
 }
 
 impl<'a, const N: usize, M> ErrorType for Input<'a, N, M> {
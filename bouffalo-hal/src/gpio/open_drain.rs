@@ -0,0 +1,139 @@
+use super::{
+    alternate::Alternate,
+    convert::{IntoPad, IntoPadv2},
+    input::Input,
+    output::Output,
+    typestate::{self, Floating, PullDown, PullUp},
+};
+use embedded_hal::digital::{ErrorType, InputPin, OutputPin};
+
+/// GPIO pad in open drain output mode.
+///
+/// The pad only drives the line low; setting it high releases the line to be pulled up.
+/// Line level can be read back at any time, e.g. to detect that another device holds it low.
+pub struct OpenDrain<'a, const N: usize> {
+    inner: super::Inner<'a, N, typestate::OpenDrain>,
+}
+
+impl<'a, const N: usize> IntoPad<'a, N> for OpenDrain<'a, N> {
+    #[inline]
+    fn into_pull_up_output(self) -> Output<'a, N, PullUp> {
+        self.inner.into_pull_up_output().into()
+    }
+    #[inline]
+    fn into_pull_down_output(self) -> Output<'a, N, PullDown> {
+        self.inner.into_pull_down_output().into()
+    }
+    #[inline]
+    fn into_floating_output(self) -> Output<'a, N, Floating> {
+        self.inner.into_floating_output().into()
+    }
+    #[inline]
+    fn into_output_high(self) -> Output<'a, N, Floating> {
+        self.inner.into_output_high().into()
+    }
+    #[inline]
+    fn into_output_low(self) -> Output<'a, N, Floating> {
+        self.inner.into_output_low().into()
+    }
+    #[inline]
+    fn into_pull_up_input(self) -> Input<'a, N, PullUp> {
+        self.inner.into_pull_up_input().into()
+    }
+    #[inline]
+    fn into_pull_down_input(self) -> Input<'a, N, PullDown> {
+        self.inner.into_pull_down_input().into()
+    }
+    #[inline]
+    fn into_floating_input(self) -> Input<'a, N, Floating> {
+        self.inner.into_floating_input().into()
+    }
+}
+
+impl<'a, const N: usize> IntoPadv2<'a, N> for OpenDrain<'a, N> {
+    #[inline]
+    fn into_spi<const I: usize>(self) -> Alternate<'a, N, typestate::Spi<I>> {
+        self.inner.into_spi().into()
+    }
+    #[inline]
+    fn into_sdh(self) -> Alternate<'a, N, typestate::Sdh> {
+        self.inner.into_sdh().into()
+    }
+    #[inline]
+    fn into_uart(self) -> Alternate<'a, N, typestate::Uart> {
+        self.inner.into_uart().into()
+    }
+    #[inline]
+    fn into_mm_uart(self) -> Alternate<'a, N, typestate::MmUart> {
+        self.inner.into_mm_uart().into()
+    }
+    #[inline]
+    fn into_pull_up_pwm<const I: usize>(self) -> Alternate<'a, N, typestate::Pwm<I>> {
+        self.inner.into_pull_up_pwm().into()
+    }
+    #[inline]
+    fn into_pull_down_pwm<const I: usize>(self) -> Alternate<'a, N, typestate::Pwm<I>> {
+        self.inner.into_pull_down_pwm().into()
+    }
+    #[inline]
+    fn into_floating_pwm<const I: usize>(self) -> Alternate<'a, N, typestate::Pwm<I>> {
+        self.inner.into_floating_pwm().into()
+    }
+    #[inline]
+    fn into_i2c<const I: usize>(self) -> Alternate<'a, N, typestate::I2c<I>> {
+        self.inner.into_i2c().into()
+    }
+    #[inline]
+    fn into_jtag_d0(self) -> Alternate<'a, N, typestate::JtagD0> {
+        self.inner.into_jtag_d0().into()
+    }
+    #[inline]
+    fn into_jtag_m0(self) -> Alternate<'a, N, typestate::JtagM0> {
+        self.inner.into_jtag_m0().into()
+    }
+    #[inline]
+    fn into_jtag_lp(self) -> Alternate<'a, N, typestate::JtagLp> {
+        self.inner.into_jtag_lp().into()
+    }
+    #[inline]
+    fn into_analog(self) -> Alternate<'a, N, typestate::Analog> {
+        self.inner.into_analog().into()
+    }
+    #[inline]
+    fn into_open_drain_output(self) -> OpenDrain<'a, N> {
+        self
+    }
+}
+
+impl<'a, const N: usize> ErrorType for OpenDrain<'a, N> {
+    type Error = core::convert::Infallible;
+}
+
+impl<'a, const N: usize> InputPin for OpenDrain<'a, N> {
+    #[inline]
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        self.inner.is_high()
+    }
+    #[inline]
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.inner.is_low()
+    }
+}
+
+impl<'a, const N: usize> OutputPin for OpenDrain<'a, N> {
+    #[inline]
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.inner.set_low()
+    }
+    #[inline]
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.inner.set_high()
+    }
+}
+
+impl<'a, const N: usize> From<super::Inner<'a, N, typestate::OpenDrain>> for OpenDrain<'a, N> {
+    #[inline]
+    fn from(inner: super::Inner<'a, N, typestate::OpenDrain>) -> Self {
+        Self { inner }
+    }
+}
@@ -1,5 +1,5 @@
 #[cfg(any(doc, feature = "glb-v2"))]
-use super::{alternate::Alternate, convert::IntoPadv2};
+use super::{alternate::Alternate, convert::IntoPadv2, open_drain::OpenDrain};
 use super::{
     convert::IntoPad,
     input::Input,
@@ -20,10 +20,35 @@ impl<'a, const N: usize, M> Output<'a, N, M> {
         self.inner.drive()
     }
     /// Set drive strength of this pad.
+    ///
+    /// GPIO pads on BL602, BL702, BL616 and BL808 have no separate slew rate control;
+    /// a lower drive strength also gives slower output edges, which helps to reduce
+    /// radiated emissions.
     #[inline]
     pub fn set_drive(&mut self, val: Drive) {
         self.inner.set_drive(val)
     }
+    /// Keep input buffer enabled while driving, so that the pad level can be read back.
+    ///
+    /// Use it with [`is_driven_level_reached`](Self::is_driven_level_reached) to detect
+    /// a shorted or overloaded output at runtime.
+    #[inline]
+    pub fn enable_read_back(&mut self) {
+        self.inner.enable_read_back()
+    }
+    /// Disable input buffer of this output pad.
+    #[inline]
+    pub fn disable_read_back(&mut self) {
+        self.inner.disable_read_back()
+    }
+    /// Check if the level read back from the pad equals the level it's driven to.
+    ///
+    /// Read back must be enabled, or the input level always reads low. Allow some time
+    /// after changing output level for the pad to settle before checking.
+    #[inline]
+    pub fn is_driven_level_reached(&self) -> bool {
+        self.inner.is_driven_level_reached()
+    }
 }
 
 impl<'a, const N: usize, M> IntoPad<'a, N> for Output<'a, N, M> {
@@ -40,6 +65,14 @@ impl<'a, const N: usize, M> IntoPad<'a, N> for Output<'a, N, M> {
         self.inner.into_floating_output().into()
     }
     #[inline]
+    fn into_output_high(self) -> Output<'a, N, Floating> {
+        self.inner.into_output_high().into()
+    }
+    #[inline]
+    fn into_output_low(self) -> Output<'a, N, Floating> {
+        self.inner.into_output_low().into()
+    }
+    #[inline]
     fn into_pull_up_input(self) -> Input<'a, N, PullUp> {
         self.inner.into_pull_up_input().into()
     }
@@ -99,6 +132,14 @@ impl<'a, const N: usize, M> IntoPadv2<'a, N> for Output<'a, N, M> {
     fn into_jtag_lp(self) -> Alternate<'a, N, typestate::JtagLp> {
         self.inner.into_jtag_lp().into()
     }
+    #[inline]
+    fn into_analog(self) -> Alternate<'a, N, typestate::Analog> {
+        self.inner.into_analog().into()
+    }
+    #[inline]
+    fn into_open_drain_output(self) -> OpenDrain<'a, N> {
+        self.inner.into_open_drain_output().into()
+    }
 }
 
 impl<'a, const N: usize, M> ErrorType for Output<'a, N, M> {
@@ -44,6 +44,18 @@ impl<'a, const N: usize, M> PadDummy<'a, N, Output<M>> {
     pub fn set_drive(&mut self, _: Drive) {
         unimplemented!()
     }
+    #[inline]
+    pub fn enable_read_back(&mut self) {
+        unimplemented!()
+    }
+    #[inline]
+    pub fn disable_read_back(&mut self) {
+        unimplemented!()
+    }
+    #[inline]
+    pub fn is_driven_level_reached(&self) -> bool {
+        unimplemented!()
+    }
 }
 
 impl<'a, const N: usize, M> PadDummy<'a, N, M> {
@@ -60,6 +72,14 @@ impl<'a, const N: usize, M> PadDummy<'a, N, M> {
         unimplemented!()
     }
     #[inline]
+    pub fn into_output_high(self) -> PadDummy<'a, N, Output<Floating>> {
+        unimplemented!()
+    }
+    #[inline]
+    pub fn into_output_low(self) -> PadDummy<'a, N, Output<Floating>> {
+        unimplemented!()
+    }
+    #[inline]
     pub fn into_pull_up_input(self) -> PadDummy<'a, N, Input<PullUp>> {
         unimplemented!()
     }
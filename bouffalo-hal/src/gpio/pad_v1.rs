@@ -60,6 +60,25 @@ impl<'a, const N: usize, M> Padv1<'a, N, Output<M>> {
         let config = self.base.gpio_config[N >> 1].read().set_drive(N & 0x1, val);
         unsafe { self.base.gpio_config[N >> 1].write(config) };
     }
+    /// Keep input buffer enabled while driving, so that the pin level can be read back.
+    #[inline]
+    pub fn enable_read_back(&mut self) {
+        let config = self.base.gpio_config[N >> 1].read().enable_input(N & 0x1);
+        unsafe { self.base.gpio_config[N >> 1].write(config) };
+    }
+    /// Disable input buffer of this output pin.
+    #[inline]
+    pub fn disable_read_back(&mut self) {
+        let config = self.base.gpio_config[N >> 1].read().disable_input(N & 0x1);
+        unsafe { self.base.gpio_config[N >> 1].write(config) };
+    }
+    /// Check if the level read back from the pin equals the level it's driven to.
+    #[inline]
+    pub fn is_driven_level_reached(&self) -> bool {
+        let output = self.base.gpio_output_value.read() & (1 << N);
+        let input = self.base.gpio_input_value.read() & (1 << N);
+        output == input
+    }
 }
 
 impl<'a, const N: usize, M> Padv1<'a, N, Input<M>> {
@@ -129,6 +148,26 @@ impl<'a, const N: usize, M> Padv1<'a, N, M> {
             _mode: PhantomData,
         }
     }
+    /// Configures the pin to operate as a floating output pin driving high level.
+    ///
+    /// Output value is written before the output driver is enabled, so the first
+    /// level driven on the pin is high.
+    #[inline]
+    pub fn into_output_high(self) -> Padv1<'a, N, Output<Floating>> {
+        let val = self.base.gpio_output_value.read();
+        unsafe { self.base.gpio_output_value.write(val | (1 << N)) };
+        self.into_floating_output()
+    }
+    /// Configures the pin to operate as a floating output pin driving low level.
+    ///
+    /// Output value is written before the output driver is enabled, so the first
+    /// level driven on the pin is low.
+    #[inline]
+    pub fn into_output_low(self) -> Padv1<'a, N, Output<Floating>> {
+        let val = self.base.gpio_output_value.read();
+        unsafe { self.base.gpio_output_value.write(val & !(1 << N)) };
+        self.into_floating_output()
+    }
     /// Configures the pin to operate as a pull up input pin.
     #[inline]
     pub fn into_pull_up_input(self) -> Padv1<'a, N, Input<PullUp>> {
@@ -218,6 +257,8 @@ impl<'a, const N: usize> Padv1<'a, N, super::typestate::Disabled> {
     #[doc(hidden)]
     #[inline]
     pub fn __from_glb(base: &'a v1::RegisterBlock) -> Self {
+        #[cfg(feature = "pin-manager")]
+        super::pin_manager::claim_pad::<N>();
         Self {
             base,
             _mode: PhantomData,
@@ -1,8 +1,8 @@
 use super::{
     Spi,
     typestate::{
-        Floating, I2c, Input, JtagD0, JtagLp, JtagM0, MmUart, Output, PullDown, PullUp, Pwm, Sdh,
-        Uart,
+        Analog, Floating, I2c, Input, JtagD0, JtagLp, JtagM0, MmUart, OpenDrain, Output, PullDown,
+        PullUp, Pwm, Sdh, Uart,
     },
 };
 use crate::glb::{Drive, Pull, v2};
@@ -65,6 +65,25 @@ impl<'a, const N: usize, M> Padv2<'a, N, Output<M>> {
         let config = self.base.gpio_config[N].read().set_drive(val);
         unsafe { self.base.gpio_config[N].write(config) };
     }
+    /// Keep input buffer enabled while driving, so that the pin level can be read back.
+    #[inline]
+    pub fn enable_read_back(&mut self) {
+        let config = self.base.gpio_config[N].read().enable_input();
+        unsafe { self.base.gpio_config[N].write(config) };
+    }
+    /// Disable input buffer of this output pin.
+    #[inline]
+    pub fn disable_read_back(&mut self) {
+        let config = self.base.gpio_config[N].read().disable_input();
+        unsafe { self.base.gpio_config[N].write(config) };
+    }
+    /// Check if the level read back from the pin equals the level it's driven to.
+    #[inline]
+    pub fn is_driven_level_reached(&self) -> bool {
+        let output = self.base.gpio_output[N >> 5].read() & (1 << (N & 0x1F));
+        let input = self.base.gpio_input[N >> 5].read() & (1 << (N & 0x1F));
+        output == input
+    }
 }
 
 impl<'a, const N: usize, M> Padv2<'a, N, Input<M>> {
@@ -117,19 +136,37 @@ impl<'a, const N: usize, M> Padv2<'a, N, M> {
     /// Configures the pin to operate as a floating output pin.
     #[inline]
     pub fn into_floating_output(self) -> Padv2<'a, N, Output<Floating>> {
-        let config = self.base.gpio_config[N]
-            .read()
-            .set_function(v2::Function::Gpio)
-            .set_mode(v2::Mode::SetClear)
-            .disable_input()
-            .enable_output()
-            .set_pull(Pull::None);
+        let config = floating_output(self.base.gpio_config[N].read());
         unsafe { self.base.gpio_config[N].write(config) };
         Padv2 {
             base: self.base,
             _mode: PhantomData,
         }
     }
+    /// Configures the pin to operate as a floating output pin driving high level.
+    ///
+    /// Output value is latched before the output driver is enabled, so the first
+    /// level driven on the pin is high; no pulse of the previous level appears.
+    #[inline]
+    pub fn into_output_high(self) -> Padv2<'a, N, Output<Floating>> {
+        into_latched_output(self.base, N, true);
+        Padv2 {
+            base: self.base,
+            _mode: PhantomData,
+        }
+    }
+    /// Configures the pin to operate as a floating output pin driving low level.
+    ///
+    /// Output value is latched before the output driver is enabled, so the first
+    /// level driven on the pin is low; no pulse of the previous level appears.
+    #[inline]
+    pub fn into_output_low(self) -> Padv2<'a, N, Output<Floating>> {
+        into_latched_output(self.base, N, false);
+        Padv2 {
+            base: self.base,
+            _mode: PhantomData,
+        }
+    }
     /// Configures the pin to operate as a pull up input pin.
     #[inline]
     pub fn into_pull_up_input(self) -> Padv2<'a, N, Input<PullUp>> {
@@ -178,6 +215,71 @@ impl<'a, const N: usize, M> Padv2<'a, N, M> {
             _mode: PhantomData,
         }
     }
+    /// Configures the pin to operate as an open drain output pin.
+    ///
+    /// The pin is released at first with pull up enabled. Input stays enabled so that
+    /// the actual line level can be read back.
+    #[inline]
+    pub fn into_open_drain_output(self) -> Padv2<'a, N, OpenDrain> {
+        unsafe { self.base.gpio_clear[N >> 5].write(1 << (N & 0x1F)) };
+        let config = self.base.gpio_config[N]
+            .read()
+            .set_function(v2::Function::Gpio)
+            .set_mode(v2::Mode::SetClear)
+            .enable_input()
+            .enable_schmitt()
+            .disable_output()
+            .set_pull(Pull::Up);
+        unsafe { self.base.gpio_config[N].write(config) };
+        Padv2 {
+            base: self.base,
+            _mode: PhantomData,
+        }
+    }
+}
+
+#[inline]
+const fn floating_output(config: v2::GpioConfig) -> v2::GpioConfig {
+    config
+        .set_function(v2::Function::Gpio)
+        .set_mode(v2::Mode::SetClear)
+        .disable_input()
+        .enable_output()
+        .set_pull(Pull::None)
+}
+
+/// Register accesses used when switching a pad into output with a known level.
+trait LatchedOutput {
+    fn gpio_config(&self, n: usize) -> v2::GpioConfig;
+    fn set_gpio_config(&self, n: usize, config: v2::GpioConfig);
+    fn latch_output(&self, n: usize, high: bool);
+}
+
+impl LatchedOutput for v2::RegisterBlock {
+    #[inline]
+    fn gpio_config(&self, n: usize) -> v2::GpioConfig {
+        self.gpio_config[n].read()
+    }
+    #[inline]
+    fn set_gpio_config(&self, n: usize, config: v2::GpioConfig) {
+        unsafe { self.gpio_config[n].write(config) }
+    }
+    #[inline]
+    fn latch_output(&self, n: usize, high: bool) {
+        let register = if high {
+            &self.gpio_set
+        } else {
+            &self.gpio_clear
+        };
+        unsafe { register[n >> 5].write(1 << (n & 0x1F)) }
+    }
+}
+
+/// Latch output level of pad `n`, then enable its output driver.
+#[inline]
+fn into_latched_output(regs: &impl LatchedOutput, n: usize, high: bool) {
+    regs.latch_output(n, high);
+    regs.set_gpio_config(n, floating_output(regs.gpio_config(n)));
 }
 
 const UART_GPIO_CONFIG: v2::GpioConfig = v2::GpioConfig::RESET_VALUE
@@ -193,6 +295,13 @@ const JTAG_GPIO_CONFIG: v2::GpioConfig = v2::GpioConfig::RESET_VALUE
     .enable_schmitt()
     .set_drive(Drive::Drive0)
     .set_pull(Pull::None);
+const ANALOG_GPIO_CONFIG: v2::GpioConfig = v2::GpioConfig::RESET_VALUE
+    .disable_input()
+    .disable_output()
+    .disable_schmitt()
+    .set_drive(Drive::Drive0)
+    .set_pull(Pull::None)
+    .set_function(v2::Function::Analog);
 
 impl<'a, const N: usize, M> Padv2<'a, N, M> {
     /// Configures the pin to operate as UART signal.
@@ -310,6 +419,18 @@ impl<'a, const N: usize, M> Padv2<'a, N, M> {
             _mode: PhantomData,
         }
     }
+    /// Configures the pin to operate as an analog signal pin, e.g. ADC input or DAC output.
+    ///
+    /// Input and output buffers, schmitt trigger and pulls are all disabled, leaving
+    /// the pad in high impedance so that no leakage current disturbs analog readings.
+    #[inline]
+    pub fn into_analog(self) -> Padv2<'a, N, Analog> {
+        unsafe { self.base.gpio_config[N].write(ANALOG_GPIO_CONFIG) };
+        Padv2 {
+            base: self.base,
+            _mode: PhantomData,
+        }
+    }
     /// Configures the pin to operate as a SPI pin.
     #[inline]
     pub fn into_spi<const I: usize>(self) -> Padv2<'a, N, Spi<I>> {
@@ -369,6 +490,38 @@ impl<'a, const N: usize, M> InputPin for Padv2<'a, N, Input<M>> {
     }
 }
 
+impl<'a, const N: usize> ErrorType for Padv2<'a, N, OpenDrain> {
+    type Error = core::convert::Infallible;
+}
+
+impl<'a, const N: usize> InputPin for Padv2<'a, N, OpenDrain> {
+    #[inline]
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.base.gpio_input[N >> 5].read() & (1 << (N & 0x1F)) != 0)
+    }
+    #[inline]
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.base.gpio_input[N >> 5].read() & (1 << (N & 0x1F)) == 0)
+    }
+}
+
+// Output value is kept low; the pin is driven low by enabling output, and released
+// by disabling output.
+impl<'a, const N: usize> OutputPin for Padv2<'a, N, OpenDrain> {
+    #[inline]
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        let config = self.base.gpio_config[N].read().enable_output();
+        unsafe { self.base.gpio_config[N].write(config) };
+        Ok(())
+    }
+    #[inline]
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        let config = self.base.gpio_config[N].read().disable_output();
+        unsafe { self.base.gpio_config[N].write(config) };
+        Ok(())
+    }
+}
+
 impl<'a, const N: usize, M> OutputPin for Padv2<'a, N, Output<M>> {
     #[inline]
     fn set_low(&mut self) -> Result<(), Self::Error> {
@@ -387,9 +540,133 @@ impl<'a, const N: usize> Padv2<'a, N, super::typestate::Disabled> {
     #[doc(hidden)]
     #[inline]
     pub fn __from_glb(base: &'a v2::RegisterBlock) -> Self {
+        #[cfg(feature = "pin-manager")]
+        super::pin_manager::claim_pad::<N>();
         Self {
             base,
             _mode: PhantomData,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{LatchedOutput, Padv2, into_latched_output};
+    use crate::glb::{Pull, v2};
+    use crate::mock::MockRegisters;
+    use core::cell::RefCell;
+
+    #[test]
+    fn into_analog_high_impedance() {
+        let mock = MockRegisters::<v2::RegisterBlock>::new();
+        let glb = mock.registers();
+        let pulled = v2::GpioConfig::RESET_VALUE
+            .enable_input()
+            .enable_output()
+            .enable_schmitt()
+            .set_pull(Pull::Up);
+        unsafe { glb.gpio_config[8].write(pulled) };
+
+        let _pad = Padv2::<8, _>::__from_glb(glb).into_analog();
+        let config = glb.gpio_config[8].read();
+        assert!(!config.is_input_enabled());
+        assert!(!config.is_output_enabled());
+        assert!(!config.is_schmitt_enabled());
+        assert_eq!(config.pull(), Pull::None);
+        assert_eq!(config.function(), v2::Function::Analog);
+    }
+
+    #[test]
+    fn output_read_back() {
+        let mock = MockRegisters::<v2::RegisterBlock>::new();
+        let glb = mock.registers();
+        let mut pad = Padv2::<40, _>::__from_glb(glb).into_floating_output();
+        assert!(!glb.gpio_config[40].read().is_input_enabled());
+        pad.enable_read_back();
+        assert!(glb.gpio_config[40].read().is_input_enabled());
+        assert!(glb.gpio_config[40].read().is_output_enabled());
+
+        // Registers are plain memory; prepare driven and sensed levels of pin 40 by hand.
+        mock.write(|glb| &glb.gpio_output[1], 1 << 8);
+        mock.write(|glb| &glb.gpio_input[1], 1 << 8);
+        assert!(pad.is_driven_level_reached());
+        mock.write(|glb| &glb.gpio_input[1], 0);
+        assert!(!pad.is_driven_level_reached());
+        mock.write(|glb| &glb.gpio_output[1], 0);
+        assert!(pad.is_driven_level_reached());
+
+        pad.disable_read_back();
+        assert!(!glb.gpio_config[40].read().is_input_enabled());
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Access {
+        Level(usize, bool),
+        Config(usize, bool),
+    }
+
+    /// Records order of register writes made while switching a pad into output.
+    struct RecordingGlb {
+        glb: MockRegisters<v2::RegisterBlock>,
+        accesses: RefCell<([Option<Access>; 4], usize)>,
+    }
+
+    impl RecordingGlb {
+        fn record(&self, access: Access) {
+            let (accesses, len) = &mut *self.accesses.borrow_mut();
+            accesses[*len] = Some(access);
+            *len += 1;
+        }
+    }
+
+    impl LatchedOutput for RecordingGlb {
+        fn gpio_config(&self, n: usize) -> v2::GpioConfig {
+            self.glb.registers().gpio_config(n)
+        }
+        fn set_gpio_config(&self, n: usize, config: v2::GpioConfig) {
+            self.record(Access::Config(n, config.is_output_enabled()));
+            self.glb.registers().set_gpio_config(n, config)
+        }
+        fn latch_output(&self, n: usize, high: bool) {
+            self.record(Access::Level(n, high));
+            self.glb.registers().latch_output(n, high)
+        }
+    }
+
+    #[test]
+    fn output_level_latched_before_enable() {
+        let recording = RecordingGlb {
+            glb: MockRegisters::new(),
+            accesses: RefCell::new(([None, None, None, None], 0)),
+        };
+        into_latched_output(&recording, 36, true);
+        into_latched_output(&recording, 37, false);
+        let (accesses, len) = &*recording.accesses.borrow();
+        assert_eq!(
+            accesses[..*len],
+            [
+                Some(Access::Level(36, true)),
+                Some(Access::Config(36, true)),
+                Some(Access::Level(37, false)),
+                Some(Access::Config(37, true)),
+            ]
+        );
+
+        // Output level goes to set or clear register of pin 36 and 37, and
+        // configuration is then switched to a GPIO output in set-clear mode.
+        let mock = MockRegisters::<v2::RegisterBlock>::new();
+        let glb = mock.registers();
+        let _pad = Padv2::<36, _>::__from_glb(glb).into_output_high();
+        assert_eq!(mock.read(|glb| &glb.gpio_set[1]), 1 << 4);
+        assert_eq!(mock.read(|glb| &glb.gpio_clear[1]), 0);
+        let config = glb.gpio_config[36].read();
+        assert!(config.is_output_enabled());
+        assert_eq!(config.function(), v2::Function::Gpio);
+        assert_eq!(config.mode(), v2::Mode::SetClear);
+        assert_eq!(config.pull(), Pull::None);
+
+        let _pad = Padv2::<37, _>::__from_glb(glb).into_output_low();
+        assert_eq!(mock.read(|glb| &glb.gpio_clear[1]), 1 << 5);
+        assert!(glb.gpio_config[37].read().is_output_enabled());
+    }
+}
@@ -0,0 +1,96 @@
+use super::Error;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+/// Runtime record of claimed GPIO pads, to catch a pad configured by two owners.
+///
+/// Typestate pads cannot be duplicated in safe code, but stealing peripherals twice or
+/// driving pads through raw registers can still hand one pad to two functions. With
+/// the `pin-manager` feature, every pad created by the runtime claims itself in the
+/// global manager returned by `gpio::pin_manager`, and creating the same pad again
+/// panics. Code accessing pads through raw registers should claim them there as well.
+/// Without the feature, pads are not tracked and release builds pay nothing.
+#[derive(Debug)]
+pub struct PinManager {
+    claimed: [AtomicU32; 2],
+}
+
+impl PinManager {
+    /// Creates a manager with no pad claimed.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            claimed: [const { AtomicU32::new(0) }; 2],
+        }
+    }
+    /// Claim pad `pad`, or return `Error::AlreadyClaimed` if it has been claimed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pad` is not below 64.
+    #[inline]
+    pub fn claim(&self, pad: usize) -> Result<(), Error> {
+        let bit = 1 << (pad & 0x1F);
+        let previous = self.claimed[pad >> 5].fetch_or(bit, Ordering::AcqRel);
+        if previous & bit != 0 {
+            return Err(Error::AlreadyClaimed);
+        }
+        Ok(())
+    }
+    /// Release pad `pad` so that it can be claimed again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pad` is not below 64.
+    #[inline]
+    pub fn release(&self, pad: usize) {
+        self.claimed[pad >> 5].fetch_and(!(1 << (pad & 0x1F)), Ordering::AcqRel);
+    }
+    /// Check if pad `pad` has been claimed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pad` is not below 64.
+    #[inline]
+    pub fn is_claimed(&self, pad: usize) -> bool {
+        self.claimed[pad >> 5].load(Ordering::Acquire) & (1 << (pad & 0x1F)) != 0
+    }
+}
+
+impl Default for PinManager {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "pin-manager")]
+pub(crate) static PIN_MANAGER: PinManager = PinManager::new();
+
+/// Claim pad `N` in the global manager as it is created, panicking on a second owner.
+#[cfg(feature = "pin-manager")]
+#[inline]
+pub(crate) fn claim_pad<const N: usize>() {
+    if PIN_MANAGER.claim(N).is_err() {
+        panic!("GPIO pad {} is already claimed", N);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PinManager;
+    use crate::gpio::Error;
+
+    #[test]
+    fn claim_and_release() {
+        let manager = PinManager::new();
+        assert!(manager.claim(3).is_ok());
+        assert!(manager.claim(40).is_ok());
+        assert!(manager.is_claimed(40));
+        assert!(!manager.is_claimed(8));
+        assert_eq!(manager.claim(40), Err(Error::AlreadyClaimed));
+        manager.release(40);
+        assert!(!manager.is_claimed(40));
+        assert!(manager.is_claimed(3));
+        assert!(manager.claim(40).is_ok());
+    }
+}
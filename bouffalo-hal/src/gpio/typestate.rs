@@ -23,6 +23,9 @@ pub struct PullUp;
 /// Floating (type state).
 pub struct Floating;
 
+/// Open drain output (type state).
+pub struct OpenDrain;
+
 /// UART alternate (type state).
 pub struct Uart;
 
@@ -38,6 +41,9 @@ pub struct JtagM0;
 /// LP core JTAG mode (type state).
 pub struct JtagLp;
 
+/// Analog signal mode (type state).
+pub struct Analog;
+
 /// Serial Peripheral Interface mode (type state).
 pub struct Spi<const F: usize>;
 
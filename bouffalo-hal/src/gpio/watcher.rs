@@ -0,0 +1,111 @@
+/// Software change detection over a set of input pads.
+///
+/// Each `poll` reads GLB input value register once and reports pads whose level changed
+/// since last poll, without keeping previous state for every pin by hand.
+///
+/// ```ignore
+/// // Watch io12 and io13, which are configured as inputs.
+/// let mut watcher = InputWatcher::new(&glb, (1 << 12) | (1 << 13));
+/// loop {
+///     for (pad, high) in watcher.poll().iter() {
+///         // handle key on `pad`
+///     }
+/// }
+/// ```
+pub struct InputWatcher<'a> {
+    #[allow(unused)]
+    base: &'a crate::glb::RegisterBlock,
+    mask: u64,
+    levels: u64,
+}
+
+impl<'a> InputWatcher<'a> {
+    /// Watch pads whose bits are set in `mask`, where bit `n` stands for pad `n`.
+    ///
+    /// Current levels are sampled on creation, so the first poll only reports later changes.
+    #[inline]
+    pub fn new(base: &'a crate::glb::RegisterBlock, mask: u64) -> Self {
+        let mut ans = Self {
+            base,
+            mask,
+            levels: 0,
+        };
+        ans.levels = ans.read_levels();
+        ans
+    }
+    /// Sample all watched pads, returning those changed since last poll.
+    #[inline]
+    pub fn poll(&mut self) -> InputChanges {
+        let levels = self.read_levels();
+        let changed = (levels ^ self.levels) & self.mask;
+        self.levels = levels;
+        InputChanges { changed, levels }
+    }
+    /// Levels of watched pads at last poll, bit `n` set if pad `n` was high.
+    #[inline]
+    pub const fn levels(&self) -> u64 {
+        self.levels
+    }
+    #[inline]
+    fn read_levels(&self) -> u64 {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "glb-v1")] {
+                self.base.gpio_input_value.read() as u64 & self.mask
+            } else if #[cfg(feature = "glb-v2")] {
+                let mut levels = self.base.gpio_input[0].read() as u64;
+                // Only read upper register when watching pads above 31.
+                if self.mask >> 32 != 0 {
+                    levels |= (self.base.gpio_input[1].read() as u64) << 32;
+                }
+                levels & self.mask
+            } else {
+                unimplemented!()
+            }
+        }
+    }
+}
+
+/// Pads changed between two polls of [`InputWatcher`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InputChanges {
+    changed: u64,
+    levels: u64,
+}
+
+impl InputChanges {
+    /// Check if no watched pad has changed.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.changed == 0
+    }
+    /// Bit mask of changed pads.
+    #[inline]
+    pub const fn changed(&self) -> u64 {
+        self.changed
+    }
+    /// Iterate over changed pads as pad number and its new level, `true` for high.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (usize, bool)> + '_ {
+        (0..64)
+            .filter(|n| self.changed & (1 << n) != 0)
+            .map(|n| (n, self.levels & (1 << n) != 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InputChanges;
+
+    #[test]
+    fn input_changes_iter() {
+        let changes = InputChanges {
+            changed: (1 << 3) | (1 << 40),
+            levels: (1 << 3) | (1 << 7),
+        };
+        assert!(!changes.is_empty());
+        let mut iter = changes.iter();
+        assert_eq!(iter.next(), Some((3, true)));
+        assert_eq!(iter.next(), Some((40, false)));
+        assert_eq!(iter.next(), None);
+    }
+}
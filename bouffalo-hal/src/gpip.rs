@@ -193,32 +193,33 @@ impl<ADC: Deref<Target = RegisterBlock>> Adc<ADC> {
 #[cfg(test)]
 mod tests {
     use super::RegisterBlock;
-    use core::mem::offset_of;
 
     #[test]
     fn struct_gpadc_config_functions() {
-        assert_eq!(offset_of!(RegisterBlock, gpadc_config), 0x0);
-        assert_eq!(offset_of!(RegisterBlock, gpadc_dma_rdata), 0x4);
-        assert_eq!(offset_of!(RegisterBlock, gpadc_pir_train), 0x20);
-        assert_eq!(offset_of!(RegisterBlock, gpdac_config), 0x40);
-        assert_eq!(offset_of!(RegisterBlock, gpdac_dma_config), 0x44);
-        assert_eq!(offset_of!(RegisterBlock, gpdac_dma_wdata), 0x48);
-        assert_eq!(offset_of!(RegisterBlock, gpdac_tx_fifo_status), 0x4C);
-        assert_eq!(offset_of!(RegisterBlock, gpdac_ctrl), 0x308);
-        assert_eq!(offset_of!(RegisterBlock, gpdac_actrl), 0x30c);
-        assert_eq!(offset_of!(RegisterBlock, gpdac_bctrl), 0x310);
-        assert_eq!(offset_of!(RegisterBlock, gpdac_data), 0x314);
-        assert_eq!(offset_of!(RegisterBlock, gpadc_command), 0x90C);
-        assert_eq!(offset_of!(RegisterBlock, gpadc_config_1), 0x910);
-        assert_eq!(offset_of!(RegisterBlock, gpadc_config_2), 0x914);
-        assert_eq!(offset_of!(RegisterBlock, adc_converation_sequence_1), 0x918);
-        assert_eq!(offset_of!(RegisterBlock, adc_converation_sequence_2), 0x91C);
-        assert_eq!(offset_of!(RegisterBlock, adc_converation_sequence_3), 0x920);
-        assert_eq!(offset_of!(RegisterBlock, adc_converation_sequence_4), 0x924);
-        assert_eq!(offset_of!(RegisterBlock, gpadc_status), 0x928);
-        assert_eq!(offset_of!(RegisterBlock, gpadc_interrupt_state), 0x92C);
-        assert_eq!(offset_of!(RegisterBlock, gpadc_result), 0x930);
-        assert_eq!(offset_of!(RegisterBlock, gpadc_raw_result), 0x934);
-        assert_eq!(offset_of!(RegisterBlock, gpadc_define), 0x938);
+        assert_offsets!(RegisterBlock {
+            gpadc_config: 0x0,
+            gpadc_dma_rdata: 0x4,
+            gpadc_pir_train: 0x20,
+            gpdac_config: 0x40,
+            gpdac_dma_config: 0x44,
+            gpdac_dma_wdata: 0x48,
+            gpdac_tx_fifo_status: 0x4C,
+            gpdac_ctrl: 0x308,
+            gpdac_actrl: 0x30c,
+            gpdac_bctrl: 0x310,
+            gpdac_data: 0x314,
+            gpadc_command: 0x90C,
+            gpadc_config_1: 0x910,
+            gpadc_config_2: 0x914,
+            adc_converation_sequence_1: 0x918,
+            adc_converation_sequence_2: 0x91C,
+            adc_converation_sequence_3: 0x920,
+            adc_converation_sequence_4: 0x924,
+            gpadc_status: 0x928,
+            gpadc_interrupt_state: 0x92C,
+            gpadc_result: 0x930,
+            gpadc_raw_result: 0x934,
+            gpadc_define: 0x938,
+        });
     }
 }
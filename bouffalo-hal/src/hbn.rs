@@ -131,6 +131,9 @@ impl Global {
         Self(self.0 | Self::CLEAR_RESET_EVENT)
     }
     /// Get reset event.
+    #[deprecated(
+        note = "reset event field latches one bit per reset source rather than an enumerated value; use `reset_cause` instead"
+    )]
     #[inline]
     pub const fn reset_event(self) -> ResetEvent {
         match (self.0 & Self::RESET_EVENT) >> 7 {
@@ -187,6 +190,31 @@ impl Global {
             _ => unreachable!(),
         }
     }
+    /// Get the source of the last chip reset.
+    ///
+    /// Reset event field latches one bit for each reset source until cleared by
+    /// `clear_reset_event`; bit order follows `HBN_RST_EVENT_Type` in `bl702_hbn.h` of
+    /// Bouffalo Lab MCU SDK, i.e. bit 0 power-on, bit 1 external reset pin, bit 2 software
+    /// reset, bit 3 power reset pin and bit 4 brown-out. If several bits are latched, the
+    /// first of power-on, brown-out, power reset pin, external reset pin and software reset
+    /// is reported.
+    #[inline]
+    pub const fn reset_cause(self) -> ResetCause {
+        let events = (self.0 & Self::RESET_EVENT) >> 7;
+        if events & (1 << 0) != 0 {
+            ResetCause::PowerOn
+        } else if events & (1 << 4) != 0 {
+            ResetCause::BrownOut
+        } else if events & (1 << 3) != 0 {
+            ResetCause::PowerPin
+        } else if events & (1 << 1) != 0 {
+            ResetCause::ExternalPin
+        } else if events & (1 << 2) != 0 {
+            ResetCause::Software
+        } else {
+            ResetCause::Unknown
+        }
+    }
     /// Set uart clock source.
     #[inline]
     pub const fn set_uart_clock_source(self, val: UartClockSource) -> Self {
@@ -307,32 +335,80 @@ pub enum ResetEvent {
     Blai = 49,
 }
 
+/// Source of the last chip reset.
+///
+/// Watchdog resets are not latched in hibernate registers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResetCause {
+    /// Power supply was turned on.
+    PowerOn,
+    /// Supply voltage dropped below brown-out threshold.
+    BrownOut,
+    /// Power reset pin was asserted.
+    PowerPin,
+    /// External reset pin was asserted.
+    ExternalPin,
+    /// Software requested a chip reset.
+    Software,
+    /// No reset event is latched, e.g. it was cleared after the last reset.
+    Unknown,
+}
+
+/// Read the source of the last chip reset.
+#[inline]
+pub fn reset_cause(hbn: &RegisterBlock) -> ResetCause {
+    hbn.global.read().reset_cause()
+}
+
+/// Clear latched reset events, so that the next reset can be told apart.
+#[inline]
+pub fn clear_reset_cause(hbn: &RegisterBlock) {
+    let val = hbn.global.read();
+    hbn.global.write(val.clear_reset_event());
+    hbn.global.write(val);
+}
+
 #[cfg(test)]
 mod tests {
     use super::RegisterBlock;
-    use core::mem::offset_of;
 
     #[test]
     fn struct_register_block_offset() {
-        assert_eq!(offset_of!(RegisterBlock, control), 0x00);
-        assert_eq!(offset_of!(RegisterBlock, time_lo), 0x04);
-        assert_eq!(offset_of!(RegisterBlock, time_hi), 0x08);
-        assert_eq!(offset_of!(RegisterBlock, rtc_time_lo), 0x0c);
-        assert_eq!(offset_of!(RegisterBlock, rtc_time_hi), 0x10);
-        assert_eq!(offset_of!(RegisterBlock, interrupt_mode), 0x14);
-        assert_eq!(offset_of!(RegisterBlock, interrupt_state), 0x18);
-        assert_eq!(offset_of!(RegisterBlock, interrupt_clear), 0x1c);
-        assert_eq!(offset_of!(RegisterBlock, pir_config), 0x20);
-        assert_eq!(offset_of!(RegisterBlock, pir_threshold), 0x24);
-        assert_eq!(offset_of!(RegisterBlock, pir_interval), 0x28);
-        assert_eq!(offset_of!(RegisterBlock, bor_config), 0x2c);
-        assert_eq!(offset_of!(RegisterBlock, global), 0x30);
-        assert_eq!(offset_of!(RegisterBlock, sram), 0x34);
-        assert_eq!(offset_of!(RegisterBlock, pad_control_0), 0x38);
-        assert_eq!(offset_of!(RegisterBlock, pad_control_1), 0x3c);
-        assert_eq!(offset_of!(RegisterBlock, rc32k), 0x200);
-        assert_eq!(offset_of!(RegisterBlock, xtal32k), 0x204);
-        assert_eq!(offset_of!(RegisterBlock, rtc_control_0), 0x208);
-        assert_eq!(offset_of!(RegisterBlock, rtc_control_1), 0x20c);
+        assert_offsets!(RegisterBlock {
+            control: 0x00,
+            time_lo: 0x04,
+            time_hi: 0x08,
+            rtc_time_lo: 0x0c,
+            rtc_time_hi: 0x10,
+            interrupt_mode: 0x14,
+            interrupt_state: 0x18,
+            interrupt_clear: 0x1c,
+            pir_config: 0x20,
+            pir_threshold: 0x24,
+            pir_interval: 0x28,
+            bor_config: 0x2c,
+            global: 0x30,
+            sram: 0x34,
+            pad_control_0: 0x38,
+            pad_control_1: 0x3c,
+            rc32k: 0x200,
+            xtal32k: 0x204,
+            rtc_control_0: 0x208,
+            rtc_control_1: 0x20c,
+        });
+    }
+
+    #[test]
+    fn struct_global_functions() {
+        use super::{Global, ResetCause};
+        assert_eq!(Global(0x0).reset_cause(), ResetCause::Unknown);
+        assert_eq!(Global(0x1 << 7).reset_cause(), ResetCause::PowerOn);
+        assert_eq!(Global(0x2 << 7).reset_cause(), ResetCause::ExternalPin);
+        assert_eq!(Global(0x4 << 7).reset_cause(), ResetCause::Software);
+        assert_eq!(Global(0x8 << 7).reset_cause(), ResetCause::PowerPin);
+        assert_eq!(Global(0x10 << 7).reset_cause(), ResetCause::BrownOut);
+        assert_eq!(Global(0x16 << 7).reset_cause(), ResetCause::BrownOut);
+        assert_eq!(Global(0x6 << 7).reset_cause(), ResetCause::ExternalPin);
+        assert_eq!(Global(0x0).clear_reset_event(), Global(0x1 << 13));
     }
 }
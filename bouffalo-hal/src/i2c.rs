@@ -2,11 +2,17 @@
 use core::ops::Deref;
 
 use crate::{
-    glb::{self, v2::I2cClockSource},
+    glb::{
+        self,
+        v2::{ClockGate, I2cClock, I2cClockSource},
+    },
     gpio::{self, Alternate},
 };
 use volatile_register::{RO, RW, WO};
 
+mod software;
+pub use software::*;
+
 /// Inter-integrated circuit registers.
 #[repr(C)]
 pub struct RegisterBlock {
@@ -505,11 +511,11 @@ impl<I2C: Deref<Target = RegisterBlock>, SCL, SDA> I2c<I2C, (SCL, SDA)> {
     #[inline]
     pub fn new<const I: usize>(i2c: I2C, pads: (SCL, SDA), glb: &glb::v2::RegisterBlock) -> Self
     where
-        SCL: SclPin<I>,
-        SDA: SdaPin<I>,
+        (SCL, SDA): Pads<I>,
     {
         // TODO: support custom clock and frequency
         // Enable clock
+        I2cClock::enable_clock(glb);
         unsafe {
             glb.i2c_config.modify(|config| {
                 config
@@ -517,7 +523,6 @@ impl<I2C: Deref<Target = RegisterBlock>, SCL, SDA> I2c<I2C, (SCL, SDA)> {
                     .set_clock_source(I2cClockSource::Xclk)
                     .set_clock_divide(0xff)
             });
-            glb.clock_config_1.modify(|config| config.enable_i2c());
             i2c.period_start.write(
                 PeriodStart(0)
                     .set_phase(0, 0xff)
@@ -555,8 +560,8 @@ impl<I2C: Deref<Target = RegisterBlock>, SCL, SDA> I2c<I2C, (SCL, SDA)> {
     pub fn free(self, glb: &glb::v2::RegisterBlock) -> (I2C, (SCL, SDA)) {
         unsafe {
             glb.i2c_config.modify(|config| config.disable_clock());
-            glb.clock_config_1.modify(|config| config.disable_i2c());
         }
+        I2cClock::disable_clock(glb);
         (self.i2c, self.pads)
     }
 
@@ -657,9 +662,26 @@ impl<I2C: Deref<Target = RegisterBlock>, PADS> embedded_hal::i2c::I2c for I2c<I2
     }
 }
 
-pub trait SclPin<const I: usize> {}
+/// Valid I2C pads.
+///
+/// Pads are given in order of clock and data; signal is decided by pad number modulo 2.
+#[diagnostic::on_unimplemented(
+    message = "the I/O pad group {Self} is not connected to I2C peripheral {I} on hardware"
+)]
+pub trait Pads<const I: usize> {}
+
+impl<SCL, SDA, const I: usize> Pads<I> for (SCL, SDA)
+where
+    SCL: HasSclSignal<I>,
+    SDA: HasSdaSignal<I>,
+{
+}
+
+/// Check if target gpio `Pin` is internally connected to I2C clock signal.
+pub trait HasSclSignal<const I: usize> {}
 
-pub trait SdaPin<const I: usize> {}
+/// Check if target gpio `Pin` is internally connected to I2C data signal.
+pub trait HasSdaSignal<const I: usize> {}
 
 #[rustfmt::skip]
 mod i2c_impls {
@@ -667,37 +689,52 @@ mod i2c_impls {
 
     // 0, 2, 4, ..., 2n: SCL
     // 1, 3, 5, ..., 2n+1: SDA
-    // TODO: support other pads if needed
-    impl<'a, const I: usize> SclPin<I> for Alternate<'a, 0, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SdaPin<I> for Alternate<'a, 1, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SclPin<I> for Alternate<'a, 2, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SdaPin<I> for Alternate<'a, 3, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SclPin<I> for Alternate<'a, 4, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SdaPin<I> for Alternate<'a, 5, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SclPin<I> for Alternate<'a, 6, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SdaPin<I> for Alternate<'a, 7, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SclPin<I> for Alternate<'a, 8, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SdaPin<I> for Alternate<'a, 9, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SclPin<I> for Alternate<'a, 10, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SdaPin<I> for Alternate<'a, 11, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SclPin<I> for Alternate<'a, 12, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SdaPin<I> for Alternate<'a, 13, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SclPin<I> for Alternate<'a, 14, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SdaPin<I> for Alternate<'a, 15, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SclPin<I> for Alternate<'a, 16, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SdaPin<I> for Alternate<'a, 17, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SclPin<I> for Alternate<'a, 18, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SdaPin<I> for Alternate<'a, 19, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SclPin<I> for Alternate<'a, 20, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SdaPin<I> for Alternate<'a, 21, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SclPin<I> for Alternate<'a, 22, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SdaPin<I> for Alternate<'a, 23, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SclPin<I> for Alternate<'a, 24, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SdaPin<I> for Alternate<'a, 25, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SclPin<I> for Alternate<'a, 26, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SdaPin<I> for Alternate<'a, 27, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SclPin<I> for Alternate<'a, 28, gpio::I2c<I>> {}
-    impl<'a, const I: usize> SdaPin<I> for Alternate<'a, 29, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSclSignal<I> for Alternate<'a, 0, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSdaSignal<I> for Alternate<'a, 1, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSclSignal<I> for Alternate<'a, 2, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSdaSignal<I> for Alternate<'a, 3, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSclSignal<I> for Alternate<'a, 4, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSdaSignal<I> for Alternate<'a, 5, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSclSignal<I> for Alternate<'a, 6, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSdaSignal<I> for Alternate<'a, 7, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSclSignal<I> for Alternate<'a, 8, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSdaSignal<I> for Alternate<'a, 9, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSclSignal<I> for Alternate<'a, 10, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSdaSignal<I> for Alternate<'a, 11, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSclSignal<I> for Alternate<'a, 12, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSdaSignal<I> for Alternate<'a, 13, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSclSignal<I> for Alternate<'a, 14, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSdaSignal<I> for Alternate<'a, 15, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSclSignal<I> for Alternate<'a, 16, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSdaSignal<I> for Alternate<'a, 17, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSclSignal<I> for Alternate<'a, 18, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSdaSignal<I> for Alternate<'a, 19, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSclSignal<I> for Alternate<'a, 20, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSdaSignal<I> for Alternate<'a, 21, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSclSignal<I> for Alternate<'a, 22, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSdaSignal<I> for Alternate<'a, 23, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSclSignal<I> for Alternate<'a, 24, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSdaSignal<I> for Alternate<'a, 25, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSclSignal<I> for Alternate<'a, 26, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSdaSignal<I> for Alternate<'a, 27, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSclSignal<I> for Alternate<'a, 28, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSdaSignal<I> for Alternate<'a, 29, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSclSignal<I> for Alternate<'a, 30, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSdaSignal<I> for Alternate<'a, 31, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSclSignal<I> for Alternate<'a, 32, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSdaSignal<I> for Alternate<'a, 33, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSclSignal<I> for Alternate<'a, 34, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSdaSignal<I> for Alternate<'a, 35, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSclSignal<I> for Alternate<'a, 36, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSdaSignal<I> for Alternate<'a, 37, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSclSignal<I> for Alternate<'a, 38, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSdaSignal<I> for Alternate<'a, 39, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSclSignal<I> for Alternate<'a, 40, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSdaSignal<I> for Alternate<'a, 41, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSclSignal<I> for Alternate<'a, 42, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSdaSignal<I> for Alternate<'a, 43, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSclSignal<I> for Alternate<'a, 44, gpio::I2c<I>> {}
+    impl<'a, const I: usize> HasSdaSignal<I> for Alternate<'a, 45, gpio::I2c<I>> {}
 }
 
 #[cfg(test)]
@@ -707,24 +744,25 @@ mod tests {
         InterruptMask, InterruptState, PeriodData, PeriodStart, PeriodStop, RegisterBlock,
         SubAddressByteCount,
     };
-    use core::mem::offset_of;
 
     #[test]
     fn struct_register_block_offset() {
-        assert_eq!(offset_of!(RegisterBlock, config), 0x00);
-        assert_eq!(offset_of!(RegisterBlock, interrupt_state), 0x04);
-        assert_eq!(offset_of!(RegisterBlock, interrupt_mask), 0x05);
-        assert_eq!(offset_of!(RegisterBlock, interrupt_clear), 0x06);
-        assert_eq!(offset_of!(RegisterBlock, interrupt_enable), 0x07);
-        assert_eq!(offset_of!(RegisterBlock, sub_address), 0x08);
-        assert_eq!(offset_of!(RegisterBlock, bus_busy), 0x0c);
-        assert_eq!(offset_of!(RegisterBlock, period_start), 0x10);
-        assert_eq!(offset_of!(RegisterBlock, period_stop), 0x14);
-        assert_eq!(offset_of!(RegisterBlock, period_data), 0x18);
-        assert_eq!(offset_of!(RegisterBlock, fifo_config_0), 0x80);
-        assert_eq!(offset_of!(RegisterBlock, fifo_config_1), 0x84);
-        assert_eq!(offset_of!(RegisterBlock, fifo_write), 0x88);
-        assert_eq!(offset_of!(RegisterBlock, fifo_read), 0x8c);
+        assert_offsets!(RegisterBlock {
+            config: 0x00,
+            interrupt_state: 0x04,
+            interrupt_mask: 0x05,
+            interrupt_clear: 0x06,
+            interrupt_enable: 0x07,
+            sub_address: 0x08,
+            bus_busy: 0x0c,
+            period_start: 0x10,
+            period_stop: 0x14,
+            period_data: 0x18,
+            fifo_config_0: 0x80,
+            fifo_config_1: 0x84,
+            fifo_write: 0x88,
+            fifo_read: 0x8c,
+        });
     }
 
     #[test]
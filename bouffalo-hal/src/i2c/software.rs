@@ -0,0 +1,380 @@
+use embedded_hal::{
+    delay::DelayNs,
+    digital::{InputPin, OutputPin},
+    i2c::{ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation},
+};
+use embedded_time::rate::Hertz;
+
+/// Maximum number of half clock periods a device may stretch the clock.
+const CLOCK_STRETCH_LIMIT: u32 = 1000;
+
+/// Inter-Integrated Circuit bus master by toggling two open drain GPIO pads.
+///
+/// It needs no I2C peripheral, thus can be used on any pair of pads. Bus timing comes
+/// from the delay provider; the actual clock is slower than requested frequency, as
+/// GPIO operations take time as well.
+///
+/// ```ignore
+/// let scl = p.gpio.io6.into_open_drain_output();
+/// let sda = p.gpio.io7.into_open_drain_output();
+/// let mut i2c = SoftwareI2c::new(scl, sda, CycleDelay::new(&c), 100_000.Hz());
+/// let mut buf = [0u8; 2];
+/// i2c.write_read(0x76, &[0xd0], &mut buf).ok();
+/// ```
+pub struct SoftwareI2c<SCL, SDA, D> {
+    scl: SCL,
+    sda: SDA,
+    delay: D,
+    half_period_ns: u32,
+}
+
+impl<SCL: InputPin + OutputPin, SDA: InputPin + OutputPin, D: DelayNs> SoftwareI2c<SCL, SDA, D> {
+    /// Create a software I2C bus on open drain `scl` and `sda` pads.
+    #[inline]
+    pub fn new(mut scl: SCL, mut sda: SDA, delay: D, frequency: Hertz) -> Self {
+        scl.set_high().ok();
+        sda.set_high().ok();
+        Self {
+            scl,
+            sda,
+            delay,
+            half_period_ns: 500_000_000 / frequency.0.max(1),
+        }
+    }
+    /// Release the bus and return the pads and delay provider.
+    #[inline]
+    pub fn free(self) -> (SCL, SDA, D) {
+        (self.scl, self.sda, self.delay)
+    }
+
+    #[inline]
+    fn half_period(&mut self) {
+        self.delay.delay_ns(self.half_period_ns);
+    }
+    /// Release SCL and wait until no device is stretching the clock.
+    fn release_scl(&mut self) -> Result<(), SoftwareI2cError> {
+        self.scl.set_high().map_err(|_| SoftwareI2cError::Pin)?;
+        for _ in 0..CLOCK_STRETCH_LIMIT {
+            if self.scl.is_high().map_err(|_| SoftwareI2cError::Pin)? {
+                return Ok(());
+            }
+            self.half_period();
+        }
+        Err(SoftwareI2cError::ClockStretchTimeout)
+    }
+    fn set_sda(&mut self, high: bool) -> Result<(), SoftwareI2cError> {
+        match high {
+            true => self.sda.set_high(),
+            false => self.sda.set_low(),
+        }
+        .map_err(|_| SoftwareI2cError::Pin)
+    }
+    fn sda_is_high(&mut self) -> Result<bool, SoftwareI2cError> {
+        self.sda.is_high().map_err(|_| SoftwareI2cError::Pin)
+    }
+    /// Send start or repeated start condition.
+    fn start(&mut self) -> Result<(), SoftwareI2cError> {
+        self.set_sda(true)?;
+        self.half_period();
+        self.release_scl()?;
+        self.half_period();
+        if !self.sda_is_high()? {
+            return Err(SoftwareI2cError::ArbitrationLoss);
+        }
+        self.set_sda(false)?;
+        self.half_period();
+        self.scl.set_low().map_err(|_| SoftwareI2cError::Pin)
+    }
+    /// Send stop condition.
+    fn stop(&mut self) -> Result<(), SoftwareI2cError> {
+        self.set_sda(false)?;
+        self.half_period();
+        self.release_scl()?;
+        self.half_period();
+        self.set_sda(true)?;
+        self.half_period();
+        Ok(())
+    }
+    /// Release SCL and SDA without a stop condition, leaving the bus to the other master.
+    fn release_bus(&mut self) -> Result<(), SoftwareI2cError> {
+        self.set_sda(true)?;
+        self.scl.set_high().map_err(|_| SoftwareI2cError::Pin)
+    }
+    fn write_bit(&mut self, bit: bool) -> Result<(), SoftwareI2cError> {
+        self.set_sda(bit)?;
+        self.half_period();
+        self.release_scl()?;
+        self.half_period();
+        if bit && !self.sda_is_high()? {
+            return Err(SoftwareI2cError::ArbitrationLoss);
+        }
+        self.scl.set_low().map_err(|_| SoftwareI2cError::Pin)
+    }
+    fn read_bit(&mut self) -> Result<bool, SoftwareI2cError> {
+        self.set_sda(true)?;
+        self.half_period();
+        self.release_scl()?;
+        self.half_period();
+        let bit = self.sda_is_high()?;
+        self.scl.set_low().map_err(|_| SoftwareI2cError::Pin)?;
+        Ok(bit)
+    }
+    /// Write a byte, returning if the device acknowledged.
+    fn write_byte(&mut self, byte: u8) -> Result<bool, SoftwareI2cError> {
+        for i in (0..8).rev() {
+            self.write_bit(byte & (1 << i) != 0)?;
+        }
+        Ok(!self.read_bit()?)
+    }
+    fn read_byte(&mut self, ack: bool) -> Result<u8, SoftwareI2cError> {
+        let mut byte = 0;
+        for _ in 0..8 {
+            byte = (byte << 1) | self.read_bit()? as u8;
+        }
+        self.write_bit(!ack)?;
+        Ok(byte)
+    }
+    fn transaction_inner(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), SoftwareI2cError> {
+        let mut last_read = None;
+        let count = operations.len();
+        for idx in 0..count {
+            let is_read = matches!(operations[idx], Operation::Read(_));
+            // Consecutive operations of the same direction are merged without restart.
+            let next_is_read = match operations.get(idx + 1) {
+                Some(Operation::Read(_)) => Some(true),
+                Some(Operation::Write(_)) => Some(false),
+                None => None,
+            };
+            if last_read != Some(is_read) {
+                self.start()?;
+                if !self.write_byte((address << 1) | is_read as u8)? {
+                    return Err(SoftwareI2cError::NoAcknowledge(
+                        NoAcknowledgeSource::Address,
+                    ));
+                }
+            }
+            match &mut operations[idx] {
+                Operation::Write(bytes) => {
+                    for &byte in bytes.iter() {
+                        if !self.write_byte(byte)? {
+                            return Err(SoftwareI2cError::NoAcknowledge(NoAcknowledgeSource::Data));
+                        }
+                    }
+                }
+                Operation::Read(bytes) => {
+                    let len = bytes.len();
+                    for (i, byte) in bytes.iter_mut().enumerate() {
+                        // Last byte before a stop or a restart is not acknowledged.
+                        let ack = i + 1 < len || next_is_read == Some(true);
+                        *byte = self.read_byte(ack)?;
+                    }
+                }
+            }
+            last_read = Some(is_read);
+        }
+        Ok(())
+    }
+}
+
+/// Software I2C error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoftwareI2cError {
+    /// Device did not acknowledge the address or data byte.
+    NoAcknowledge(NoAcknowledgeSource),
+    /// SDA is held low by another device while this master releases it.
+    ArbitrationLoss,
+    /// Device stretched the clock for too long.
+    ClockStretchTimeout,
+    /// Failed to operate on GPIO pads.
+    Pin,
+}
+
+impl embedded_hal::i2c::Error for SoftwareI2cError {
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        match self {
+            SoftwareI2cError::NoAcknowledge(source) => ErrorKind::NoAcknowledge(*source),
+            SoftwareI2cError::ArbitrationLoss => ErrorKind::ArbitrationLoss,
+            SoftwareI2cError::ClockStretchTimeout | SoftwareI2cError::Pin => ErrorKind::Other,
+        }
+    }
+}
+
+impl<SCL, SDA, D> ErrorType for SoftwareI2c<SCL, SDA, D> {
+    type Error = SoftwareI2cError;
+}
+
+impl<SCL: InputPin + OutputPin, SDA: InputPin + OutputPin, D: DelayNs> I2c
+    for SoftwareI2c<SCL, SDA, D>
+{
+    #[inline]
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let ans = self.transaction_inner(address, operations);
+        // Bus now belongs to the master that won arbitration; a stop would corrupt its transfer.
+        if ans == Err(SoftwareI2cError::ArbitrationLoss) {
+            return ans.and(self.release_bus());
+        }
+        // Stop condition is sent even on other errors, leaving the bus idle.
+        let stop = self.stop();
+        ans.and(stop)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SoftwareI2c, SoftwareI2cError};
+    use core::cell::Cell;
+    use embedded_hal::{
+        delay::DelayNs,
+        digital::{ErrorType, InputPin, OutputPin},
+        i2c::{I2c, NoAcknowledgeSource},
+    };
+    use embedded_time::rate::Hertz;
+
+    struct Bus {
+        scl: Cell<bool>,
+        sda: Cell<bool>,
+        scl_stuck_low: bool,
+        // Another master pulls SDA low from this many SCL rising edges on.
+        sda_held_low_from: Cell<u32>,
+        // SDA falling edges while SCL is high, i.e. start conditions.
+        start_count: Cell<u32>,
+        // SDA levels sampled on SCL rising edges.
+        samples: Cell<u32>,
+        sample_count: Cell<u32>,
+    }
+
+    impl Bus {
+        const fn new(scl_stuck_low: bool) -> Self {
+            Bus {
+                scl: Cell::new(true),
+                sda: Cell::new(true),
+                scl_stuck_low,
+                sda_held_low_from: Cell::new(u32::MAX),
+                start_count: Cell::new(0),
+                samples: Cell::new(0),
+                sample_count: Cell::new(0),
+            }
+        }
+    }
+
+    struct Scl<'a>(&'a Bus);
+    struct Sda<'a>(&'a Bus);
+    struct NoDelay;
+
+    impl ErrorType for Scl<'_> {
+        type Error = core::convert::Infallible;
+    }
+
+    impl InputPin for Scl<'_> {
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
+            Ok(self.0.scl.get() && !self.0.scl_stuck_low)
+        }
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
+            self.is_high().map(|x| !x)
+        }
+    }
+
+    impl OutputPin for Scl<'_> {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.0.scl.set(false);
+            Ok(())
+        }
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            if !self.0.scl.get() {
+                let bus = self.0;
+                bus.samples
+                    .set((bus.samples.get() << 1) | bus.sda.get() as u32);
+                bus.sample_count.set(bus.sample_count.get() + 1);
+            }
+            self.0.scl.set(true);
+            Ok(())
+        }
+    }
+
+    impl ErrorType for Sda<'_> {
+        type Error = core::convert::Infallible;
+    }
+
+    impl InputPin for Sda<'_> {
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
+            let bus = self.0;
+            Ok(bus.sda.get() && bus.sample_count.get() < bus.sda_held_low_from.get())
+        }
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
+            self.is_high().map(|x| !x)
+        }
+    }
+
+    impl OutputPin for Sda<'_> {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            if self.0.sda.get() && self.0.scl.get() {
+                self.0.start_count.set(self.0.start_count.get() + 1);
+            }
+            self.0.sda.set(false);
+            Ok(())
+        }
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.0.sda.set(true);
+            Ok(())
+        }
+    }
+
+    impl DelayNs for NoDelay {
+        fn delay_ns(&mut self, _: u32) {}
+    }
+
+    #[test]
+    fn software_i2c_address_not_acknowledged() {
+        let bus = Bus::new(false);
+        let mut i2c = SoftwareI2c::new(Scl(&bus), Sda(&bus), NoDelay, Hertz(100_000));
+        assert_eq!(
+            i2c.write(0x50, &[0x12]),
+            Err(SoftwareI2cError::NoAcknowledge(
+                NoAcknowledgeSource::Address
+            ))
+        );
+        // 7-bit address and write bit, acknowledge bit released high, then SCL rising
+        // edge of stop condition with SDA low.
+        assert_eq!(bus.sample_count.get(), 8 + 1 + 1);
+        assert_eq!(bus.samples.get(), (0xa0 << 2) | 0b10);
+        // Bus is left idle.
+        assert!(bus.scl.get() && bus.sda.get());
+    }
+
+    #[test]
+    fn software_i2c_clock_stretch_timeout() {
+        let bus = Bus::new(true);
+        let mut i2c = SoftwareI2c::new(Scl(&bus), Sda(&bus), NoDelay, Hertz(100_000));
+        let mut buf = [0u8; 1];
+        assert_eq!(
+            i2c.read(0x50, &mut buf),
+            Err(SoftwareI2cError::ClockStretchTimeout)
+        );
+    }
+
+    #[test]
+    fn software_i2c_arbitration_loss_releases_bus() {
+        let bus = Bus::new(false);
+        let mut i2c = SoftwareI2c::new(Scl(&bus), Sda(&bus), NoDelay, Hertz(100_000));
+        // Other master drives its first address bit low while this one sends high.
+        bus.sda_held_low_from.set(1);
+        assert_eq!(
+            i2c.write(0x50, &[0x12]),
+            Err(SoftwareI2cError::ArbitrationLoss)
+        );
+        // Only the initial start; SDA is not pulled low again for a stop condition.
+        assert_eq!(bus.start_count.get(), 1);
+        assert_eq!(bus.sample_count.get(), 1);
+        // Both lines are released to the winning master.
+        assert!(bus.scl.get() && bus.sda.get());
+    }
+}
@@ -58,17 +58,18 @@ pub struct IoConfig(u32);
 #[cfg(test)]
 mod tests {
     use super::RegisterBlock;
-    use core::mem::offset_of;
 
     #[test]
     fn struct_register_block_offset() {
-        assert_eq!(offset_of!(RegisterBlock, config), 0x0);
-        assert_eq!(offset_of!(RegisterBlock, interrupt_config), 0x4);
-        assert_eq!(offset_of!(RegisterBlock, bclk_config), 0x08);
-        assert_eq!(offset_of!(RegisterBlock, fifo_config_0), 0x80);
-        assert_eq!(offset_of!(RegisterBlock, fifo_config_1), 0x84);
-        assert_eq!(offset_of!(RegisterBlock, fifo_write), 0x88);
-        assert_eq!(offset_of!(RegisterBlock, fifo_read), 0x8c);
-        assert_eq!(offset_of!(RegisterBlock, io_config), 0xfc);
+        assert_offsets!(RegisterBlock {
+            config: 0x0,
+            interrupt_config: 0x4,
+            bclk_config: 0x08,
+            fifo_config_0: 0x80,
+            fifo_config_1: 0x84,
+            fifo_write: 0x88,
+            fifo_read: 0x8c,
+            io_config: 0xfc,
+        });
     }
 }
@@ -58,18 +58,19 @@ pub struct FifoConfig1(u32);
 #[cfg(test)]
 mod tests {
     use super::RegisterBlock;
-    use core::mem::offset_of;
 
     #[test]
     fn struct_register_block_offset() {
-        assert_eq!(offset_of!(RegisterBlock, receive_config), 0x40);
-        assert_eq!(offset_of!(RegisterBlock, receive_interrupt), 0x44);
-        assert_eq!(offset_of!(RegisterBlock, receive_threshold), 0x48);
-        assert_eq!(offset_of!(RegisterBlock, receive_data_count), 0x50);
-        assert_eq!(offset_of!(RegisterBlock, receive_word_0), 0x54);
-        assert_eq!(offset_of!(RegisterBlock, receive_word_1), 0x58);
-        assert_eq!(offset_of!(RegisterBlock, fifo_config_0), 0x80);
-        assert_eq!(offset_of!(RegisterBlock, fifo_config_1), 0x84);
-        assert_eq!(offset_of!(RegisterBlock, fifo_read), 0x8c);
+        assert_offsets!(RegisterBlock {
+            receive_config: 0x40,
+            receive_interrupt: 0x44,
+            receive_threshold: 0x48,
+            receive_data_count: 0x50,
+            receive_word_0: 0x54,
+            receive_word_1: 0x58,
+            fifo_config_0: 0x80,
+            fifo_config_1: 0x84,
+            fifo_read: 0x8c,
+        });
     }
 }
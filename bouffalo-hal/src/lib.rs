@@ -8,38 +8,92 @@
 //! this package with `embedded-hal` ecosystem drivers to provide abundant amount of features.
 #![no_std]
 
+/// Assert byte offsets of register block fields in unit tests.
+///
+/// Every peripheral `RegisterBlock` should have a `struct_register_block_offset` test
+/// written with this macro, listing each public field with its offset from the manual.
+///
+/// ```ignore
+/// assert_offsets!(RegisterBlock {
+///     transmit_config: 0x00,
+///     receive_config: 0x04,
+/// });
+/// ```
+#[cfg(test)]
+macro_rules! assert_offsets {
+    ($ty:ty { $($field:ident: $offset:expr),+ $(,)? }) => {
+        $(
+            assert_eq!(
+                core::mem::offset_of!($ty, $field),
+                $offset,
+                concat!("offset of field `", stringify!($field), "` in `", stringify!($ty), "`"),
+            );
+        )+
+    };
+}
+
+#[cfg(test)]
+mod mock;
+
 pub mod clocks;
+pub mod delay;
 
+#[cfg(feature = "audio")]
 pub mod audio;
+#[cfg(feature = "dbi")]
 pub mod dbi;
+#[cfg(feature = "dma")]
 pub mod dma;
+#[cfg(feature = "efuse")]
+pub mod efuse;
+#[cfg(feature = "emac")]
 pub mod emac;
 pub mod glb;
 pub mod gpio;
+#[cfg(feature = "gpip")]
 pub mod gpip;
+#[cfg(feature = "hbn")]
 pub mod hbn;
+#[cfg(feature = "i2c")]
 pub mod i2c;
+#[cfg(feature = "i2s")]
 pub mod i2s;
+#[cfg(feature = "ir")]
 pub mod ir;
+#[cfg(feature = "lz4d")]
 pub mod lz4d;
+#[cfg(feature = "psram")]
 pub mod psram;
+#[cfg(feature = "pwm")]
 pub mod pwm;
+#[cfg(feature = "sdio")]
 pub mod sdio;
+#[cfg(feature = "sec")]
 pub mod sec;
+#[cfg(feature = "spi")]
 pub mod spi;
+#[cfg(feature = "timer")]
 pub mod timer;
+#[cfg(feature = "uart")]
 pub mod uart;
+#[cfg(feature = "usb")]
 pub mod usb;
 
 #[doc(hidden)]
 pub mod prelude {
     pub use crate::gpio::{IntoPad as _, IntoPadv2 as _};
+    #[cfg(feature = "lz4d")]
     pub use crate::lz4d::Lz4dExt as _;
+    #[cfg(feature = "uart")]
     pub use crate::uart::UartExt as _;
+    pub use embedded_hal::delay::DelayNs as _;
     pub use embedded_hal::digital::{InputPin as _, OutputPin as _, PinState};
+    #[cfg(feature = "i2c")]
     pub use embedded_hal::i2c::I2c as _;
+    #[cfg(feature = "pwm")]
     pub use embedded_hal::pwm::SetDutyCycle as _;
     pub use embedded_io::{Read as _, Write as _};
+    #[cfg(feature = "async")]
     pub use embedded_io_async::{Read as _, Write as _};
 }
 
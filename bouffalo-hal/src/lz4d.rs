@@ -300,15 +300,16 @@ impl<T: Deref<Target = RegisterBlock>> Lz4dExt for T {
 #[cfg(test)]
 mod tests {
     use super::RegisterBlock;
-    use core::mem::offset_of;
     #[test]
     fn struct_register_block_offset() {
-        assert_eq!(offset_of!(RegisterBlock, config), 0x00);
-        assert_eq!(offset_of!(RegisterBlock, source_start), 0x10);
-        assert_eq!(offset_of!(RegisterBlock, source_end), 0x14);
-        assert_eq!(offset_of!(RegisterBlock, destination_start), 0x18);
-        assert_eq!(offset_of!(RegisterBlock, destination_end), 0x1c);
-        assert_eq!(offset_of!(RegisterBlock, interrupt_enable), 0x20);
-        assert_eq!(offset_of!(RegisterBlock, interrupt_state), 0x24);
+        assert_offsets!(RegisterBlock {
+            config: 0x00,
+            source_start: 0x10,
+            source_end: 0x14,
+            destination_start: 0x18,
+            destination_end: 0x1c,
+            interrupt_enable: 0x20,
+            interrupt_state: 0x24,
+        });
     }
 }
@@ -0,0 +1,37 @@
+//! Register blocks backed by plain memory, for testing drivers on host.
+use core::{cell::UnsafeCell, mem::MaybeUninit};
+
+/// Register block `T` backed by plain memory, for testing driver logic on host.
+///
+/// Registers read back the value last written. Hardware behaviors, such as updating state
+/// registers or clearing bits on write, do not happen; prepare them with `write`.
+pub struct MockRegisters<T> {
+    memory: UnsafeCell<MaybeUninit<T>>,
+}
+
+impl<T> MockRegisters<T> {
+    /// Create a register block with all registers cleared to zero.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            memory: UnsafeCell::new(MaybeUninit::zeroed()),
+        }
+    }
+    /// Get register block to pass into drivers.
+    #[inline]
+    pub fn registers(&self) -> &T {
+        unsafe { &*(self.memory.get() as *const T) }
+    }
+    /// Read raw word of 32-bit register selected by `field`, including write-only registers.
+    #[inline]
+    pub fn read<R>(&self, field: impl FnOnce(&T) -> &R) -> u32 {
+        assert_eq!(size_of::<R>(), 4, "register should be 32 bits wide");
+        unsafe { (field(self.registers()) as *const R as *const u32).read_volatile() }
+    }
+    /// Write raw word to 32-bit register selected by `field`, including read-only registers.
+    #[inline]
+    pub fn write<R>(&self, field: impl FnOnce(&T) -> &R, value: u32) {
+        assert_eq!(size_of::<R>(), 4, "register should be 32 bits wide");
+        unsafe { (field(self.registers()) as *const R as *mut u32).write_volatile(value) }
+    }
+}
@@ -58,12 +58,13 @@ pub fn init_psram(psram: &RegisterBlock, glb: &glb::v2::RegisterBlock) {
 #[cfg(test)]
 mod tests {
     use super::RegisterBlock;
-    use core::mem::offset_of;
 
     #[test]
     fn struct_register_block_offset() {
-        assert_eq!(offset_of!(RegisterBlock, basic_config), 0x0);
-        assert_eq!(offset_of!(RegisterBlock, psram_config), 0x20);
-        assert_eq!(offset_of!(RegisterBlock, phy_config), 0x100);
+        assert_offsets!(RegisterBlock {
+            basic_config: 0x0,
+            psram_config: 0x20,
+            phy_config: 0x100,
+        });
     }
 }
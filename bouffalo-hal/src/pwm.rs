@@ -1,8 +1,28 @@
 //! Pulse Width Modulation peripheral.
+//!
+//! # Pad routing
+//!
+//! PWM channel outputs are the only timed events that the global register (`GLB`) function
+//! multiplexer routes to pads; edges are generated by hardware without processor involvement.
+//! Any pad can carry a PWM signal once converted with `into_*_pwm::<F>()`, where `F` selects
+//! the `Pwm0` or `Pwm1` pad function, and signal on the pad is decided by pad number:
+//!
+//! | Pad function | Signal setting | Signal on pad `n` |
+//! |:-------------|:---------------|:------------------|
+//! | `Pwm<0>` | `SingleEnd` | group 0, channel `n % 4` |
+//! | `Pwm<0>` | `DifferentialEnd` | group 0, channel `n % 8 / 2`, positive if `n` is even |
+//! | `Pwm<1>` | `SingleEnd` | group 1, channel `n % 4` |
+//!
+//! Pads that also carry external break input are listed by [`HasPwmExternalBreak`]
+//! implementations. Mapping is checked at compile time when binding a pad with
+//! [`Channel::positive_signal_pin`] or [`Channel::negative_signal_pin`].
+//!
+//! Timer peripheral has no pad function, so timer events cannot drive a pad directly;
+//! use a PWM channel for hardware-timed pulses instead.
 use crate::clocks::Clocks;
 use crate::glb::{
     self,
-    v2::{PwmSignal0, PwmSignal1},
+    v2::{ClockGate, PwmClock, PwmSignal0, PwmSignal1},
 };
 use crate::gpio::{self, Alternate};
 use core::marker::PhantomData;
@@ -659,8 +679,8 @@ impl<PWM: Deref<Target = RegisterBlock>, S0: Signal0, S1: Signal1> Pwm<PWM, (S0,
         unsafe {
             glb.pwm_config
                 .modify(|config| config.set_signal_0(S0::VALUE).set_signal_1(S1::VALUE));
-            glb.clock_config_1.modify(|config| config.enable_pwm());
         }
+        PwmClock::enable_clock(glb);
         drop((signal_0, signal_1));
         Pwm {
             group0: Channels {
@@ -683,6 +703,18 @@ impl<PWM: Deref<Target = RegisterBlock>, S0: Signal0, S1: Signal1> Pwm<PWM, (S0,
             },
         }
     }
+
+    /// Release the pulse width modulation instance and return the peripheral.
+    ///
+    /// Clock gate of the peripheral is disabled.
+    #[inline]
+    pub fn free<GLB>(self, glb: &GLB) -> PWM
+    where
+        GLB: Deref<Target = glb::v2::RegisterBlock>,
+    {
+        PwmClock::disable_clock(glb);
+        self.group1.pwm
+    }
 }
 
 /// PWM group with all its channels.
@@ -1190,25 +1222,28 @@ mod tests {
         Interrupt, InterruptClear, InterruptConfig, InterruptEnable, InterruptMask, InterruptState,
         PeriodConfig, Polarity, RegisterBlock, StopMode, Threshold,
     };
-    use core::mem::offset_of;
 
     #[test]
     fn struct_register_block_offset() {
-        assert_eq!(offset_of!(RegisterBlock, interrupt_config), 0x00);
-        assert_eq!(offset_of!(RegisterBlock, group), 0x40);
+        assert_offsets!(RegisterBlock {
+            interrupt_config: 0x00,
+            group: 0x40,
+        });
     }
 
     #[test]
     fn struct_group_offset() {
-        assert_eq!(offset_of!(Group, group_config), 0x00);
-        assert_eq!(offset_of!(Group, channel_config), 0x04);
-        assert_eq!(offset_of!(Group, period_config), 0x08);
-        assert_eq!(offset_of!(Group, dead_time), 0x0c);
-        assert_eq!(offset_of!(Group, threshold), 0x10);
-        assert_eq!(offset_of!(Group, interrupt_state), 0x20);
-        assert_eq!(offset_of!(Group, interrupt_mask), 0x24);
-        assert_eq!(offset_of!(Group, interrupt_clear), 0x28);
-        assert_eq!(offset_of!(Group, interrupt_enable), 0x2c);
+        assert_offsets!(Group {
+            group_config: 0x00,
+            channel_config: 0x04,
+            period_config: 0x08,
+            dead_time: 0x0c,
+            threshold: 0x10,
+            interrupt_state: 0x20,
+            interrupt_mask: 0x24,
+            interrupt_clear: 0x28,
+            interrupt_enable: 0x2c,
+        });
     }
 
     #[test]
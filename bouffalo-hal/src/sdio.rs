@@ -3832,75 +3832,55 @@ mod tests {
         SlotInterruptStatus, SlotType, SoftwareReset, SpecificVersion, SpeedMode, SpiMode,
         SystemAddress, TimeoutControl, TransferMode, TransferWidth, TxConfiguration, WakeupControl,
     };
-    use core::mem::offset_of;
 
     #[test]
     fn struct_register_block_offset() {
-        assert_eq!(offset_of!(RegisterBlock, system_address), 0x0);
-        assert_eq!(offset_of!(RegisterBlock, block_size), 0x4);
-        assert_eq!(offset_of!(RegisterBlock, block_count), 0x06);
-        assert_eq!(offset_of!(RegisterBlock, argument), 0x08);
-        assert_eq!(offset_of!(RegisterBlock, transfer_mode), 0x0c);
-        assert_eq!(offset_of!(RegisterBlock, command), 0x0e);
-        assert_eq!(offset_of!(RegisterBlock, response), 0x10);
-        assert_eq!(offset_of!(RegisterBlock, buffer_data_port), 0x20);
-        assert_eq!(offset_of!(RegisterBlock, present_state), 0x24);
-        assert_eq!(offset_of!(RegisterBlock, host_control_1), 0x28);
-        assert_eq!(offset_of!(RegisterBlock, power_control), 0x29);
-        assert_eq!(offset_of!(RegisterBlock, block_gap), 0x2a);
-        assert_eq!(offset_of!(RegisterBlock, wakeup_control), 0x2b);
-        assert_eq!(offset_of!(RegisterBlock, clock_control), 0x2c);
-        assert_eq!(offset_of!(RegisterBlock, timeout_control), 0x2e);
-        assert_eq!(offset_of!(RegisterBlock, software_reset), 0x2f);
-        assert_eq!(offset_of!(RegisterBlock, normal_interrupt_status), 0x30);
-        assert_eq!(offset_of!(RegisterBlock, error_interrupt_status), 0x32);
-        assert_eq!(
-            offset_of!(RegisterBlock, normal_interrupt_status_enable),
-            0x34
-        );
-        assert_eq!(
-            offset_of!(RegisterBlock, error_interrupt_status_enable),
-            0x36
-        );
-        assert_eq!(
-            offset_of!(RegisterBlock, normal_interrupt_signal_enable),
-            0x38
-        );
-        assert_eq!(
-            offset_of!(RegisterBlock, error_interrupt_signal_enable),
-            0x3a
-        );
-        assert_eq!(offset_of!(RegisterBlock, auto_cmd_error_status), 0x3c);
-        assert_eq!(offset_of!(RegisterBlock, host_control_2), 0x3e);
-        assert_eq!(offset_of!(RegisterBlock, capabilities), 0x40);
-        assert_eq!(offset_of!(RegisterBlock, max_current_capabilities), 0x48);
-        assert_eq!(
-            offset_of!(RegisterBlock, force_event_auto_cmd_error_status),
-            0x50
-        );
-        assert_eq!(
-            offset_of!(RegisterBlock, force_event_error_interrupt_status),
-            0x52
-        );
-        assert_eq!(offset_of!(RegisterBlock, adma2_error_status), 0x54);
-        assert_eq!(offset_of!(RegisterBlock, adma2_system_address), 0x58);
-        assert_eq!(offset_of!(RegisterBlock, preset_value), 0x60);
-        assert_eq!(
-            offset_of!(RegisterBlock, adma3_integrated_descriptor_address),
-            0x78
-        );
-        assert_eq!(offset_of!(RegisterBlock, shared_bus_control), 0xe0);
-        assert_eq!(offset_of!(RegisterBlock, slot_interrupt_status), 0xfc);
-        assert_eq!(offset_of!(RegisterBlock, host_controller_version), 0xfe);
-        assert_eq!(offset_of!(RegisterBlock, sd_extra_parameters), 0x100);
-        assert_eq!(offset_of!(RegisterBlock, fifo_parameters), 0x104);
-        assert_eq!(offset_of!(RegisterBlock, spi_mode), 0x108);
-        assert_eq!(offset_of!(RegisterBlock, clock_and_burst_size_setup), 0x10a);
-        assert_eq!(offset_of!(RegisterBlock, ce_ata), 0x10c);
-        assert_eq!(offset_of!(RegisterBlock, pad_io_setup), 0x110);
-        assert_eq!(offset_of!(RegisterBlock, rx_configuration), 0x114);
-        assert_eq!(offset_of!(RegisterBlock, tx_configuration), 0x118);
-        assert_eq!(offset_of!(RegisterBlock, tuning_configuration), 0x11c);
+        assert_offsets!(RegisterBlock {
+            system_address: 0x0,
+            block_size: 0x4,
+            block_count: 0x06,
+            argument: 0x08,
+            transfer_mode: 0x0c,
+            command: 0x0e,
+            response: 0x10,
+            buffer_data_port: 0x20,
+            present_state: 0x24,
+            host_control_1: 0x28,
+            power_control: 0x29,
+            block_gap: 0x2a,
+            wakeup_control: 0x2b,
+            clock_control: 0x2c,
+            timeout_control: 0x2e,
+            software_reset: 0x2f,
+            normal_interrupt_status: 0x30,
+            error_interrupt_status: 0x32,
+            normal_interrupt_status_enable: 0x34,
+            error_interrupt_status_enable: 0x36,
+            normal_interrupt_signal_enable: 0x38,
+            error_interrupt_signal_enable: 0x3a,
+            auto_cmd_error_status: 0x3c,
+            host_control_2: 0x3e,
+            capabilities: 0x40,
+            max_current_capabilities: 0x48,
+            force_event_auto_cmd_error_status: 0x50,
+            force_event_error_interrupt_status: 0x52,
+            adma2_error_status: 0x54,
+            adma2_system_address: 0x58,
+            preset_value: 0x60,
+            adma3_integrated_descriptor_address: 0x78,
+            shared_bus_control: 0xe0,
+            slot_interrupt_status: 0xfc,
+            host_controller_version: 0xfe,
+            sd_extra_parameters: 0x100,
+            fifo_parameters: 0x104,
+            spi_mode: 0x108,
+            clock_and_burst_size_setup: 0x10a,
+            ce_ata: 0x10c,
+            pad_io_setup: 0x110,
+            rx_configuration: 0x114,
+            tx_configuration: 0x118,
+            tuning_configuration: 0x11c,
+        });
     }
 
     #[test]
@@ -562,22 +562,23 @@ impl ControlProtection {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use core::mem::offset_of;
 
     #[test]
     fn struct_register_block_offset() {
-        assert_eq!(offset_of!(RegisterBlock, control), 0x00);
-        assert_eq!(offset_of!(RegisterBlock, message_source_address), 0x04);
-        assert_eq!(offset_of!(RegisterBlock, message_destination_address), 0x08);
-        assert_eq!(offset_of!(RegisterBlock, status), 0x0C);
-        assert_eq!(offset_of!(RegisterBlock, initial_vector), 0x10);
-        assert_eq!(offset_of!(RegisterBlock, key), 0x20);
-        assert_eq!(offset_of!(RegisterBlock, key_select_0), 0x40);
-        assert_eq!(offset_of!(RegisterBlock, key_select_1), 0x44);
-        assert_eq!(offset_of!(RegisterBlock, endianness), 0x48);
-        assert_eq!(offset_of!(RegisterBlock, secure_boot), 0x4C);
-        assert_eq!(offset_of!(RegisterBlock, link_config_address), 0x50);
-        assert_eq!(offset_of!(RegisterBlock, control_protection), 0xFC);
+        assert_offsets!(RegisterBlock {
+            control: 0x00,
+            message_source_address: 0x04,
+            message_destination_address: 0x08,
+            status: 0x0C,
+            initial_vector: 0x10,
+            key: 0x20,
+            key_select_0: 0x40,
+            key_select_1: 0x44,
+            endianness: 0x48,
+            secure_boot: 0x4C,
+            link_config_address: 0x50,
+            control_protection: 0xFC,
+        });
     }
 
     #[test]
@@ -296,15 +296,16 @@ impl ControlProtection {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use core::mem::offset_of;
 
     #[test]
     fn struct_register_block_offset() {
-        assert_eq!(offset_of!(RegisterBlock, control_0), 0x00);
-        assert_eq!(offset_of!(RegisterBlock, control_1), 0x04);
-        assert_eq!(offset_of!(RegisterBlock, control_2), 0x08);
-        assert_eq!(offset_of!(RegisterBlock, control_3), 0x0C);
-        assert_eq!(offset_of!(RegisterBlock, control_protection), 0xFC);
+        assert_offsets!(RegisterBlock {
+            control_0: 0x00,
+            control_1: 0x04,
+            control_2: 0x08,
+            control_3: 0x0C,
+            control_protection: 0xFC,
+        });
     }
     #[test]
     fn struct_control_0_functions() {
@@ -200,14 +200,15 @@ impl ControlProtection {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use core::mem::offset_of;
 
     #[test]
     fn struct_register_block_offset() {
-        assert_eq!(offset_of!(RegisterBlock, control), 0x00);
-        assert_eq!(offset_of!(RegisterBlock, link_config_address), 0x04);
-        assert_eq!(offset_of!(RegisterBlock, status), 0x08);
-        assert_eq!(offset_of!(RegisterBlock, control_protection), 0xFC);
+        assert_offsets!(RegisterBlock {
+            control: 0x00,
+            link_config_address: 0x04,
+            status: 0x08,
+            control_protection: 0xFC,
+        });
     }
 
     #[test]
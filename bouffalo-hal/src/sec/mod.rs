@@ -151,17 +151,18 @@ impl ControlProtectionRd {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use core::mem::offset_of;
 
     #[test]
     fn struct_register_block_offset() {
-        assert_eq!(offset_of!(RegisterBlock, sha), 0x000);
-        assert_eq!(offset_of!(RegisterBlock, aes), 0x100);
-        assert_eq!(offset_of!(RegisterBlock, trng), 0x200);
-        assert_eq!(offset_of!(RegisterBlock, pka), 0x300);
-        assert_eq!(offset_of!(RegisterBlock, cdet), 0x400);
-        assert_eq!(offset_of!(RegisterBlock, gmac), 0x500);
-        assert_eq!(offset_of!(RegisterBlock, control_protection_rd), 0xF00);
+        assert_offsets!(RegisterBlock {
+            sha: 0x000,
+            aes: 0x100,
+            trng: 0x200,
+            pka: 0x300,
+            cdet: 0x400,
+            gmac: 0x500,
+            control_protection_rd: 0xF00,
+        });
     }
 
     #[test]
@@ -293,16 +293,17 @@ impl ControlProtection {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use core::mem::offset_of;
 
     #[test]
     fn struct_register_block_offset() {
-        assert_eq!(offset_of!(RegisterBlock, control_0), 0x00);
-        assert_eq!(offset_of!(RegisterBlock, seed), 0x0C);
-        assert_eq!(offset_of!(RegisterBlock, control_1), 0x10);
-        assert_eq!(offset_of!(RegisterBlock, rw), 0x40);
-        assert_eq!(offset_of!(RegisterBlock, rw_burst), 0x60);
-        assert_eq!(offset_of!(RegisterBlock, control_protection), 0xFC);
+        assert_offsets!(RegisterBlock {
+            control_0: 0x00,
+            seed: 0x0C,
+            control_1: 0x10,
+            rw: 0x40,
+            rw_burst: 0x60,
+            control_protection: 0xFC,
+        });
     }
     #[test]
     fn struct_control_0_functions() {
@@ -312,18 +312,19 @@ impl ControlProtection {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use core::mem::offset_of;
 
     #[test]
     fn struct_register_block_offset() {
-        assert_eq!(offset_of!(RegisterBlock, control), 0x00);
-        assert_eq!(offset_of!(RegisterBlock, message_source_address), 0x04);
-        assert_eq!(offset_of!(RegisterBlock, status), 0x08);
-        assert_eq!(offset_of!(RegisterBlock, endianness), 0x0C);
-        assert_eq!(offset_of!(RegisterBlock, hash_l), 0x10);
-        assert_eq!(offset_of!(RegisterBlock, hash_h), 0x30);
-        assert_eq!(offset_of!(RegisterBlock, link_config_address), 0x50);
-        assert_eq!(offset_of!(RegisterBlock, control_protection), 0xFC);
+        assert_offsets!(RegisterBlock {
+            control: 0x00,
+            message_source_address: 0x04,
+            status: 0x08,
+            endianness: 0x0C,
+            hash_l: 0x10,
+            hash_h: 0x30,
+            link_config_address: 0x50,
+            control_protection: 0xFC,
+        });
     }
 
     #[test]
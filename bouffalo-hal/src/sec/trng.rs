@@ -460,19 +460,20 @@ impl ControlProtection {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use core::mem::offset_of;
 
     #[test]
     fn struct_register_block_offset() {
-        assert_eq!(offset_of!(RegisterBlock, control_0), 0x00);
-        assert_eq!(offset_of!(RegisterBlock, status), 0x04);
-        assert_eq!(offset_of!(RegisterBlock, output_data), 0x08);
-        assert_eq!(offset_of!(RegisterBlock, test), 0x28);
-        assert_eq!(offset_of!(RegisterBlock, control_1), 0x2C);
-        assert_eq!(offset_of!(RegisterBlock, control_2), 0x30);
-        assert_eq!(offset_of!(RegisterBlock, control_3), 0x34);
-        assert_eq!(offset_of!(RegisterBlock, test_output), 0x40);
-        assert_eq!(offset_of!(RegisterBlock, control_protection), 0xFC);
+        assert_offsets!(RegisterBlock {
+            control_0: 0x00,
+            status: 0x04,
+            output_data: 0x08,
+            test: 0x28,
+            control_1: 0x2C,
+            control_2: 0x30,
+            control_3: 0x34,
+            test_output: 0x40,
+            control_protection: 0xFC,
+        });
     }
 
     #[test]
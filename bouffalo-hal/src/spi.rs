@@ -1,6 +1,19 @@
 //! Serial Peripheral Interface peripheral.
-
-use crate::glb::{self, v2::SpiMode};
+//!
+//! # Data lanes
+//!
+//! This peripheral only has single-lane transfers, with one MOSI and one MISO signal;
+//! its configuration register has no dual or quad mode, and the pad multiplexer carries
+//! no extra data signals, so lane width is not part of the transfer API. Pad `n` carries
+//! CS, MOSI, MISO and clock signals for `n % 4` of 0, 1, 2 and 3 respectively.
+//!
+//! Quad-lane displays are served by the display bus interface in [`dbi`](crate::dbi)
+//! module, and quad-lane flash by the flash controller, which are separate peripherals.
+
+use crate::glb::{
+    self,
+    v2::{ClockGate, SpiClock, SpiMode},
+};
 use crate::gpio::{self, Alternate};
 use core::cmp::max;
 use core::ops::Deref;
@@ -657,6 +670,7 @@ impl<SPI: Deref<Target = RegisterBlock>, PADS, const I: usize> Spi<SPI, PADS, I>
             embedded_hal::spi::Polarity::IdleLow => config.set_clock_polarity(Polarity::IdleLow),
         };
 
+        SpiClock::<I>::enable_clock(glb);
         unsafe {
             glb.param_config
                 .modify(|c| c.set_spi_mode::<I>(SpiMode::Master));
@@ -683,8 +697,183 @@ impl<SPI: Deref<Target = RegisterBlock>, PADS, const I: usize> Spi<SPI, PADS, I>
     }
 
     /// Release the SPI instance and return the pads.
+    ///
+    /// Clock gate of the peripheral is disabled.
+    #[inline]
+    pub fn free<GLB>(self, glb: &GLB) -> (SPI, PADS)
+    where
+        GLB: Deref<Target = glb::v2::RegisterBlock>,
+    {
+        SpiClock::<I>::disable_clock(glb);
+        (self.spi, self.pads)
+    }
+}
+
+/// Serial Peripheral Interface peripheral in slave mode.
+///
+/// Transactions are framed by Chip Select (CS) signal from the external master. Data to
+/// be sent is queued before the master starts clocking; bytes received are read while
+/// CS stays asserted.
+pub struct SpiSlave<SPI, PADS, const I: usize> {
+    spi: SPI,
+    pads: PADS,
+}
+
+impl<SPI: Deref<Target = RegisterBlock>, PADS, const I: usize> SpiSlave<SPI, PADS, I> {
+    /// Create a new Serial Peripheral Interface slave instance.
+    #[inline]
+    pub fn new<GLB>(spi: SPI, pads: PADS, mode: Mode, glb: &GLB) -> Self
+    where
+        PADS: Pads<I>,
+        GLB: Deref<Target = glb::v2::RegisterBlock>,
+    {
+        let mut config = Config(0)
+            .disable_deglitch()
+            .disable_slave_three_pin()
+            .disable_byte_inverse()
+            .disable_bit_inverse()
+            .set_frame_size(FrameSize::Eight)
+            .disable_master();
+
+        config = match mode.phase {
+            embedded_hal::spi::Phase::CaptureOnFirstTransition => {
+                config.set_clock_phase(Phase::CaptureOnFirstTransition)
+            }
+            embedded_hal::spi::Phase::CaptureOnSecondTransition => {
+                config.set_clock_phase(Phase::CaptureOnSecondTransition)
+            }
+        };
+
+        config = match mode.polarity {
+            embedded_hal::spi::Polarity::IdleHigh => config.set_clock_polarity(Polarity::IdleHigh),
+            embedded_hal::spi::Polarity::IdleLow => config.set_clock_polarity(Polarity::IdleLow),
+        };
+
+        SpiClock::<I>::enable_clock(glb);
+        unsafe {
+            glb.param_config
+                .modify(|c| c.set_spi_mode::<I>(SpiMode::Slave));
+
+            spi.config.write(config);
+            spi.fifo_config_0.write(
+                FifoConfig0(0)
+                    .disable_dma_receive()
+                    .disable_dma_transmit()
+                    .clear_transmit_fifo()
+                    .clear_receive_fifo(),
+            );
+            spi.fifo_config_1.write(
+                FifoConfig1(0)
+                    .set_receive_threshold(0)
+                    .set_transmit_threshold(0),
+            );
+            spi.interrupt_config
+                .modify(|val| val.clear_interrupt(Interrupt::TransferEnd));
+            spi.config.modify(|val| val.enable_slave());
+        }
+        SpiSlave { spi, pads }
+    }
+
+    /// Enable transmit DMA.
+    ///
+    /// DMA channel should move data into `fifo_write` before the master asserts CS.
+    #[inline]
+    pub fn enable_tx_dma(self) -> Self {
+        unsafe {
+            self.spi
+                .fifo_config_0
+                .modify(|val| val.enable_dma_transmit());
+        }
+        self
+    }
+
+    /// Enable receive DMA.
+    #[inline]
+    pub fn enable_rx_dma(self) -> Self {
+        unsafe {
+            self.spi
+                .fifo_config_0
+                .modify(|val| val.enable_dma_receive());
+        }
+        self
+    }
+
+    /// Check if CS is asserted and a transaction is in progress.
+    #[inline]
+    pub fn is_selected(&self) -> bool {
+        self.spi.bus_busy.read().is_bus_busy()
+    }
+
+    /// Check if CS has been deasserted since last call, ending a transaction.
+    ///
+    /// Reports `true` once for every transaction.
+    #[inline]
+    pub fn poll_transaction_end(&mut self) -> bool {
+        if self
+            .spi
+            .interrupt_config
+            .read()
+            .has_interrupt(Interrupt::TransferEnd)
+        {
+            unsafe {
+                self.spi
+                    .interrupt_config
+                    .modify(|val| val.clear_interrupt(Interrupt::TransferEnd))
+            };
+            return true;
+        }
+        false
+    }
+
+    /// Serve one transaction, blocking until CS is deasserted.
+    ///
+    /// Bytes from `tx` are sent in order, followed by zeros if the master clocks more.
+    /// Received bytes are stored into `rx`, extra bytes are discarded. Returns number of
+    /// bytes clocked by the master during this transaction.
+    #[inline]
+    pub fn transaction(&mut self, rx: &mut [u8], tx: &[u8]) -> usize {
+        let mut tx = tx.iter().copied();
+        let mut received = 0;
+        let mut receive = |byte: u8| {
+            if let Some(slot) = rx.get_mut(received) {
+                *slot = byte;
+            }
+            received += 1;
+        };
+        loop {
+            let fifo_config = self.spi.fifo_config_1.read();
+            for _ in 0..fifo_config.transmit_available_bytes() {
+                unsafe { self.spi.fifo_write.write(tx.next().unwrap_or(0)) };
+            }
+            for _ in 0..fifo_config.receive_available_bytes() {
+                receive(self.spi.fifo_read.read());
+            }
+            if self.poll_transaction_end() {
+                break;
+            }
+        }
+        // Bytes received after the last poll but before CS deasserted.
+        for _ in 0..self.spi.fifo_config_1.read().receive_available_bytes() {
+            receive(self.spi.fifo_read.read());
+        }
+        // Queued bytes were not clocked out; do not send them in the next transaction.
+        unsafe {
+            self.spi
+                .fifo_config_0
+                .modify(|val| val.clear_transmit_fifo())
+        };
+        received
+    }
+
+    /// Release the SPI instance and return the pads.
+    ///
+    /// Clock gate of the peripheral is disabled.
     #[inline]
-    pub fn free(self) -> (SPI, PADS) {
+    pub fn free<GLB>(self, glb: &GLB) -> (SPI, PADS)
+    where
+        GLB: Deref<Target = glb::v2::RegisterBlock>,
+    {
+        SpiClock::<I>::disable_clock(glb);
         (self.spi, self.pads)
     }
 }
@@ -693,6 +882,8 @@ impl<SPI: Deref<Target = RegisterBlock>, PADS, const I: usize> Spi<SPI, PADS, I>
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum Error {
+    /// Operation needs a data signal whose pad is not part of this SPI instance.
+    MissingPad,
     Other,
 }
 
@@ -701,7 +892,7 @@ impl embedded_hal::spi::Error for Error {
     fn kind(&self) -> embedded_hal::spi::ErrorKind {
         use embedded_hal::spi::ErrorKind;
         match self {
-            Error::Other => ErrorKind::Other,
+            Error::MissingPad | Error::Other => ErrorKind::Other,
         }
     }
 }
@@ -712,101 +903,39 @@ impl<SPI: Deref<Target = RegisterBlock>, PADS, const I: usize> embedded_hal::spi
     type Error = Error;
 }
 
-impl<SPI: Deref<Target = RegisterBlock>, PADS, const I: usize> embedded_hal::spi::SpiBus
+impl<SPI: Deref<Target = RegisterBlock>, PADS: Pads<I>, const I: usize> embedded_hal::spi::SpiBus
     for Spi<SPI, PADS, I>
 {
     #[inline]
     fn read(&mut self, buf: &mut [u8]) -> Result<(), Self::Error> {
-        unsafe { self.spi.config.modify(|config| config.enable_master()) };
-
-        buf.iter_mut().for_each(|slot| {
-            while self.spi.fifo_config_1.read().receive_available_bytes() == 0 {
-                core::hint::spin_loop();
-            }
-            *slot = self.spi.fifo_read.read()
-        });
-
-        unsafe { self.spi.config.modify(|config| config.disable_master()) };
-        Ok(())
+        const { assert!(PADS::MISO, "reading from SPI requires a MISO pad") };
+        spi_read(&self.spi, buf)
     }
     #[inline]
     fn write(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
-        unsafe { self.spi.config.modify(|config| config.enable_master()) };
-
-        buf.iter().for_each(|&word| {
-            while self.spi.fifo_config_1.read().transmit_available_bytes() == 0 {
-                core::hint::spin_loop();
-            }
-            unsafe { self.spi.fifo_write.write(word) }
-            _ = self.spi.fifo_read.read();
-        });
-
-        unsafe { self.spi.config.modify(|config| config.disable_master()) };
-        Ok(())
+        const { assert!(PADS::MOSI, "writing to SPI requires a MOSI pad") };
+        spi_write(&self.spi, buf)
     }
     #[inline]
     fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), Self::Error> {
-        const MAX_RETRY: usize = 1000;
-        unsafe { self.spi.config.modify(|config| config.enable_master()) };
-
-        let (mut tx, mut rx) = (0, 0);
-        let mut fifo_config = self.spi.fifo_config_1.read();
-        let mut retry = 0;
-        while tx < write.len() || rx < read.len() {
-            while fifo_config.receive_available_bytes() == 0
-                && fifo_config.transmit_available_bytes() == 0
-            {
-                fifo_config = self.spi.fifo_config_1.read();
-            }
-            if fifo_config.transmit_available_bytes() != 0 && tx < write.len() {
-                unsafe { self.spi.fifo_write.write(write[tx]) }
-                tx += 1;
-            }
-            if fifo_config.receive_available_bytes() != 0 && rx < read.len() {
-                read[rx] = self.spi.fifo_read.read();
-                rx += 1;
-            }
-            retry += 1;
-            if retry > MAX_RETRY * max(write.len(), read.len()) {
-                return Err(Error::Other);
-            }
-        }
-
-        unsafe { self.spi.config.modify(|config| config.disable_master()) };
-        Ok(())
+        const {
+            assert!(
+                PADS::MOSI && PADS::MISO,
+                "full-duplex SPI transfer requires both MOSI and MISO pads"
+            )
+        };
+        spi_transfer(&self.spi, read, write)
     }
     #[inline]
     fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
-        const MAX_RETRY: usize = 1000;
-        unsafe { self.spi.config.modify(|config| config.enable_master()) };
-
-        let (mut tx, mut rx) = (0, 0);
-        let mut fifo_config = self.spi.fifo_config_1.read();
-        let mut retry = 0;
-        while tx < words.len() || rx < words.len() {
-            while fifo_config.receive_available_bytes() == 0
-                && fifo_config.transmit_available_bytes() == 0
-            {
-                fifo_config = self.spi.fifo_config_1.read();
-            }
-            if fifo_config.transmit_available_bytes() != 0 && tx < words.len() {
-                unsafe { self.spi.fifo_write.write(words[tx]) }
-                tx += 1;
-            }
-            if fifo_config.receive_available_bytes() != 0 && rx < tx {
-                words[rx] = self.spi.fifo_read.read();
-                rx += 1;
-            }
-            retry += 1;
-            if retry > MAX_RETRY * words.len() {
-                return Err(Error::Other);
-            }
-        }
-
-        unsafe { self.spi.config.modify(|config| config.disable_master()) };
-        Ok(())
+        const {
+            assert!(
+                PADS::MOSI && PADS::MISO,
+                "full-duplex SPI transfer requires both MOSI and MISO pads"
+            )
+        };
+        spi_transfer_in_place(&self.spi, words)
     }
-    #[inline]
     fn flush(&mut self) -> Result<(), Self::Error> {
         while self.spi.fifo_config_1.read().transmit_available_bytes() != 32 {
             core::hint::spin_loop();
@@ -818,26 +947,40 @@ impl<SPI: Deref<Target = RegisterBlock>, PADS, const I: usize> embedded_hal::spi
     }
 }
 
-impl<SPI: Deref<Target = RegisterBlock>, PADS, const I: usize> embedded_hal::spi::SpiDevice
+impl<SPI: Deref<Target = RegisterBlock>, PADS: Pads<I>, const I: usize> embedded_hal::spi::SpiDevice
     for Spi<SPI, PADS, I>
 {
     fn transaction(
         &mut self,
         operations: &mut [embedded_hal::spi::Operation<'_, u8>],
     ) -> Result<(), Self::Error> {
+        // Operations are only known at runtime here, so missing pads are reported as errors
+        // rather than rejected at compile time like `SpiBus` does.
         for op in operations {
             match op {
                 embedded_hal::spi::Operation::Read(buf) => {
-                    embedded_hal::spi::SpiBus::read(self, buf)?
+                    if !PADS::MISO {
+                        return Err(Error::MissingPad);
+                    }
+                    spi_read(&self.spi, buf)?
                 }
                 embedded_hal::spi::Operation::Write(buf) => {
-                    embedded_hal::spi::SpiBus::write(self, buf)?
+                    if !PADS::MOSI {
+                        return Err(Error::MissingPad);
+                    }
+                    spi_write(&self.spi, buf)?
                 }
                 embedded_hal::spi::Operation::Transfer(read, write) => {
-                    embedded_hal::spi::SpiBus::transfer(self, read, write)?
+                    if !(PADS::MOSI && PADS::MISO) {
+                        return Err(Error::MissingPad);
+                    }
+                    spi_transfer(&self.spi, read, write)?
                 }
                 embedded_hal::spi::Operation::TransferInPlace(buf) => {
-                    embedded_hal::spi::SpiBus::transfer_in_place(self, buf)?
+                    if !(PADS::MOSI && PADS::MISO) {
+                        return Err(Error::MissingPad);
+                    }
+                    spi_transfer_in_place(&self.spi, buf)?
                 }
                 embedded_hal::spi::Operation::DelayNs(_delay) => {
                     for _ in 0..*_delay {
@@ -855,7 +998,7 @@ impl<SPI: Deref<Target = RegisterBlock>, PADS, const I: usize> embedded_hal::spi
 // ecosystem crates, as some of them depends on embedded-hal v0.2.7 traits.
 // We encourage ecosystem developers to use embedded-hal v1.0.0 traits; after that, this part of code
 // would be removed in the future.
-impl<SPI: Deref<Target = RegisterBlock>, PADS, const I: usize>
+impl<SPI: Deref<Target = RegisterBlock>, PADS: Pads<I>, const I: usize>
     embedded_hal_027::blocking::spi::Write<u8> for Spi<SPI, PADS, I>
 {
     type Error = Error;
@@ -866,8 +1009,8 @@ impl<SPI: Deref<Target = RegisterBlock>, PADS, const I: usize>
     }
 }
 
-impl<SPI: Deref<Target = RegisterBlock>, PINS, const I: usize>
-    embedded_hal_027::blocking::spi::Transfer<u8> for Spi<SPI, PINS, I>
+impl<SPI: Deref<Target = RegisterBlock>, PADS: Pads<I>, const I: usize>
+    embedded_hal_027::blocking::spi::Transfer<u8> for Spi<SPI, PADS, I>
 {
     type Error = Error;
     #[inline]
@@ -877,120 +1020,351 @@ impl<SPI: Deref<Target = RegisterBlock>, PINS, const I: usize>
     }
 }
 
+#[inline]
+fn spi_read(spi: &RegisterBlock, buf: &mut [u8]) -> Result<(), Error> {
+    unsafe { spi.config.modify(|config| config.enable_master()) };
+
+    buf.iter_mut().for_each(|slot| {
+        while spi.fifo_config_1.read().receive_available_bytes() == 0 {
+            core::hint::spin_loop();
+        }
+        *slot = spi.fifo_read.read()
+    });
+
+    unsafe { spi.config.modify(|config| config.disable_master()) };
+    Ok(())
+}
+
+#[inline]
+fn spi_write(spi: &RegisterBlock, buf: &[u8]) -> Result<(), Error> {
+    unsafe { spi.config.modify(|config| config.enable_master()) };
+
+    buf.iter().for_each(|&word| {
+        while spi.fifo_config_1.read().transmit_available_bytes() == 0 {
+            core::hint::spin_loop();
+        }
+        unsafe { spi.fifo_write.write(word) }
+        _ = spi.fifo_read.read();
+    });
+
+    unsafe { spi.config.modify(|config| config.disable_master()) };
+    Ok(())
+}
+
+#[inline]
+fn spi_transfer(spi: &RegisterBlock, read: &mut [u8], write: &[u8]) -> Result<(), Error> {
+    const MAX_RETRY: usize = 1000;
+    unsafe { spi.config.modify(|config| config.enable_master()) };
+
+    let (mut tx, mut rx) = (0, 0);
+    let mut fifo_config = spi.fifo_config_1.read();
+    let mut retry = 0;
+    while tx < write.len() || rx < read.len() {
+        while fifo_config.receive_available_bytes() == 0
+            && fifo_config.transmit_available_bytes() == 0
+        {
+            fifo_config = spi.fifo_config_1.read();
+        }
+        if fifo_config.transmit_available_bytes() != 0 && tx < write.len() {
+            unsafe { spi.fifo_write.write(write[tx]) }
+            tx += 1;
+        }
+        if fifo_config.receive_available_bytes() != 0 && rx < read.len() {
+            read[rx] = spi.fifo_read.read();
+            rx += 1;
+        }
+        retry += 1;
+        if retry > MAX_RETRY * max(write.len(), read.len()) {
+            return Err(Error::Other);
+        }
+    }
+
+    unsafe { spi.config.modify(|config| config.disable_master()) };
+    Ok(())
+}
+
+#[inline]
+fn spi_transfer_in_place(spi: &RegisterBlock, words: &mut [u8]) -> Result<(), Error> {
+    const MAX_RETRY: usize = 1000;
+    unsafe { spi.config.modify(|config| config.enable_master()) };
+
+    let (mut tx, mut rx) = (0, 0);
+    let mut fifo_config = spi.fifo_config_1.read();
+    let mut retry = 0;
+    while tx < words.len() || rx < words.len() {
+        while fifo_config.receive_available_bytes() == 0
+            && fifo_config.transmit_available_bytes() == 0
+        {
+            fifo_config = spi.fifo_config_1.read();
+        }
+        if fifo_config.transmit_available_bytes() != 0 && tx < words.len() {
+            unsafe { spi.fifo_write.write(words[tx]) }
+            tx += 1;
+        }
+        if fifo_config.receive_available_bytes() != 0 && rx < tx {
+            words[rx] = spi.fifo_read.read();
+            rx += 1;
+        }
+        retry += 1;
+        if retry > MAX_RETRY * words.len() {
+            return Err(Error::Other);
+        }
+    }
+
+    unsafe { spi.config.modify(|config| config.disable_master()) };
+    Ok(())
+}
+
 /// Valid SPI pads.
-pub trait Pads<const I: usize> {}
+///
+/// Both SPI peripherals share the same pad mapping, with signal decided by pad number modulo 4.
+/// Pads are given in order of clock, MOSI, MISO and CS, with MOSI or MISO omitted for
+/// transmit-only or receive-only buses.
+#[diagnostic::on_unimplemented(
+    message = "the I/O pad group {Self} is not connected to SPI peripheral {I} on hardware"
+)]
+pub trait Pads<const I: usize> {
+    /// Checks if this pad configuration includes Master-Out Slave-In signal.
+    const MOSI: bool;
+    /// Checks if this pad configuration includes Master-In Slave-Out signal.
+    const MISO: bool;
+}
 
-impl<'a, 'b, 'c, const N1: usize, const N2: usize, const N3: usize> Pads<1>
+impl<'a, 'b, 'c, const N1: usize, const N2: usize, const N3: usize, const I: usize> Pads<I>
     for (
-        Alternate<'a, N1, gpio::Spi<1>>,
-        Alternate<'b, N2, gpio::Spi<1>>,
-        Alternate<'c, N3, gpio::Spi<1>>,
+        Alternate<'a, N1, gpio::Spi<I>>,
+        Alternate<'b, N2, gpio::Spi<I>>,
+        Alternate<'c, N3, gpio::Spi<I>>,
     )
 where
-    Alternate<'a, N1, gpio::Spi<1>>: HasClkSignal,
-    Alternate<'b, N2, gpio::Spi<1>>: HasMosiSignal,
-    Alternate<'c, N3, gpio::Spi<1>>: HasCsSignal,
+    Alternate<'a, N1, gpio::Spi<I>>: HasClkSignal,
+    Alternate<'b, N2, gpio::Spi<I>>: HasDataSignal,
+    Alternate<'c, N3, gpio::Spi<I>>: HasCsSignal,
 {
+    const MOSI: bool = <Alternate<'b, N2, gpio::Spi<I>> as HasDataSignal>::IS_MOSI;
+    const MISO: bool = !Self::MOSI;
 }
 
-impl<'a, 'b, 'c, 'd, const N1: usize, const N2: usize, const N3: usize, const N4: usize> Pads<1>
+impl<
+    'a,
+    'b,
+    'c,
+    'd,
+    const N1: usize,
+    const N2: usize,
+    const N3: usize,
+    const N4: usize,
+    const I: usize,
+> Pads<I>
     for (
-        Alternate<'a, N1, gpio::Spi<1>>,
-        Alternate<'b, N2, gpio::Spi<1>>,
-        Alternate<'c, N3, gpio::Spi<1>>,
-        Alternate<'d, N4, gpio::Spi<1>>,
+        Alternate<'a, N1, gpio::Spi<I>>,
+        Alternate<'b, N2, gpio::Spi<I>>,
+        Alternate<'c, N3, gpio::Spi<I>>,
+        Alternate<'d, N4, gpio::Spi<I>>,
     )
 where
-    Alternate<'a, N1, gpio::Spi<1>>: HasClkSignal,
-    Alternate<'b, N2, gpio::Spi<1>>: HasMosiSignal,
-    Alternate<'c, N3, gpio::Spi<1>>: HasMisoSignal,
-    Alternate<'d, N4, gpio::Spi<1>>: HasCsSignal,
+    Alternate<'a, N1, gpio::Spi<I>>: HasClkSignal,
+    Alternate<'b, N2, gpio::Spi<I>>: HasMosiSignal,
+    Alternate<'c, N3, gpio::Spi<I>>: HasMisoSignal,
+    Alternate<'d, N4, gpio::Spi<I>>: HasCsSignal,
 {
+    const MOSI: bool = true;
+    const MISO: bool = true;
 }
 
 /// Check if target gpio `Pin` is internally connected to SPI clock signal.
 pub trait HasClkSignal {}
 
-impl<'a> HasClkSignal for Alternate<'a, 3, gpio::Spi<1>> {}
-impl<'a> HasClkSignal for Alternate<'a, 7, gpio::Spi<1>> {}
-impl<'a> HasClkSignal for Alternate<'a, 11, gpio::Spi<1>> {}
-impl<'a> HasClkSignal for Alternate<'a, 15, gpio::Spi<1>> {}
-impl<'a> HasClkSignal for Alternate<'a, 19, gpio::Spi<1>> {}
-impl<'a> HasClkSignal for Alternate<'a, 23, gpio::Spi<1>> {}
-impl<'a> HasClkSignal for Alternate<'a, 27, gpio::Spi<1>> {}
-impl<'a> HasClkSignal for Alternate<'a, 31, gpio::Spi<1>> {}
-impl<'a> HasClkSignal for Alternate<'a, 35, gpio::Spi<1>> {}
-impl<'a> HasClkSignal for Alternate<'a, 39, gpio::Spi<1>> {}
-impl<'a> HasClkSignal for Alternate<'a, 43, gpio::Spi<1>> {}
+impl<'a, const I: usize> HasClkSignal for Alternate<'a, 3, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasClkSignal for Alternate<'a, 7, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasClkSignal for Alternate<'a, 11, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasClkSignal for Alternate<'a, 15, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasClkSignal for Alternate<'a, 19, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasClkSignal for Alternate<'a, 23, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasClkSignal for Alternate<'a, 27, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasClkSignal for Alternate<'a, 31, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasClkSignal for Alternate<'a, 35, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasClkSignal for Alternate<'a, 39, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasClkSignal for Alternate<'a, 43, gpio::Spi<I>> {}
 
 /// Check if target gpio `Pin` is internally connected to SPI MISO signal.
 pub trait HasMisoSignal {}
 
-impl<'a> HasMisoSignal for Alternate<'a, 2, gpio::Spi<1>> {}
-impl<'a> HasMisoSignal for Alternate<'a, 6, gpio::Spi<1>> {}
-impl<'a> HasMisoSignal for Alternate<'a, 10, gpio::Spi<1>> {}
-impl<'a> HasMisoSignal for Alternate<'a, 14, gpio::Spi<1>> {}
-impl<'a> HasMisoSignal for Alternate<'a, 18, gpio::Spi<1>> {}
-impl<'a> HasMisoSignal for Alternate<'a, 22, gpio::Spi<1>> {}
-impl<'a> HasMisoSignal for Alternate<'a, 26, gpio::Spi<1>> {}
-impl<'a> HasMisoSignal for Alternate<'a, 30, gpio::Spi<1>> {}
-impl<'a> HasMisoSignal for Alternate<'a, 34, gpio::Spi<1>> {}
-impl<'a> HasMisoSignal for Alternate<'a, 38, gpio::Spi<1>> {}
-impl<'a> HasMisoSignal for Alternate<'a, 42, gpio::Spi<1>> {}
+impl<'a, const I: usize> HasMisoSignal for Alternate<'a, 2, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasMisoSignal for Alternate<'a, 6, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasMisoSignal for Alternate<'a, 10, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasMisoSignal for Alternate<'a, 14, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasMisoSignal for Alternate<'a, 18, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasMisoSignal for Alternate<'a, 22, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasMisoSignal for Alternate<'a, 26, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasMisoSignal for Alternate<'a, 30, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasMisoSignal for Alternate<'a, 34, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasMisoSignal for Alternate<'a, 38, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasMisoSignal for Alternate<'a, 42, gpio::Spi<I>> {}
 
 /// Check if target gpio `Pin` is internally connected to SPI MOSI signal.
 pub trait HasMosiSignal {}
 
-impl<'a> HasMosiSignal for Alternate<'a, 1, gpio::Spi<1>> {}
-impl<'a> HasMosiSignal for Alternate<'a, 5, gpio::Spi<1>> {}
-impl<'a> HasMosiSignal for Alternate<'a, 9, gpio::Spi<1>> {}
-impl<'a> HasMosiSignal for Alternate<'a, 13, gpio::Spi<1>> {}
-impl<'a> HasMosiSignal for Alternate<'a, 17, gpio::Spi<1>> {}
-impl<'a> HasMosiSignal for Alternate<'a, 21, gpio::Spi<1>> {}
-impl<'a> HasMosiSignal for Alternate<'a, 25, gpio::Spi<1>> {}
-impl<'a> HasMosiSignal for Alternate<'a, 29, gpio::Spi<1>> {}
-impl<'a> HasMosiSignal for Alternate<'a, 33, gpio::Spi<1>> {}
-impl<'a> HasMosiSignal for Alternate<'a, 37, gpio::Spi<1>> {}
-impl<'a> HasMosiSignal for Alternate<'a, 41, gpio::Spi<1>> {}
-impl<'a> HasMosiSignal for Alternate<'a, 45, gpio::Spi<1>> {}
+impl<'a, const I: usize> HasMosiSignal for Alternate<'a, 1, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasMosiSignal for Alternate<'a, 5, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasMosiSignal for Alternate<'a, 9, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasMosiSignal for Alternate<'a, 13, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasMosiSignal for Alternate<'a, 17, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasMosiSignal for Alternate<'a, 21, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasMosiSignal for Alternate<'a, 25, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasMosiSignal for Alternate<'a, 29, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasMosiSignal for Alternate<'a, 33, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasMosiSignal for Alternate<'a, 37, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasMosiSignal for Alternate<'a, 41, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasMosiSignal for Alternate<'a, 45, gpio::Spi<I>> {}
 
 /// Check if target gpio `Pin` is internally connected to SPI CS signal.
 pub trait HasCsSignal {}
 
-impl<'a> HasCsSignal for Alternate<'a, 0, gpio::Spi<1>> {}
-impl<'a> HasCsSignal for Alternate<'a, 4, gpio::Spi<1>> {}
-impl<'a> HasCsSignal for Alternate<'a, 8, gpio::Spi<1>> {}
-impl<'a> HasCsSignal for Alternate<'a, 12, gpio::Spi<1>> {}
-impl<'a> HasCsSignal for Alternate<'a, 16, gpio::Spi<1>> {}
-impl<'a> HasCsSignal for Alternate<'a, 20, gpio::Spi<1>> {}
-impl<'a> HasCsSignal for Alternate<'a, 24, gpio::Spi<1>> {}
-impl<'a> HasCsSignal for Alternate<'a, 28, gpio::Spi<1>> {}
-impl<'a> HasCsSignal for Alternate<'a, 32, gpio::Spi<1>> {}
-impl<'a> HasCsSignal for Alternate<'a, 36, gpio::Spi<1>> {}
-impl<'a> HasCsSignal for Alternate<'a, 40, gpio::Spi<1>> {}
-impl<'a> HasCsSignal for Alternate<'a, 44, gpio::Spi<1>> {}
+impl<'a, const I: usize> HasCsSignal for Alternate<'a, 0, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasCsSignal for Alternate<'a, 4, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasCsSignal for Alternate<'a, 8, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasCsSignal for Alternate<'a, 12, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasCsSignal for Alternate<'a, 16, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasCsSignal for Alternate<'a, 20, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasCsSignal for Alternate<'a, 24, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasCsSignal for Alternate<'a, 28, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasCsSignal for Alternate<'a, 32, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasCsSignal for Alternate<'a, 36, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasCsSignal for Alternate<'a, 40, gpio::Spi<I>> {}
+impl<'a, const I: usize> HasCsSignal for Alternate<'a, 44, gpio::Spi<I>> {}
+
+/// Check if target gpio `Pin` is internally connected to either SPI data signal.
+///
+/// Used by half-duplex pad configurations, where a single data pad decides bus direction.
+pub trait HasDataSignal {
+    /// The pad carries MOSI signal if `true`, or MISO signal if `false`.
+    const IS_MOSI: bool;
+}
+
+impl<'a, const I: usize> HasDataSignal for Alternate<'a, 1, gpio::Spi<I>> {
+    const IS_MOSI: bool = true;
+}
+impl<'a, const I: usize> HasDataSignal for Alternate<'a, 2, gpio::Spi<I>> {
+    const IS_MOSI: bool = false;
+}
+impl<'a, const I: usize> HasDataSignal for Alternate<'a, 5, gpio::Spi<I>> {
+    const IS_MOSI: bool = true;
+}
+impl<'a, const I: usize> HasDataSignal for Alternate<'a, 6, gpio::Spi<I>> {
+    const IS_MOSI: bool = false;
+}
+impl<'a, const I: usize> HasDataSignal for Alternate<'a, 9, gpio::Spi<I>> {
+    const IS_MOSI: bool = true;
+}
+impl<'a, const I: usize> HasDataSignal for Alternate<'a, 10, gpio::Spi<I>> {
+    const IS_MOSI: bool = false;
+}
+impl<'a, const I: usize> HasDataSignal for Alternate<'a, 13, gpio::Spi<I>> {
+    const IS_MOSI: bool = true;
+}
+impl<'a, const I: usize> HasDataSignal for Alternate<'a, 14, gpio::Spi<I>> {
+    const IS_MOSI: bool = false;
+}
+impl<'a, const I: usize> HasDataSignal for Alternate<'a, 17, gpio::Spi<I>> {
+    const IS_MOSI: bool = true;
+}
+impl<'a, const I: usize> HasDataSignal for Alternate<'a, 18, gpio::Spi<I>> {
+    const IS_MOSI: bool = false;
+}
+impl<'a, const I: usize> HasDataSignal for Alternate<'a, 21, gpio::Spi<I>> {
+    const IS_MOSI: bool = true;
+}
+impl<'a, const I: usize> HasDataSignal for Alternate<'a, 22, gpio::Spi<I>> {
+    const IS_MOSI: bool = false;
+}
+impl<'a, const I: usize> HasDataSignal for Alternate<'a, 25, gpio::Spi<I>> {
+    const IS_MOSI: bool = true;
+}
+impl<'a, const I: usize> HasDataSignal for Alternate<'a, 26, gpio::Spi<I>> {
+    const IS_MOSI: bool = false;
+}
+impl<'a, const I: usize> HasDataSignal for Alternate<'a, 29, gpio::Spi<I>> {
+    const IS_MOSI: bool = true;
+}
+impl<'a, const I: usize> HasDataSignal for Alternate<'a, 30, gpio::Spi<I>> {
+    const IS_MOSI: bool = false;
+}
+impl<'a, const I: usize> HasDataSignal for Alternate<'a, 33, gpio::Spi<I>> {
+    const IS_MOSI: bool = true;
+}
+impl<'a, const I: usize> HasDataSignal for Alternate<'a, 34, gpio::Spi<I>> {
+    const IS_MOSI: bool = false;
+}
+impl<'a, const I: usize> HasDataSignal for Alternate<'a, 37, gpio::Spi<I>> {
+    const IS_MOSI: bool = true;
+}
+impl<'a, const I: usize> HasDataSignal for Alternate<'a, 38, gpio::Spi<I>> {
+    const IS_MOSI: bool = false;
+}
+impl<'a, const I: usize> HasDataSignal for Alternate<'a, 41, gpio::Spi<I>> {
+    const IS_MOSI: bool = true;
+}
+impl<'a, const I: usize> HasDataSignal for Alternate<'a, 42, gpio::Spi<I>> {
+    const IS_MOSI: bool = false;
+}
+impl<'a, const I: usize> HasDataSignal for Alternate<'a, 45, gpio::Spi<I>> {
+    const IS_MOSI: bool = true;
+}
 
 #[cfg(test)]
 mod tests {
     use super::{
-        BusBusy, Config, FifoConfig0, FifoConfig1, FrameSize, Interrupt, InterruptConfig,
+        BusBusy, Config, FifoConfig0, FifoConfig1, FrameSize, Interrupt, InterruptConfig, Pads,
         PeriodInterval, PeriodSignal, Phase, Polarity, ReceiveIgnore, RegisterBlock, SlaveTimeout,
+        SpiSlave,
     };
-    use core::mem::offset_of;
+    use crate::gpio::{self, Alternate};
 
     #[test]
     fn struct_register_block_offset() {
-        assert_eq!(offset_of!(RegisterBlock, config), 0x0);
-        assert_eq!(offset_of!(RegisterBlock, interrupt_config), 0x4);
-        assert_eq!(offset_of!(RegisterBlock, bus_busy), 0x08);
-        assert_eq!(offset_of!(RegisterBlock, period_signal), 0x10);
-        assert_eq!(offset_of!(RegisterBlock, period_interval), 0x14);
-        assert_eq!(offset_of!(RegisterBlock, receive_ignore), 0x18);
-        assert_eq!(offset_of!(RegisterBlock, slave_timeout), 0x1c);
-        assert_eq!(offset_of!(RegisterBlock, fifo_config_0), 0x80);
-        assert_eq!(offset_of!(RegisterBlock, fifo_config_1), 0x84);
-        assert_eq!(offset_of!(RegisterBlock, fifo_write), 0x88);
-        assert_eq!(offset_of!(RegisterBlock, fifo_read), 0x8c);
+        assert_offsets!(RegisterBlock {
+            config: 0x0,
+            interrupt_config: 0x4,
+            bus_busy: 0x08,
+            period_signal: 0x10,
+            period_interval: 0x14,
+            receive_ignore: 0x18,
+            slave_timeout: 0x1c,
+            fifo_config_0: 0x80,
+            fifo_config_1: 0x84,
+            fifo_write: 0x88,
+            fifo_read: 0x8c,
+        });
+    }
+
+    #[test]
+    fn spi_slave_transaction() {
+        let mut memory = [0u32; 0x90 / 4];
+        // Transfer ended, two free transmit slots and three received bytes.
+        memory[0x04 / 4] = 0x1;
+        memory[0x84 / 4] = 0x0302;
+        memory[0x8c / 4] = 0xab;
+        let spi = unsafe { &*(memory.as_mut_ptr() as *const RegisterBlock) };
+        let mut slave = SpiSlave::<_, (), 1> { spi, pads: () };
+
+        let mut rx = [0u8; 4];
+        assert_eq!(slave.transaction(&mut rx, &[0x12, 0x34]), 6);
+        assert_eq!(rx, [0xab; 4]);
+        // Leftover transmit bytes and transfer end flag are cleared.
+        assert_eq!(spi.fifo_config_0.read().0 & (1 << 2), 1 << 2);
+        assert_eq!(spi.interrupt_config.read().0 & (1 << 16), 1 << 16);
+    }
+
+    #[test]
+    fn pads_data_signals() {
+        type Pad<const N: usize> = Alternate<'static, N, gpio::Spi<1>>;
+        fn signals<P: Pads<1>>() -> (bool, bool) {
+            (P::MOSI, P::MISO)
+        }
+        assert_eq!(signals::<(Pad<3>, Pad<1>, Pad<0>)>(), (true, false));
+        assert_eq!(signals::<(Pad<7>, Pad<2>, Pad<4>)>(), (false, true));
+        assert_eq!(signals::<(Pad<11>, Pad<5>, Pad<6>, Pad<8>)>(), (true, true));
     }
 
     #[test]
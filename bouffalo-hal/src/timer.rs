@@ -1,7 +1,730 @@
 //! Timer and watchdog peripheral.
+//!
+//! Timer comparator matches raise interrupts only and cannot drive a pad; see
+//! [`pwm`](crate::pwm) module for hardware-timed pad output.
+
+use crate::clocks::Clocks;
+use core::ops::Deref;
+#[cfg(feature = "async")]
+use core::{
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicUsize, Ordering},
+    task::{Context, Poll},
+};
+use volatile_register::{RO, RW, WO};
 
 /// Timer and watchdog peripheral registers.
 #[repr(C)]
 pub struct RegisterBlock {
-    // todo fields
+    /// Clock source of timers and watchdog.
+    pub clock_config: RW<ClockConfig>,
+    _reserved0: [u8; 0xc],
+    /// Comparator values of each timer, three comparators per timer.
+    pub match_value: [[RW<u32>; 3]; 2],
+    _reserved1: [u8; 0x4],
+    /// Current counter value of each timer.
+    pub counter_value: [RO<u32>; 2],
+    _reserved2: [u8; 0x4],
+    /// Comparator match state of each timer.
+    pub match_state: [RO<MatchState>; 2],
+    _reserved3: [u8; 0x4],
+    /// Comparator match interrupt enable of each timer.
+    pub match_enable: [RW<MatchEnable>; 2],
+    _reserved4: [u8; 0x4],
+    /// Value loaded into counter of each timer on preload event.
+    pub preload_value: [RW<u32>; 2],
+    _reserved5: [u8; 0x4],
+    /// Preload event source of each timer.
+    pub preload_control: [RW<PreloadControl>; 2],
+    // todo: watchdog registers
+    _reserved6: [u8; 0x14],
+    /// Clear comparator match state of each timer.
+    pub match_clear: [WO<MatchClear>; 2],
+    _reserved7: [u8; 0x4],
+    /// Counter enable of each timer.
+    pub counter_enable: RW<CounterEnable>,
+    /// Counter mode of each timer.
+    pub counter_mode: RW<CounterMode>,
+    _reserved8: [u8; 0x30],
+    /// Clock divider of each timer.
+    pub clock_divide: RW<ClockDivide>,
+}
+
+/// Clock source configuration register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[repr(transparent)]
+pub struct ClockConfig(u32);
+
+impl ClockConfig {
+    const CLOCK_SOURCE: u32 = 0xf;
+
+    /// Set clock source of `timer`.
+    #[inline]
+    pub const fn set_clock_source(self, timer: usize, val: ClockSource) -> Self {
+        let shift = timer * 4;
+        Self((self.0 & !(Self::CLOCK_SOURCE << shift)) | ((val as u32) << shift))
+    }
+    /// Get clock source of `timer`.
+    #[inline]
+    pub const fn clock_source(self, timer: usize) -> ClockSource {
+        match (self.0 >> (timer * 4)) & Self::CLOCK_SOURCE {
+            0 => ClockSource::Bclk,
+            1 => ClockSource::F32kClk,
+            2 => ClockSource::F1kClk,
+            3 => ClockSource::Xclk,
+            4 => ClockSource::Gpio,
+            _ => ClockSource::None,
+        }
+    }
+}
+
+/// Timer clock source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ClockSource {
+    /// Bus clock.
+    Bclk = 0,
+    /// 32-kHz clock.
+    F32kClk = 1,
+    /// 1-kHz clock.
+    F1kClk = 2,
+    /// Crystal oscillator clock.
+    Xclk = 3,
+    /// Clock from GPIO pad.
+    Gpio = 4,
+    /// No clock, counter stops.
+    None = 5,
+}
+
+/// Comparator match state register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[repr(transparent)]
+pub struct MatchState(u32);
+
+impl MatchState {
+    /// Check if counter has matched `comparator`.
+    #[inline]
+    pub const fn has_match(self, comparator: usize) -> bool {
+        self.0 & (1 << comparator) != 0
+    }
+}
+
+/// Comparator match interrupt enable register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[repr(transparent)]
+pub struct MatchEnable(u32);
+
+impl MatchEnable {
+    /// Enable interrupt on match of `comparator`.
+    #[inline]
+    pub const fn enable_match(self, comparator: usize) -> Self {
+        Self(self.0 | (1 << comparator))
+    }
+    /// Disable interrupt on match of `comparator`.
+    #[inline]
+    pub const fn disable_match(self, comparator: usize) -> Self {
+        Self(self.0 & !(1 << comparator))
+    }
+    /// Check if interrupt on match of `comparator` is enabled.
+    #[inline]
+    pub const fn is_match_enabled(self, comparator: usize) -> bool {
+        self.0 & (1 << comparator) != 0
+    }
+}
+
+/// Clear comparator match state register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[repr(transparent)]
+pub struct MatchClear(u32);
+
+impl MatchClear {
+    /// Clear match state of `comparator`.
+    #[inline]
+    pub const fn clear_match(self, comparator: usize) -> Self {
+        Self(self.0 | (1 << comparator))
+    }
+}
+
+/// Preload control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[repr(transparent)]
+pub struct PreloadControl(u32);
+
+impl PreloadControl {
+    const SOURCE: u32 = 0x3;
+
+    /// Set preload event source.
+    #[inline]
+    pub const fn set_preload_source(self, val: PreloadSource) -> Self {
+        Self((self.0 & !Self::SOURCE) | val as u32)
+    }
+    /// Get preload event source.
+    #[inline]
+    pub const fn preload_source(self) -> PreloadSource {
+        match self.0 & Self::SOURCE {
+            0 => PreloadSource::None,
+            1 => PreloadSource::Match0,
+            2 => PreloadSource::Match1,
+            _ => PreloadSource::Match2,
+        }
+    }
+}
+
+/// Event on which counter is loaded with preload value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum PreloadSource {
+    /// Counter is never preloaded.
+    None = 0,
+    /// Preload on match of comparator 0.
+    Match0 = 1,
+    /// Preload on match of comparator 1.
+    Match1 = 2,
+    /// Preload on match of comparator 2.
+    Match2 = 3,
+}
+
+/// Counter enable register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[repr(transparent)]
+pub struct CounterEnable(u32);
+
+impl CounterEnable {
+    /// Enable counter of `timer`.
+    #[inline]
+    pub const fn enable_counter(self, timer: usize) -> Self {
+        Self(self.0 | (1 << (timer + 1)))
+    }
+    /// Disable counter of `timer`.
+    #[inline]
+    pub const fn disable_counter(self, timer: usize) -> Self {
+        Self(self.0 & !(1 << (timer + 1)))
+    }
+    /// Check if counter of `timer` is enabled.
+    #[inline]
+    pub const fn is_counter_enabled(self, timer: usize) -> bool {
+        self.0 & (1 << (timer + 1)) != 0
+    }
+}
+
+/// Counter mode register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[repr(transparent)]
+pub struct CounterMode(u32);
+
+impl CounterMode {
+    /// Let counter of `timer` run freely, wrapping around on overflow.
+    #[inline]
+    pub const fn enable_freerun(self, timer: usize) -> Self {
+        Self(self.0 | (1 << (timer + 1)))
+    }
+    /// Let counter of `timer` restart from preload value on preload event.
+    #[inline]
+    pub const fn disable_freerun(self, timer: usize) -> Self {
+        Self(self.0 & !(1 << (timer + 1)))
+    }
+    /// Check if counter of `timer` runs freely.
+    #[inline]
+    pub const fn is_freerun_enabled(self, timer: usize) -> bool {
+        self.0 & (1 << (timer + 1)) != 0
+    }
+}
+
+/// Clock divider register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[repr(transparent)]
+pub struct ClockDivide(u32);
+
+impl ClockDivide {
+    const DIVIDE: u32 = 0xff;
+
+    /// Set clock divider of `timer`, counting once every `val + 1` source clocks.
+    #[inline]
+    pub const fn set_clock_divide(self, timer: usize, val: u8) -> Self {
+        let shift = 8 * (timer + 1);
+        Self((self.0 & !(Self::DIVIDE << shift)) | ((val as u32) << shift))
+    }
+    /// Get clock divider of `timer`.
+    #[inline]
+    pub const fn clock_divide(self, timer: usize) -> u8 {
+        ((self.0 >> (8 * (timer + 1))) & Self::DIVIDE) as u8
+    }
+}
+
+/// Configure timer `I` as a free running counter at about 1 MHz from crystal oscillator.
+///
+/// Returns the counter frequency in Hertz.
+#[inline]
+fn configure_freerun<const I: usize>(timer: &RegisterBlock, clocks: &Clocks) -> u32 {
+    let xclk = clocks.xclk().0;
+    let divide = xclk.div_ceil(1_000_000).clamp(1, 256);
+    unsafe {
+        timer.counter_enable.modify(|val| val.disable_counter(I));
+        timer
+            .clock_config
+            .modify(|val| val.set_clock_source(I, ClockSource::Xclk));
+        timer
+            .clock_divide
+            .modify(|val| val.set_clock_divide(I, (divide - 1) as u8));
+        timer.preload_control[I].write(PreloadControl::default());
+        timer.counter_mode.modify(|val| val.enable_freerun(I));
+        timer.match_enable[I].modify(|val| val.disable_match(0));
+        timer.match_clear[I].write(MatchClear::default().clear_match(0));
+        timer.counter_enable.modify(|val| val.enable_counter(I));
+    }
+    xclk / divide
+}
+
+/// Fixed-rate period measured by the free running counter of timer `I`.
+///
+/// Polling [`elapsed`](Self::elapsed) tells whether the period has passed without blocking,
+/// so a main loop can blink a LED at a precise rate while doing other work. Further jobs
+/// at other rates are [`Task`]s created by [`task`](Self::task) and polled on the same
+/// counter; no interrupt or allocation is involved.
+///
+/// ```ignore
+/// let mut blink = Periodic::<_, 0>::new(p.timer0, &c, 500_000);
+/// let mut report = blink.task(1_000_000);
+/// loop {
+///     if blink.elapsed() {
+///         led.toggle().ok();
+///     }
+///     if blink.poll(&mut report) {
+///         writeln!(serial, "still alive").ok();
+///     }
+///     // other work
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Periodic<TIMER, const I: usize> {
+    timer: TIMER,
+    tick_hz: u32,
+    task: Task,
+}
+
+impl<TIMER: Deref<Target = RegisterBlock>, const I: usize> Periodic<TIMER, I> {
+    /// Configure timer `I` as a free running counter and start a period of `period_us`
+    /// microseconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the period is not shorter than half of counter range, about 35 minutes.
+    #[inline]
+    pub fn new(timer: TIMER, clocks: &Clocks, period_us: u32) -> Self {
+        let tick_hz = configure_freerun::<I>(&timer, clocks);
+        let now = timer.counter_value[I].read();
+        Self {
+            timer,
+            tick_hz,
+            task: Task::new(now, period_ticks(tick_hz, period_us)),
+        }
+    }
+    /// Check if the period has elapsed, starting the next period if so.
+    ///
+    /// Next period starts where the previous one ended, so polling late does not shift
+    /// later periods. Periods missed entirely are skipped instead of reported in a row.
+    #[inline]
+    pub fn elapsed(&mut self) -> bool {
+        let now = self.timer.counter_value[I].read();
+        self.task.poll(now)
+    }
+    /// Block until the period elapses, then start the next period.
+    #[inline]
+    pub fn wait(&mut self) {
+        while !self.elapsed() {
+            core::hint::spin_loop();
+        }
+    }
+    /// Create a task running every `period_us` microseconds from now on this counter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the period is not shorter than half of counter range, about 35 minutes.
+    #[inline]
+    pub fn task(&self, period_us: u32) -> Task {
+        let now = self.timer.counter_value[I].read();
+        Task::new(now, period_ticks(self.tick_hz, period_us))
+    }
+    /// Check if `task` is due, starting its next period if so.
+    #[inline]
+    pub fn poll(&self, task: &mut Task) -> bool {
+        let now = self.timer.counter_value[I].read();
+        task.poll(now)
+    }
+    /// Release timer peripheral.
+    #[inline]
+    pub fn free(self) -> TIMER {
+        self.timer
+    }
+}
+
+/// Periodic job polled through [`Periodic::poll`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Task {
+    start: u32,
+    period: u32,
+}
+
+impl Task {
+    #[inline]
+    const fn new(now: u32, period: u32) -> Self {
+        Self { start: now, period }
+    }
+    #[inline]
+    const fn poll(&mut self, now: u32) -> bool {
+        let passed = now.wrapping_sub(self.start);
+        if passed < self.period {
+            return false;
+        }
+        self.start = self.start.wrapping_add(passed - passed % self.period);
+        true
+    }
+}
+
+/// Number of counter ticks at `tick_hz` in a period of `period_us` microseconds.
+#[inline]
+const fn period_ticks(tick_hz: u32, period_us: u32) -> u32 {
+    let ticks = (period_us as u64 * tick_hz as u64).div_ceil(1_000_000);
+    // Keep periods within half of counter range, so wrapping differences stay valid.
+    assert!(ticks < (u32::MAX / 2) as u64, "timer period too long");
+    if ticks == 0 { 1 } else { ticks as u32 }
+}
+
+/// Async/await delay parking on comparator match interrupt of timer `I`.
+///
+/// Counter of timer `I` runs freely at about 1 MHz from crystal oscillator, and each delay
+/// arms comparator 0 and waits for its interrupt instead of spinning. Call
+/// [`TimerState::on_interrupt`] from the timer interrupt handler to wake the delay.
+///
+/// ```ignore
+/// static TIMER_STATE: TimerState = TimerState::new();
+///
+/// let mut delay = AsyncDelay::<_, 0>::new(p.timer0, &c, &TIMER_STATE);
+/// loop {
+///     led.toggle().ok();
+///     delay.delay_ms(500).await;
+/// }
+/// ```
+#[cfg(feature = "async")]
+#[derive(Debug)]
+pub struct AsyncDelay<TIMER, const I: usize> {
+    timer: TIMER,
+    tick_hz: u32,
+    state: &'static TimerState,
+}
+
+#[cfg(feature = "async")]
+impl<TIMER: Deref<Target = RegisterBlock>, const I: usize> AsyncDelay<TIMER, I> {
+    /// Configure timer `I` as a free running counter and create the delay.
+    #[inline]
+    pub fn new(timer: TIMER, clocks: &Clocks, state: &'static TimerState) -> Self {
+        let tick_hz = configure_freerun::<I>(&timer, clocks);
+        state
+            .ref_to_timer
+            .store(&*timer as *const _ as usize, Ordering::Release);
+        state.index.store(I, Ordering::Release);
+        Self {
+            timer,
+            tick_hz,
+            state,
+        }
+    }
+    /// Release timer peripheral.
+    #[inline]
+    pub fn free(self) -> TIMER {
+        self.timer
+    }
+}
+
+/// Waker and timer reference as the state for an async/await delay.
+#[cfg(feature = "async")]
+#[derive(Debug)]
+pub struct TimerState {
+    match_ready: atomic_waker::AtomicWaker,
+    ref_to_timer: AtomicUsize,
+    index: AtomicUsize,
+}
+
+#[cfg(feature = "async")]
+impl TimerState {
+    /// Creates the state for a timer delay.
+    #[inline]
+    pub const fn new() -> TimerState {
+        TimerState {
+            match_ready: atomic_waker::AtomicWaker::new(),
+            ref_to_timer: AtomicUsize::new(0),
+            index: AtomicUsize::new(0),
+        }
+    }
+    /// Use this state to handle timer interrupt.
+    #[inline]
+    pub fn on_interrupt(&self) {
+        let timer = self.ref_to_timer.load(Ordering::Acquire) as *const RegisterBlock;
+        // Interrupt may fire before any delay is created on this state.
+        if timer.is_null() {
+            return;
+        }
+        let timer = unsafe { &*timer };
+        let i = self.index.load(Ordering::Acquire);
+        if timer.match_state[i].read().has_match(0) {
+            unsafe {
+                timer.match_enable[i].modify(|val| val.disable_match(0));
+                timer.match_clear[i].write(MatchClear::default().clear_match(0));
+            }
+            self.match_ready.wake();
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl Default for TimerState {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "async")]
+struct WaitForTicks<'r> {
+    timer: &'r RegisterBlock,
+    index: usize,
+    start: u32,
+    ticks: u32,
+    registry: &'r atomic_waker::AtomicWaker,
+}
+
+#[cfg(feature = "async")]
+impl Future for WaitForTicks<'_> {
+    type Output = ();
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.registry.register(cx.waker());
+        // Elapsed ticks are checked as well, in case counter passed comparator before it was armed.
+        let now = self.timer.counter_value[self.index].read();
+        if now.wrapping_sub(self.start) >= self.ticks {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// Number of counter ticks at `tick_hz` lasting at least `time` in units of `1 / scale` seconds.
+#[cfg(feature = "async")]
+#[inline]
+const fn delay_ticks(tick_hz: u32, time: u32, scale: u64) -> u64 {
+    (time as u64 * tick_hz as u64).div_ceil(scale)
+}
+
+#[cfg(feature = "async")]
+#[inline]
+async fn timer_delay(timer: &RegisterBlock, index: usize, mut ticks: u64, state: &TimerState) {
+    // Keep each wait within half of counter range, so wrapping differences stay valid.
+    const MAX_CHUNK: u64 = (u32::MAX / 2) as u64;
+    while ticks > 0 {
+        let chunk = ticks.min(MAX_CHUNK) as u32;
+        let start = timer.counter_value[index].read();
+        unsafe {
+            timer.match_value[index][0].write(start.wrapping_add(chunk));
+            timer.match_clear[index].write(MatchClear::default().clear_match(0));
+            timer.match_enable[index].modify(|val| val.enable_match(0));
+        }
+        WaitForTicks {
+            timer,
+            index,
+            start,
+            ticks: chunk,
+            registry: &state.match_ready,
+        }
+        .await;
+        unsafe { timer.match_enable[index].modify(|val| val.disable_match(0)) };
+        ticks -= chunk as u64;
+    }
+}
+
+#[cfg(feature = "async")]
+impl<TIMER: Deref<Target = RegisterBlock>, const I: usize> embedded_hal_async::delay::DelayNs
+    for AsyncDelay<TIMER, I>
+{
+    #[inline]
+    async fn delay_ns(&mut self, ns: u32) {
+        let ticks = delay_ticks(self.tick_hz, ns, 1_000_000_000);
+        timer_delay(&self.timer, I, ticks, self.state).await
+    }
+    #[inline]
+    async fn delay_us(&mut self, us: u32) {
+        let ticks = delay_ticks(self.tick_hz, us, 1_000_000);
+        timer_delay(&self.timer, I, ticks, self.state).await
+    }
+    #[inline]
+    async fn delay_ms(&mut self, ms: u32) {
+        let ticks = delay_ticks(self.tick_hz, ms, 1_000);
+        timer_delay(&self.timer, I, ticks, self.state).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ClockConfig, ClockDivide, ClockSource, CounterEnable, CounterMode, MatchEnable,
+        PreloadControl, PreloadSource, RegisterBlock,
+    };
+
+    #[test]
+    fn struct_register_block_offset() {
+        assert_offsets!(RegisterBlock {
+            clock_config: 0x00,
+            match_value: 0x10,
+            counter_value: 0x2c,
+            match_state: 0x38,
+            match_enable: 0x44,
+            preload_value: 0x50,
+            preload_control: 0x5c,
+            match_clear: 0x78,
+            counter_enable: 0x84,
+            counter_mode: 0x88,
+            clock_divide: 0xbc,
+        });
+    }
+
+    #[test]
+    fn struct_clock_config_functions() {
+        let mut val = ClockConfig(0x0);
+        val = val.set_clock_source(0, ClockSource::Xclk);
+        assert_eq!(val.0, 0x00000003);
+        assert_eq!(val.clock_source(0), ClockSource::Xclk);
+        val = val.set_clock_source(1, ClockSource::F32kClk);
+        assert_eq!(val.0, 0x00000013);
+        assert_eq!(val.clock_source(1), ClockSource::F32kClk);
+        val = val.set_clock_source(0, ClockSource::None);
+        assert_eq!(val.0, 0x00000015);
+        assert_eq!(val.clock_source(0), ClockSource::None);
+    }
+
+    #[test]
+    fn struct_match_enable_functions() {
+        let mut val = MatchEnable(0x0);
+        val = val.enable_match(2);
+        assert_eq!(val.0, 0x00000004);
+        assert!(val.is_match_enabled(2));
+        val = val.disable_match(2);
+        assert_eq!(val.0, 0x00000000);
+        assert!(!val.is_match_enabled(2));
+    }
+
+    #[test]
+    fn struct_preload_control_functions() {
+        let mut val = PreloadControl(0x0);
+        val = val.set_preload_source(PreloadSource::Match1);
+        assert_eq!(val.0, 0x00000002);
+        assert_eq!(val.preload_source(), PreloadSource::Match1);
+        val = val.set_preload_source(PreloadSource::None);
+        assert_eq!(val.0, 0x00000000);
+        assert_eq!(val.preload_source(), PreloadSource::None);
+    }
+
+    #[test]
+    fn struct_counter_enable_mode_functions() {
+        let mut val = CounterEnable(0x0);
+        val = val.enable_counter(1);
+        assert_eq!(val.0, 0x00000004);
+        assert!(val.is_counter_enabled(1));
+        assert!(!val.is_counter_enabled(0));
+        val = val.disable_counter(1);
+        assert_eq!(val.0, 0x00000000);
+
+        let mut val = CounterMode(0x0);
+        val = val.enable_freerun(0);
+        assert_eq!(val.0, 0x00000002);
+        assert!(val.is_freerun_enabled(0));
+        val = val.disable_freerun(0);
+        assert_eq!(val.0, 0x00000000);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn async_delay_configures_freerun_counter() {
+        use super::{AsyncDelay, TimerState, delay_ticks};
+        use crate::clocks::Clocks;
+        use crate::mock::MockRegisters;
+        use embedded_time::rate::Hertz;
+
+        static STATE: TimerState = TimerState::new();
+        // Interrupt before any delay is created has no timer to serve.
+        STATE.on_interrupt();
+        let mock = MockRegisters::<RegisterBlock>::new();
+        let timer = mock.registers();
+        let clocks = Clocks::new(Hertz(40_000_000));
+        let delay = AsyncDelay::<_, 1>::new(timer, &clocks, &STATE);
+        assert_eq!(delay.tick_hz, 1_000_000);
+        assert_eq!(timer.clock_config.read().clock_source(1), ClockSource::Xclk);
+        assert_eq!(timer.clock_divide.read().clock_divide(1), 39);
+        assert!(timer.counter_mode.read().is_freerun_enabled(1));
+        assert!(timer.counter_enable.read().is_counter_enabled(1));
+
+        assert_eq!(delay_ticks(1_000_000, 1, 1_000_000_000), 1);
+        assert_eq!(delay_ticks(1_000_000, 1_500, 1_000_000_000), 2);
+        assert_eq!(delay_ticks(1_000_000, 500, 1_000), 500_000);
+        assert_eq!(
+            delay_ticks(1_000_000, u32::MAX, 1_000),
+            u32::MAX as u64 * 1_000
+        );
+    }
+
+    #[test]
+    fn periodic_elapsed_and_tasks() {
+        use super::Periodic;
+        use crate::clocks::Clocks;
+        use crate::mock::MockRegisters;
+        use embedded_time::rate::Hertz;
+
+        let mock = MockRegisters::<RegisterBlock>::new();
+        let timer = mock.registers();
+        let set_counter = |value: u32| mock.write(|timer| &timer.counter_value[0], value);
+        let clocks = Clocks::new(Hertz(40_000_000));
+        set_counter(u32::MAX - 100);
+        let mut periodic = Periodic::<_, 0>::new(timer, &clocks, 1_000);
+        assert!(timer.counter_mode.read().is_freerun_enabled(0));
+        assert!(timer.counter_enable.read().is_counter_enabled(0));
+        let mut task = periodic.task(250);
+        assert!(!periodic.elapsed());
+        assert!(!periodic.poll(&mut task));
+
+        // Counter wraps around; task period of 250 ticks has passed.
+        set_counter(200);
+        assert!(!periodic.elapsed());
+        assert!(periodic.poll(&mut task));
+        assert!(!periodic.poll(&mut task));
+
+        // Polled late; next period still ends 1000 ticks after the first one.
+        set_counter(1_000);
+        assert!(periodic.elapsed());
+        assert!(!periodic.elapsed());
+        set_counter(1_898);
+        assert!(!periodic.elapsed());
+        set_counter(1_899);
+        assert!(periodic.elapsed());
+
+        // Missed periods are skipped instead of reported in a row.
+        set_counter(10_000);
+        assert!(periodic.poll(&mut task));
+        assert!(!periodic.poll(&mut task));
+        set_counter(10_148);
+        assert!(!periodic.poll(&mut task));
+        set_counter(10_149);
+        assert!(periodic.poll(&mut task));
+    }
+
+    #[test]
+    fn struct_clock_divide_functions() {
+        let mut val = ClockDivide(0x0);
+        val = val.set_clock_divide(0, 39);
+        assert_eq!(val.0, 0x00002700);
+        assert_eq!(val.clock_divide(0), 39);
+        val = val.set_clock_divide(1, 0xff);
+        assert_eq!(val.0, 0x00ff2700);
+        assert_eq!(val.clock_divide(1), 0xff);
+    }
 }
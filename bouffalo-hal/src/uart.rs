@@ -14,8 +14,55 @@ mod error;
 pub use error::*;
 mod blocking;
 pub use blocking::*;
+mod checksum;
+pub use checksum::ChecksumKind;
+mod fmt_buffer;
+pub use fmt_buffer::FmtBuffer;
+#[cfg(feature = "async")]
 mod asynch;
+#[cfg(feature = "async")]
 pub use asynch::*;
+mod dmx;
+pub use dmx::*;
+mod lin;
+pub use lin::{LinChecksum, LinConfig};
+mod queue;
+pub use queue::TransmitQueue;
+mod status;
+pub use status::SerialStatus;
+mod xmodem;
+pub use xmodem::{XmodemError, xmodem_receive};
+#[cfg(feature = "m1s-dock")]
+mod board;
+#[cfg(feature = "m1s-dock")]
+pub use board::*;
+#[cfg(feature = "dma")]
+mod circular;
+#[cfg(feature = "dma")]
+pub use circular::*;
+#[cfg(any(test, feature = "mock"))]
+mod mock;
+#[cfg(any(test, feature = "mock"))]
+pub use mock::*;
+#[cfg(feature = "critical-section")]
+mod shared;
+#[cfg(feature = "critical-section")]
+pub use shared::*;
+
+/// Transmit string literals on a blocking serial without formatting machinery.
+///
+/// Multiple literals are concatenated at compile time. Use `write!` instead when values
+/// need to be formatted.
+///
+/// ```ignore
+/// uart_print!(serial, "Hello from ", "bouffalo-hal!\r\n");
+/// ```
+#[macro_export]
+macro_rules! uart_print {
+    ($serial:expr, $($s:literal),+ $(,)?) => {
+        $serial.write_str_blocking(concat!($($s),+))
+    };
+}
 
 /// Extend constructor to owned UART register blocks.
 pub trait UartExt<PADS>: Sized {
@@ -29,6 +76,7 @@ pub trait UartExt<PADS>: Sized {
     where
         PADS: Pads<I>;
     /// Creates an interrupt driven async/await serial instance without DMA configurations.
+    #[cfg(feature = "async")]
     fn with_interrupt<const I: usize>(
         self,
         config: Config,
@@ -53,6 +101,7 @@ impl<UART: Deref<Target = RegisterBlock>, PADS> UartExt<PADS> for UART {
     {
         BlockingSerial::freerun(self, config, pads, clocks)
     }
+    #[cfg(feature = "async")]
     #[inline]
     fn with_interrupt<const I: usize>(
         self,
@@ -67,3 +116,9 @@ impl<UART: Deref<Target = RegisterBlock>, PADS> UartExt<PADS> for UART {
         AsyncSerial::new(self, config, pads, clocks, state)
     }
 }
+
+/// Object safe byte sink, so that a logger may hold `&mut dyn ByteWriter` and switch backends at runtime.
+pub trait ByteWriter {
+    /// Write all bytes in `buf`, blocking until they are queued for transmission.
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), Error>;
+}
@@ -1,5 +1,6 @@
 use super::{
-    Config, ConfigError, Error, Interrupt, InterruptClear, Pads, RegisterBlock, uart_config,
+    Config, ConfigError, Error, Interrupt, InterruptClear, Pads, RegisterBlock,
+    blocking::uart_reset_peripheral, uart_config,
 };
 use crate::clocks::Clocks;
 use core::{
@@ -36,6 +37,8 @@ impl<UART: Deref<Target = RegisterBlock>, PADS> AsyncSerial<UART, PADS> {
         let (bit_period, data_config, transmit_config, receive_config) =
             uart_config::<I, PADS>(config, &clocks)?;
 
+        // Discard state left by bootloader or previous firmware.
+        uart_reset_peripheral(&uart);
         // Write bit period.
         unsafe { uart.bit_period.write(bit_period) };
         // Write the bit-order.
@@ -1,11 +1,43 @@
-use super::{Config, ConfigError, Error, Pads, RegisterBlock, uart_config};
+use super::queue::uart_start_transmit;
+use super::status::{SerialStatus, uart_status};
+use super::{
+    ByteWriter, ChecksumKind, Config, ConfigError, Error, Interrupt, InterruptClear,
+    InterruptEnable, LinConfig, Pads, Parity, RegisterBlock, TransmitQueue, uart_config,
+    uart_read_config,
+};
 use crate::clocks::Clocks;
+use crate::glb::v2::{ClockGate, PeripheralReset, UartClock};
 use core::ops::Deref;
+use embedded_time::rate::Baud;
 
 /// Managed blocking serial peripheral.
 pub struct BlockingSerial<UART, PADS> {
     uart: UART,
     pads: PADS,
+    peeked: Option<u8>,
+    in_break: bool,
+}
+
+impl<'a, PADS> BlockingSerial<&'a RegisterBlock, PADS> {
+    /// Creates a polling serial session on a borrowed peripheral, leaving its owner in place.
+    ///
+    /// It configures the peripheral as [`freerun`](Self::freerun) does; dropping the session
+    /// leaves the peripheral running with this configuration, and the owner can reach it
+    /// again once the borrow ends. Registers are volatile cells, so sharing the peripheral
+    /// is memory safe; still, two sessions or raw register writes during a session on the
+    /// same peripheral would overwrite each other's configuration and queue contents.
+    #[inline]
+    pub fn borrow<const I: usize, UART: Deref<Target = RegisterBlock>>(
+        uart: &'a UART,
+        config: Config,
+        pads: PADS,
+        clocks: &Clocks,
+    ) -> Result<Self, ConfigError>
+    where
+        PADS: Pads<I>,
+    {
+        Self::freerun(&**uart, config, pads, clocks)
+    }
 }
 
 impl<UART: Deref<Target = RegisterBlock>, PADS> BlockingSerial<UART, PADS> {
@@ -24,6 +56,8 @@ impl<UART: Deref<Target = RegisterBlock>, PADS> BlockingSerial<UART, PADS> {
         let (bit_period, data_config, transmit_config, receive_config) =
             uart_config::<I, PADS>(config, &clocks)?;
 
+        // Discard state left by bootloader or previous firmware.
+        uart_reset_peripheral(&uart);
         // Write bit period.
         unsafe { uart.bit_period.write(bit_period) };
         // Write the bit-order.
@@ -36,7 +70,106 @@ impl<UART: Deref<Target = RegisterBlock>, PADS> BlockingSerial<UART, PADS> {
         // Configure receive feature.
         unsafe { uart.receive_config.write(receive_config) };
 
-        Ok(Self { uart, pads })
+        Ok(Self {
+            uart,
+            pads,
+            peeked: None,
+            in_break: false,
+        })
+    }
+
+    /// Creates a polling serial instance as [`freerun`](Self::freerun) does, enabling the
+    /// peripheral clock gate in global configuration first.
+    ///
+    /// Clock gate is disabled again if configuration fails.
+    #[inline]
+    pub fn freerun_gated<const I: usize>(
+        uart: UART,
+        config: Config,
+        pads: PADS,
+        clocks: &Clocks,
+        glb: &crate::glb::v2::RegisterBlock,
+    ) -> Result<Self, ConfigError>
+    where
+        PADS: Pads<I>,
+    {
+        UartClock::<I>::enable_clock(glb);
+        Self::freerun(uart, config, pads, clocks)
+            .inspect_err(|_| UartClock::<I>::disable_clock(glb))
+    }
+
+    /// Creates a polling serial instance, rejecting baudrates the divider cannot meet closely.
+    ///
+    /// Fails with `ConfigError::BaudrateErrorTooLarge` if the achievable baudrate of either
+    /// half deviates from `config` by more than `max_error_ppm` parts per million, as
+    /// computed by [`Clocks::baud_error`]. Returns the serial with the achieved transmit
+    /// baudrate, e.g. for logging; the receive baudrate can be read back with `config`.
+    #[inline]
+    pub fn new_checked<const I: usize>(
+        uart: UART,
+        config: Config,
+        pads: PADS,
+        clocks: &Clocks,
+        max_error_ppm: u32,
+    ) -> Result<(Self, Baud), ConfigError>
+    where
+        PADS: Pads<I>,
+    {
+        let (transmit_baudrate, transmit_error) = clocks
+            .baud_error::<I>(config.transmit_baudrate)
+            .map_err(|e| match e {
+                ConfigError::BaudrateTooHigh => ConfigError::TransmitBaudrateTooHigh,
+                ConfigError::BaudrateTooLow => ConfigError::TransmitBaudrateTooLow,
+                e => e,
+            })?;
+        let (_, receive_error) = clocks
+            .baud_error::<I>(config.receive_baudrate)
+            .map_err(|e| match e {
+                ConfigError::BaudrateTooHigh => ConfigError::ReceiveBaudrateTooHigh,
+                ConfigError::BaudrateTooLow => ConfigError::ReceiveBaudrateTooLow,
+                e => e,
+            })?;
+        if transmit_error.unsigned_abs() > max_error_ppm
+            || receive_error.unsigned_abs() > max_error_ppm
+        {
+            return Err(ConfigError::BaudrateErrorTooLarge);
+        }
+        let serial = Self::freerun(uart, config, pads, clocks)?;
+        Ok((serial, transmit_baudrate))
+    }
+
+    /// Creates a polling serial instance as a LIN node.
+    ///
+    /// Serial is configured as [`freerun`](Self::freerun) does with `config`, which should
+    /// hold 8-bit words, no parity and one stop bit. LIN transmission and reception are
+    /// then enabled with break length of `lin`; frame checksums are computed with
+    /// [`LinChecksum::compute`](super::LinChecksum::compute) of `lin.checksum`.
+    #[inline]
+    pub fn new_lin<const I: usize>(
+        uart: UART,
+        config: Config,
+        lin: LinConfig,
+        pads: PADS,
+        clocks: &Clocks,
+    ) -> Result<Self, ConfigError>
+    where
+        PADS: Pads<I>,
+    {
+        if lin.break_bits > LinConfig::MAX_BREAK_BITS {
+            return Err(ConfigError::LinBreakBitsTooMany);
+        }
+        let serial = Self::freerun(uart, config, pads, clocks)?;
+        unsafe {
+            serial
+                .uart
+                .transmit_config
+                .modify(|val| val.enable_lin_transmit().set_lin_break_bits(lin.break_bits));
+            serial
+                .uart
+                .receive_config
+                .modify(|val| val.enable_lin_receive());
+        }
+        Ok(serial)
     }
 
     /// Enable transmit DMA.
@@ -67,13 +200,500 @@ impl<UART: Deref<Target = RegisterBlock>, PADS> BlockingSerial<UART, PADS> {
         self
     }
 
+    /// Change baudrate and frame format of a running serial.
+    ///
+    /// Bytes in transmit queue are sent with the old configuration before the switch;
+    /// this function blocks until they have left the bus. Bytes already in receive
+    /// queue were decoded with the old configuration and are kept for reading.
+    /// Transmitter and receiver are disabled while registers are being rewritten.
+    #[inline]
+    pub fn reconfigure<const I: usize>(
+        &mut self,
+        config: Config,
+        clocks: &Clocks,
+    ) -> Result<(), ConfigError>
+    where
+        PADS: Pads<I>,
+    {
+        let (bit_period, data_config, transmit_config, receive_config) =
+            uart_config::<I, PADS>(config, clocks)?;
+
+        uart_wait_transmit_idle(&self.uart);
+        unsafe {
+            self.uart
+                .transmit_config
+                .modify(|val| val.disable_txd().disable_freerun());
+            self.uart.receive_config.modify(|val| val.disable_rxd());
+
+            self.uart.bit_period.write(bit_period);
+            self.uart.data_config.write(data_config);
+            self.uart
+                .transmit_config
+                .write(transmit_config.enable_freerun());
+            self.uart.receive_config.write(receive_config);
+        }
+        Ok(())
+    }
+
+    /// Recompute bit periods after UART clock frequency changed from `old` to `new`.
+    ///
+    /// Baudrates configured under `old` clocks are kept. Waits until transmission is done,
+    /// as a byte on the bus would be corrupted by the change.
+    #[inline]
+    pub fn clocks_changed<const I: usize>(
+        &mut self,
+        old: &Clocks,
+        new: &Clocks,
+    ) -> Result<(), ConfigError>
+    where
+        PADS: Pads<I>,
+    {
+        let (Some(old_clock), Some(new_clock)) = (old.uart_clock::<I>(), new.uart_clock::<I>())
+        else {
+            return Err(ConfigError::ClockSource);
+        };
+        if old_clock == new_clock {
+            return Ok(());
+        }
+        let bit_period = self.uart.bit_period.read();
+        let baud = |interval: u16| {
+            let interval = interval as u32;
+            Baud((old_clock.0 + interval / 2) / interval)
+        };
+        let transmit_interval = new
+            .baud_interval::<I>(baud(bit_period.transmit_time_interval()))
+            .map_err(|e| match e {
+                ConfigError::BaudrateTooHigh => ConfigError::TransmitBaudrateTooHigh,
+                ConfigError::BaudrateTooLow => ConfigError::TransmitBaudrateTooLow,
+                e => e,
+            })?;
+        let receive_interval = new
+            .baud_interval::<I>(baud(bit_period.receive_time_interval()))
+            .map_err(|e| match e {
+                ConfigError::BaudrateTooHigh => ConfigError::ReceiveBaudrateTooHigh,
+                ConfigError::BaudrateTooLow => ConfigError::ReceiveBaudrateTooLow,
+                e => e,
+            })?;
+        uart_wait_transmit_idle(&self.uart);
+        unsafe {
+            self.uart.bit_period.write(
+                bit_period
+                    .set_transmit_time_interval(transmit_interval)
+                    .set_receive_time_interval(receive_interval),
+            )
+        };
+        Ok(())
+    }
+
+    /// Put serial peripheral into a known idle state, as if the chip was just reset.
+    ///
+    /// Transmitter and receiver are stopped, DMA and all interrupts are disabled, both
+    /// queues are cleared and latched interrupt and overflow flags are discarded. Bytes
+    /// in transmit queue are dropped without being sent. `freerun` already does this, so
+    /// warm restart behaves like cold one; call `reconfigure` afterwards to start again.
+    #[inline]
+    pub fn reset_peripheral(&self) {
+        uart_reset_peripheral(&self.uart)
+    }
+
+    /// Send one byte with a wrong parity bit, for testing receiver error handling.
+    ///
+    /// UART peripheral has no bit to force a parity error. Instead, this function waits
+    /// until previous data has left the bus, switches between odd and even parity
+    /// for this byte only and restores the original parity afterwards. It blocks until
+    /// the byte is sent.
+    ///
+    /// Returns `false` without sending anything if transmit parity is disabled,
+    /// as there is no parity bit to corrupt.
+    #[inline]
+    pub fn send_bad_parity(&mut self, byte: u8) -> bool {
+        let config = self.uart.transmit_config.read();
+        let bad_parity = match config.parity() {
+            Parity::Even => Parity::Odd,
+            Parity::Odd => Parity::Even,
+            Parity::None => return false,
+        };
+        uart_wait_transmit_idle(&self.uart);
+        unsafe {
+            self.uart
+                .transmit_config
+                .write(config.set_parity(bad_parity));
+            self.uart.fifo_write.write(byte);
+        }
+        uart_wait_transmit_idle(&self.uart);
+        unsafe { self.uart.transmit_config.write(config) };
+        true
+    }
+
+    /// Transmit bytes from an iterator, blocking until there is room in transmit queue.
+    ///
+    /// Bytes are produced on demand, thus long generated sequences can be sent without
+    /// a buffer holding all of them.
+    #[inline]
+    pub fn write_iter(&mut self, iter: impl IntoIterator<Item = u8>) {
+        uart_write_iter(&self.uart, iter)
+    }
+
+    /// Transmit a string, blocking until all bytes are queued.
+    ///
+    /// Unlike `write_fmt`, this does not pull in formatting code; see also [`uart_print!`](crate::uart_print).
+    #[inline]
+    pub fn write_str_blocking(&mut self, s: &str) {
+        uart_write_iter(&self.uart, s.bytes())
+    }
+
+    /// Transmit bytes as 7-bit frames, discarding the most significant bit of each byte.
+    ///
+    /// Serial should be configured with 7-bit word length; parity bit, if any, is generated
+    /// by hardware in the configured bit order.
+    #[inline]
+    pub fn write_frame_7bit(&mut self, data: &[u8]) {
+        uart_write_iter(&self.uart, data.iter().map(|byte| byte & 0x7f))
+    }
+
+    /// Transmit `segments` back-to-back using one chain of DMA descriptors, without copying
+    /// them into a common buffer.
+    ///
+    /// At most [`MAX_DMA_SEGMENTS`] segments are supported. `channel` should be configured
+    /// as memory to peripheral transfer requested by transmit signal of this serial, with
+    /// byte width and incrementing source address; byte width places no alignment
+    /// constraint on segment addresses or lengths. `lli_pool` needs one item for every
+    /// 4064 bytes of each segment. Returns when the last byte is queued for transmission,
+    /// or `Error::FifoOverflow` if transmit queue overflowed during the transfer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than [`MAX_DMA_SEGMENTS`] segments, or if `lli_pool`
+    /// is too small for them.
+    #[cfg(feature = "dma")]
+    #[inline]
+    pub fn write_dma_scatter<DMA: Deref<Target = crate::dma::RegisterBlock>>(
+        &mut self,
+        channel: &crate::dma::Dma<DMA>,
+        lli_pool: &mut [crate::dma::LliPool],
+        segments: &[&[u8]],
+    ) -> Result<(), Error> {
+        uart_write_dma_scatter(&self.uart, channel, lli_pool, segments)
+    }
+
+    /// Receive 7-bit frames into `buf`, returning number of bytes read.
+    ///
+    /// Most significant bit of each byte is cleared. Returns `Error::Parity` if hardware
+    /// detected a parity mismatch in received data.
+    #[inline]
+    pub fn read_frame_7bit(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        uart_read_7bit(&self.uart, &mut self.peeked, buf)
+    }
+
+    /// Receive bytes into `buf` until `delim` is received, returning number of bytes stored.
+    ///
+    /// The delimiter is stored at the end of `buf` if `keep_delim` is `true`, or consumed and
+    /// dropped otherwise. If `buf` fills before the delimiter arrives, returns `Error::BufferFull`
+    /// with all of `buf` holding received bytes; the next byte is left unread, so the caller
+    /// can keep reading the same line into another buffer.
+    #[inline]
+    pub fn read_until(
+        &mut self,
+        delim: u8,
+        keep_delim: bool,
+        buf: &mut [u8],
+    ) -> Result<usize, Error> {
+        uart_read_until(&self.uart, &mut self.peeked, delim, keep_delim, buf)
+    }
+
+    /// Transmit `tx` while receiving into `rx`, for protocols where every byte sent elicits a response.
+    ///
+    /// Transmit and receive queues are served in turn, and no more bytes are sent ahead than
+    /// receive queue can hold, so responses are not lost while waiting for transmit queue space.
+    /// Returns after `tx` is queued and `rx` is filled; if `rx` is longer than `tx`, it waits for
+    /// the remaining bytes without sending, and if shorter, responses to the extra `tx` bytes are
+    /// left in receive queue. Sending stays throttled after `rx` is filled, discarding the oldest
+    /// extra responses whenever receive queue is full, so only the latest ones are left. A peeked
+    /// byte counts as the first received byte.
+    ///
+    /// Returns `Error::Overrun` if receive queue overflowed, in which case the queue is cleared.
+    #[inline]
+    pub fn transfer(&mut self, tx: &[u8], rx: &mut [u8]) -> Result<(), Error> {
+        uart_transfer(&self.uart, &mut self.peeked, tx, rx)
+    }
+
+    /// Start transmitting `buf` in background, driven by transmit FIFO ready interrupt.
+    ///
+    /// Call `on_interrupt` of `queue` from the UART interrupt handler to keep transmitting,
+    /// and check `is_transmit_complete` or the handler's return value for completion.
+    /// Returns `WouldBlock` if the previous transmission of `queue` has not completed.
+    #[inline]
+    pub fn start_transmit(
+        &mut self,
+        buf: &'static [u8],
+        queue: &'static TransmitQueue,
+    ) -> nb::Result<(), Error> {
+        uart_start_transmit(&self.uart, buf, queue)
+    }
+
+    /// Check if transmit queue is empty.
+    ///
+    /// The last byte may still be shifting out on the bus when this returns `true`.
+    #[inline]
+    pub fn is_transmit_fifo_empty(&self) -> bool {
+        uart_is_transmit_fifo_empty(&self.uart)
+    }
+
+    /// Check if all data has left the bus, including the byte in transmit shift register.
+    ///
+    /// Use this before switching an RS-485 transceiver back to receive direction.
+    #[inline]
+    pub fn is_transmit_done(&self) -> bool {
+        uart_is_transmit_done(&self.uart)
+    }
+
+    /// Switch transmitter to counted mode, ending the transfer after `length` bytes.
+    ///
+    /// Free-run mode is disabled and the transmit end flag is cleared; poll
+    /// [`is_transfer_length_reached`](Self::is_transfer_length_reached) or enable
+    /// `Interrupt::TransmitEnd` to learn when the transfer is over.
+    #[inline]
+    pub fn set_transfer_length(&mut self, length: u16) {
+        uart_set_transfer_length(&self.uart, length)
+    }
+
+    /// Number of bytes written into transmit queue that have not yet been sent.
+    ///
+    /// Hardware does not count bytes sent in counted mode; progress of a large transfer
+    /// is the number of bytes written so far minus this value.
+    #[inline]
+    pub fn transmit_pending(&self) -> u8 {
+        uart_transmit_pending(&self.uart)
+    }
+
+    /// Check if the counted transfer set by [`set_transfer_length`](Self::set_transfer_length) has ended.
+    #[inline]
+    pub fn is_transfer_length_reached(&self) -> bool {
+        uart_is_transfer_length_reached(&self.uart)
+    }
+
+    /// Read a snapshot of transmit and receive state, e.g. for a diagnostics command.
+    ///
+    /// Baudrates are computed from bit periods and UART clock `I` in `clocks`.
+    #[inline]
+    pub fn status<const I: usize>(&self, clocks: &Clocks) -> SerialStatus {
+        uart_status(&self.uart, clocks.uart_clock::<I>(), self.in_break)
+    }
+
+    /// Read back serial configuration from peripheral registers, without side effects.
+    ///
+    /// Baudrates are computed from bit periods and UART clock `I` in `clocks`, so they are
+    /// the rates actually produced by the divider, which may differ slightly from the rates
+    /// requested. Returns `ConfigError::ClockSource` if UART clock is unknown.
+    #[inline]
+    pub fn config<const I: usize>(&self, clocks: &Clocks) -> Result<Config, ConfigError> {
+        let uart_clock = clocks.uart_clock::<I>().ok_or(ConfigError::ClockSource)?;
+        Ok(uart_read_config(&self.uart, uart_clock))
+    }
+
+    /// Number of bytes that can still be received before receive queue overruns.
+    ///
+    /// The queue holds [`FIFO_DEPTH`] bytes, and the rest are received bytes waiting to be read.
+    #[inline]
+    pub fn receive_fifo_free(&self) -> u8 {
+        uart_receive_fifo_free(&self.uart)
+    }
+
+    /// Discard all received bytes and clear receive error flags, leaving transmission untouched.
+    ///
+    /// Use it to resynchronize with the remote side after an error; bytes arriving after
+    /// this call are kept.
+    #[inline]
+    pub fn clear_receive_fifo(&mut self) {
+        self.in_break = false;
+        uart_clear_receive_fifo(&self.uart, &mut self.peeked)
+    }
+
+    /// Check queue overflow and underflow flags, clearing the queue that raised one.
+    ///
+    /// Returns `Error::Overrun` if receive queue overflowed, `Error::FifoOverflow` if transmit
+    /// queue overflowed, or `Error::FifoUnderflow` if either queue underflowed. DMA transfers
+    /// do not report these conditions by themselves; call it after a transfer or on a timeout
+    /// to tell whether data was lost. Bytes left in the affected queue are discarded.
+    #[inline]
+    pub fn check_fifo_errors(&mut self) -> Result<(), Error> {
+        uart_check_receive_fifo(&self.uart)?;
+        uart_check_transmit_fifo(&self.uart)
+    }
+
+    /// Receive one byte, or return `WouldBlock` if receive queue is empty.
+    ///
+    /// Trait implementations of single-byte reads delegate to this function.
+    #[inline]
+    pub fn read_byte(&mut self) -> nb::Result<u8, Error> {
+        uart_read_nb(&self.uart, &mut self.peeked)
+    }
+
+    /// Transmit one byte, or return `WouldBlock` if transmit queue is full.
+    ///
+    /// Trait implementations of single-byte writes delegate to this function.
+    #[inline]
+    pub fn write_byte(&mut self, word: u8) -> nb::Result<(), Error> {
+        uart_write_nb(&self.uart, word)
+    }
+
+    /// Look at the next received byte without consuming it.
+    ///
+    /// Hardware has no peek; the byte is read out of receive queue and cached, and is
+    /// returned again by the next peek or read.
+    #[inline]
+    pub fn peek(&mut self) -> nb::Result<u8, Error> {
+        uart_peek_nb(&self.uart, &mut self.peeked)
+    }
+
+    /// Transmit `buf` on a shared single-wire or multi-drop bus, checking every byte for collision.
+    ///
+    /// Hardware has no collision detection; transmit and receive pads must sense the same bus
+    /// line, so that every byte sent is received back and compared. Bytes already received are
+    /// discarded before transmitting. On mismatch it stops and returns `Error::Collision`,
+    /// after which the caller should back off and retry the whole frame.
+    #[inline]
+    pub fn write_checked(&mut self, buf: &[u8]) -> Result<(), Error> {
+        uart_write_checked(&self.uart, &mut self.peeked, buf)
+    }
+
+    /// Transmit `payload` followed by its checksum byte, blocking until all bytes are queued.
+    #[inline]
+    pub fn write_with_checksum(&mut self, payload: &[u8], kind: ChecksumKind) -> Result<(), Error> {
+        uart_write_with_checksum(&self.uart, payload, kind)
+    }
+
+    /// Receive a payload of `buf.len()` bytes followed by its checksum byte.
+    ///
+    /// Blocks until the whole frame is received. Returns `Error::Checksum` if the received
+    /// checksum byte does not match the payload, in which case `buf` holds the received payload.
+    #[inline]
+    pub fn read_and_verify(&mut self, buf: &mut [u8], kind: ChecksumKind) -> Result<(), Error> {
+        uart_read_and_verify(&self.uart, &mut self.peeked, buf, kind)
+    }
+
+    /// Check if a break has been received, consuming its zero byte.
+    ///
+    /// A break holds receive line low beyond a full frame, e.g. the start of a DMX512 packet
+    /// or LIN frame. It is received as a zero byte without stop bit, and the receiver stays
+    /// busy afterwards as the line is still low. A zero byte with valid stop bit, or any byte
+    /// with framing error, releases the line and is not reported as break.
+    ///
+    /// Data bytes are not lost: a received byte that is not a break is kept for the next
+    /// read as `peek` does. Bytes received before the break should be read first, as a break
+    /// is only recognized as the last byte in receive queue. A detected break is also
+    /// reported by [`status`](Self::status) until the line is released.
+    #[inline]
+    pub fn break_received(&mut self) -> bool {
+        self.in_break = uart_break_received(&self.uart, &mut self.peeked);
+        self.in_break
+    }
+
+    /// Check if receive line has been idle for at least `bits` bit periods since the last received byte.
+    ///
+    /// It uses the receive timeout hardware and reports `true` once for every idle gap,
+    /// which makes it suitable for detecting frame boundaries, e.g. the 3.5-character silent
+    /// interval of Modbus RTU. Changing `bits` restarts the detection.
+    #[inline]
+    pub fn poll_idle(&mut self, bits: u8) -> bool {
+        uart_poll_idle(&self.uart, bits)
+    }
+
+    /// Start receiving continuously into `buf` using circular DMA transfer.
+    ///
+    /// `channel` should be configured as peripheral to memory transfer requested by
+    /// receive signal of this serial, with byte width and incrementing destination address.
+    /// `lli_pool` needs one item for every 4064 bytes of `buf`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lli_pool` is too small for `buf`.
+    #[cfg(feature = "dma")]
+    #[inline]
+    pub fn read_dma_circular<DMA: Deref<Target = crate::dma::RegisterBlock>>(
+        &mut self,
+        channel: crate::dma::Dma<DMA>,
+        lli_pool: &'static mut [crate::dma::LliPool],
+        buf: &'static mut [u8],
+    ) -> super::CircularReceive<DMA> {
+        super::CircularReceive::start(&self.uart, channel, lli_pool, buf)
+    }
+
+    /// Reset the whole peripheral through global configuration, releasing peripheral and pads.
+    ///
+    /// Recovers from states that clearing queues cannot; all serial registers return to reset
+    /// values, thus the returned peripheral should be configured again, e.g. by `freerun`.
+    #[inline]
+    pub fn hard_reset<const I: usize>(self, glb: &crate::glb::v2::RegisterBlock) -> (UART, PADS)
+    where
+        PADS: Pads<I>,
+    {
+        UartClock::<I>::reset(glb);
+        (self.uart, self.pads)
+    }
+
+    /// Lend pads to `f` with transmitter and receiver disabled, then take them back.
+    ///
+    /// Waits for pending bytes to leave the bus first. The closure gets pads by value, so
+    /// that a pad can be converted into another mode, e.g. for bit-banging on the same line,
+    /// as long as it is converted back and returned with the original type. Transmitter and
+    /// receiver are restored to their previous states afterwards.
+    ///
+    /// Program aborts if `f` panics, as the serial cannot be left without its pads.
+    #[inline]
+    pub fn with_pads_released<R>(&mut self, f: impl FnOnce(PADS) -> (PADS, R)) -> R {
+        struct AbortOnUnwind;
+        impl Drop for AbortOnUnwind {
+            fn drop(&mut self) {
+                // Panicking while unwinding aborts the program.
+                panic!("pads were not returned to the serial");
+            }
+        }
+        uart_wait_transmit_idle(&self.uart);
+        let transmit_config = self.uart.transmit_config.read();
+        let receive_config = self.uart.receive_config.read();
+        unsafe {
+            self.uart
+                .transmit_config
+                .write(transmit_config.disable_txd());
+            self.uart.receive_config.write(receive_config.disable_rxd());
+        }
+        let guard = AbortOnUnwind;
+        let pads = unsafe { core::ptr::read(&self.pads) };
+        let (pads, ans) = f(pads);
+        unsafe { core::ptr::write(&mut self.pads, pads) };
+        core::mem::forget(guard);
+        unsafe {
+            self.uart.transmit_config.write(transmit_config);
+            self.uart.receive_config.write(receive_config);
+        }
+        ans
+    }
+
     /// Release serial instance and return its peripheral and pads.
+    ///
+    /// A byte cached by `peek` is discarded.
     #[inline]
     pub fn free(self) -> (UART, PADS) {
         (self.uart, self.pads)
     }
 
+    /// Release serial instance as [`free`](Self::free) does, and disable the peripheral clock
+    /// gate in global configuration.
+    ///
+    /// Pending bytes in transmit queue are not waited for; flush the serial before if needed.
+    #[inline]
+    pub fn free_gated<const I: usize>(self, glb: &crate::glb::v2::RegisterBlock) -> (UART, PADS)
+    where
+        PADS: Pads<I>,
+    {
+        UartClock::<I>::disable_clock(glb);
+        self.free()
+    }
+
     /// Split serial instance into transmit and receive halves.
+    ///
+    /// A byte cached by `peek` is discarded.
     #[inline]
     pub fn split<const I: usize>(self) -> <PADS as Pads<I>>::Split<UART>
     where
@@ -89,10 +709,212 @@ pub struct BlockingTransmitHalf<UART, PADS> {
     pub(crate) _pads: PADS,
 }
 
+impl<UART: Deref<Target = RegisterBlock>, PADS> BlockingTransmitHalf<UART, PADS> {
+    /// Transmit one byte, or return `WouldBlock` if transmit queue is full.
+    #[inline]
+    pub fn write_byte(&mut self, word: u8) -> nb::Result<(), Error> {
+        uart_write_nb(&self.uart, word)
+    }
+
+    /// Transmit bytes from an iterator, blocking until there is room in transmit queue.
+    #[inline]
+    pub fn write_iter(&mut self, iter: impl IntoIterator<Item = u8>) {
+        uart_write_iter(&self.uart, iter)
+    }
+
+    /// Transmit a string, blocking until all bytes are queued.
+    #[inline]
+    pub fn write_str_blocking(&mut self, s: &str) {
+        uart_write_iter(&self.uart, s.bytes())
+    }
+
+    /// Transmit bytes as 7-bit frames, discarding the most significant bit of each byte.
+    #[inline]
+    pub fn write_frame_7bit(&mut self, data: &[u8]) {
+        uart_write_iter(&self.uart, data.iter().map(|byte| byte & 0x7f))
+    }
+
+    /// Transmit `payload` followed by its checksum byte.
+    ///
+    /// See [`BlockingSerial::write_with_checksum`].
+    #[inline]
+    pub fn write_with_checksum(&mut self, payload: &[u8], kind: ChecksumKind) -> Result<(), Error> {
+        uart_write_with_checksum(&self.uart, payload, kind)
+    }
+
+    /// Transmit `segments` back-to-back using one chain of DMA descriptors.
+    ///
+    /// See [`BlockingSerial::write_dma_scatter`] for channel configuration and limits.
+    #[cfg(feature = "dma")]
+    #[inline]
+    pub fn write_dma_scatter<DMA: Deref<Target = crate::dma::RegisterBlock>>(
+        &mut self,
+        channel: &crate::dma::Dma<DMA>,
+        lli_pool: &mut [crate::dma::LliPool],
+        segments: &[&[u8]],
+    ) -> Result<(), Error> {
+        uart_write_dma_scatter(&self.uart, channel, lli_pool, segments)
+    }
+
+    /// Check transmit queue overflow and underflow flags, clearing the queue if one is set.
+    ///
+    /// See [`BlockingSerial::check_fifo_errors`].
+    #[inline]
+    pub fn check_fifo_errors(&mut self) -> Result<(), Error> {
+        uart_check_transmit_fifo(&self.uart)
+    }
+
+    /// Start transmitting `buf` in background, driven by transmit FIFO ready interrupt.
+    ///
+    /// See [`BlockingSerial::start_transmit`].
+    #[inline]
+    pub fn start_transmit(
+        &mut self,
+        buf: &'static [u8],
+        queue: &'static TransmitQueue,
+    ) -> nb::Result<(), Error> {
+        uart_start_transmit(&self.uart, buf, queue)
+    }
+
+    /// Check if transmit queue is empty.
+    ///
+    /// The last byte may still be shifting out on the bus when this returns `true`.
+    #[inline]
+    pub fn is_transmit_fifo_empty(&self) -> bool {
+        uart_is_transmit_fifo_empty(&self.uart)
+    }
+
+    /// Check if all data has left the bus, including the byte in transmit shift register.
+    #[inline]
+    pub fn is_transmit_done(&self) -> bool {
+        uart_is_transmit_done(&self.uart)
+    }
+
+    /// Switch transmitter to counted mode, ending the transfer after `length` bytes.
+    ///
+    /// See [`BlockingSerial::set_transfer_length`].
+    #[inline]
+    pub fn set_transfer_length(&mut self, length: u16) {
+        uart_set_transfer_length(&self.uart, length)
+    }
+
+    /// Number of bytes written into transmit queue that have not yet been sent.
+    #[inline]
+    pub fn transmit_pending(&self) -> u8 {
+        uart_transmit_pending(&self.uart)
+    }
+
+    /// Check if the counted transfer has ended.
+    #[inline]
+    pub fn is_transfer_length_reached(&self) -> bool {
+        uart_is_transfer_length_reached(&self.uart)
+    }
+}
+
 /// Receive half from splitted serial structure.
 pub struct BlockingReceiveHalf<UART, PADS> {
     pub(crate) uart: UART,
     pub(crate) _pads: PADS,
+    pub(crate) peeked: Option<u8>,
+}
+
+impl<UART: Deref<Target = RegisterBlock>, PADS> BlockingReceiveHalf<UART, PADS> {
+    /// Number of bytes that can still be received before receive queue overruns.
+    ///
+    /// The queue holds [`FIFO_DEPTH`] bytes, and the rest are received bytes waiting to be read.
+    #[inline]
+    pub fn receive_fifo_free(&self) -> u8 {
+        uart_receive_fifo_free(&self.uart)
+    }
+
+    /// Discard all received bytes and clear receive error flags.
+    ///
+    /// See [`BlockingSerial::clear_receive_fifo`].
+    #[inline]
+    pub fn clear_receive_fifo(&mut self) {
+        uart_clear_receive_fifo(&self.uart, &mut self.peeked)
+    }
+
+    /// Check receive queue overflow and underflow flags, clearing the queue if one is set.
+    ///
+    /// See [`BlockingSerial::check_fifo_errors`].
+    #[inline]
+    pub fn check_fifo_errors(&mut self) -> Result<(), Error> {
+        uart_check_receive_fifo(&self.uart)
+    }
+
+    /// Receive one byte, or return `WouldBlock` if receive queue is empty.
+    #[inline]
+    pub fn read_byte(&mut self) -> nb::Result<u8, Error> {
+        uart_read_nb(&self.uart, &mut self.peeked)
+    }
+
+    /// Look at the next received byte without consuming it.
+    ///
+    /// See [`BlockingSerial::peek`].
+    #[inline]
+    pub fn peek(&mut self) -> nb::Result<u8, Error> {
+        uart_peek_nb(&self.uart, &mut self.peeked)
+    }
+
+    /// Receive bytes into `buf` until `delim` is received, returning number of bytes stored.
+    ///
+    /// See [`BlockingSerial::read_until`].
+    #[inline]
+    pub fn read_until(
+        &mut self,
+        delim: u8,
+        keep_delim: bool,
+        buf: &mut [u8],
+    ) -> Result<usize, Error> {
+        uart_read_until(&self.uart, &mut self.peeked, delim, keep_delim, buf)
+    }
+
+    /// Receive a payload followed by its checksum byte, and verify the checksum.
+    ///
+    /// See [`BlockingSerial::read_and_verify`].
+    #[inline]
+    pub fn read_and_verify(&mut self, buf: &mut [u8], kind: ChecksumKind) -> Result<(), Error> {
+        uart_read_and_verify(&self.uart, &mut self.peeked, buf, kind)
+    }
+
+    /// Check if a break has been received, consuming its zero byte.
+    ///
+    /// See [`BlockingSerial::break_received`] for how a break is told apart from data.
+    #[inline]
+    pub fn break_received(&mut self) -> bool {
+        uart_break_received(&self.uart, &mut self.peeked)
+    }
+
+    /// Check if receive line has been idle for at least `bits` bit periods since the last received byte.
+    ///
+    /// Reports `true` once for every idle gap.
+    #[inline]
+    pub fn poll_idle(&mut self, bits: u8) -> bool {
+        uart_poll_idle(&self.uart, bits)
+    }
+
+    /// Receive 7-bit frames into `buf`, returning number of bytes read.
+    ///
+    /// See [`BlockingSerial::read_frame_7bit`] for parity handling.
+    #[inline]
+    pub fn read_frame_7bit(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        uart_read_7bit(&self.uart, &mut self.peeked, buf)
+    }
+
+    /// Start receiving continuously into `buf` using circular DMA transfer.
+    ///
+    /// See [`BlockingSerial::read_dma_circular`] for channel configuration.
+    #[cfg(feature = "dma")]
+    #[inline]
+    pub fn read_dma_circular<DMA: Deref<Target = crate::dma::RegisterBlock>>(
+        &mut self,
+        channel: crate::dma::Dma<DMA>,
+        lli_pool: &'static mut [crate::dma::LliPool],
+        buf: &'static mut [u8],
+    ) -> super::CircularReceive<DMA> {
+        super::CircularReceive::start(&self.uart, channel, lli_pool, buf)
+    }
 }
 
 #[inline]
@@ -110,6 +932,95 @@ fn uart_write(uart: &RegisterBlock, buf: &[u8]) -> Result<usize, Error> {
     Ok(len)
 }
 
+/// Stop the peripheral and clear queues, interrupt state and error flags.
+#[inline]
+pub(crate) fn uart_reset_peripheral(uart: &RegisterBlock) {
+    unsafe {
+        uart.transmit_config
+            .modify(|val| val.disable_txd().disable_freerun());
+        uart.receive_config.modify(|val| val.disable_rxd());
+        uart.interrupt_enable.write(InterruptEnable::default());
+        // Clearing queues also clears their overflow and underflow flags.
+        uart.fifo_config_0.modify(|val| {
+            val.disable_transmit_dma()
+                .disable_receive_dma()
+                .clear_transmit_fifo()
+                .clear_receive_fifo()
+        });
+        uart.interrupt_clear
+            .write(InterruptClear::default().clear_all());
+    }
+}
+
+#[inline]
+fn uart_write_all(uart: &RegisterBlock, mut buf: &[u8]) -> Result<(), Error> {
+    while !buf.is_empty() {
+        let len = uart_write(uart, buf)?;
+        buf = &buf[len..];
+    }
+    Ok(())
+}
+
+/// Depth of transmit and receive queues in bytes.
+pub const FIFO_DEPTH: u8 = 32;
+
+/// Maximum number of segments in one scatter-gather DMA transmission.
+#[cfg(feature = "dma")]
+pub const MAX_DMA_SEGMENTS: usize = 16;
+
+#[cfg(feature = "dma")]
+#[inline]
+fn uart_write_dma_scatter<DMA: Deref<Target = crate::dma::RegisterBlock>>(
+    uart: &RegisterBlock,
+    channel: &crate::dma::Dma<DMA>,
+    lli_pool: &mut [crate::dma::LliPool],
+    segments: &[&[u8]],
+) -> Result<(), Error> {
+    use crate::dma::LliTransfer;
+    assert!(
+        segments.len() <= MAX_DMA_SEGMENTS,
+        "too many segments for scatter-gather transmission"
+    );
+    let mut transfer = [LliTransfer {
+        src_addr: 0,
+        dst_addr: 0,
+        nbytes: 0,
+    }; MAX_DMA_SEGMENTS];
+    let mut len = 0;
+    for segment in segments.iter().filter(|segment| !segment.is_empty()) {
+        transfer[len] = LliTransfer {
+            src_addr: segment.as_ptr() as u32,
+            dst_addr: &uart.fifo_write as *const _ as u32,
+            nbytes: segment.len() as u32,
+        };
+        len += 1;
+    }
+    if len == 0 {
+        return Ok(());
+    }
+    let max_lli_count = lli_pool.len() as u32;
+    let count = channel.lli_reload(lli_pool, max_lli_count, &mut transfer, len as u32);
+    assert!(count > 0, "linked list item pool too small for segments");
+    unsafe {
+        uart.fifo_config_0.modify(|val| val.enable_transmit_dma());
+    }
+    channel.start();
+    while channel.is_busy() {
+        core::hint::spin_loop();
+    }
+    uart_check_transmit_fifo(uart)
+}
+
+#[inline]
+pub(crate) fn uart_write_iter(uart: &RegisterBlock, iter: impl IntoIterator<Item = u8>) {
+    for byte in iter {
+        while uart.fifo_config_1.read().transmit_available_bytes() == 0 {
+            core::hint::spin_loop();
+        }
+        unsafe { uart.fifo_write.write(byte) };
+    }
+}
+
 #[inline]
 fn uart_write_nb(uart: &RegisterBlock, word: u8) -> nb::Result<(), Error> {
     if uart.fifo_config_1.read().transmit_available_bytes() == 0 {
@@ -129,6 +1040,96 @@ fn uart_flush(uart: &RegisterBlock) -> Result<(), Error> {
     Ok(())
 }
 
+#[inline]
+fn uart_receive_fifo_free(uart: &RegisterBlock) -> u8 {
+    FIFO_DEPTH.saturating_sub(uart.fifo_config_1.read().receive_available_bytes())
+}
+
+#[inline]
+fn uart_check_receive_fifo(uart: &RegisterBlock) -> Result<(), Error> {
+    let config = uart.fifo_config_0.read();
+    let error = if config.receive_fifo_overflow() {
+        Error::Overrun
+    } else if config.receive_fifo_underflow() {
+        Error::FifoUnderflow
+    } else {
+        return Ok(());
+    };
+    // Clearing the queue also clears its overflow and underflow flags.
+    unsafe { uart.fifo_config_0.modify(|val| val.clear_receive_fifo()) };
+    Err(error)
+}
+
+#[inline]
+fn uart_check_transmit_fifo(uart: &RegisterBlock) -> Result<(), Error> {
+    let config = uart.fifo_config_0.read();
+    let error = if config.transmit_fifo_overflow() {
+        Error::FifoOverflow
+    } else if config.transmit_fifo_underflow() {
+        Error::FifoUnderflow
+    } else {
+        return Ok(());
+    };
+    unsafe { uart.fifo_config_0.modify(|val| val.clear_transmit_fifo()) };
+    Err(error)
+}
+
+#[inline]
+fn uart_clear_receive_fifo(uart: &RegisterBlock, peeked: &mut Option<u8>) {
+    *peeked = None;
+    unsafe {
+        // Clearing receive queue also clears its overflow and underflow flags.
+        uart.fifo_config_0.modify(|val| val.clear_receive_fifo());
+        uart.interrupt_clear.write(
+            InterruptClear::default()
+                .clear_interrupt(Interrupt::ReceiveParityError)
+                .clear_interrupt(Interrupt::ReceiveFifoError)
+                .clear_interrupt(Interrupt::ReceiveSyncError),
+        );
+    }
+}
+
+#[inline]
+fn uart_set_transfer_length(uart: &RegisterBlock, length: u16) {
+    unsafe {
+        uart.transmit_config
+            .modify(|val| val.disable_freerun().set_transfer_length(length));
+        uart.interrupt_clear
+            .write(InterruptClear::default().clear_interrupt(Interrupt::TransmitEnd));
+    }
+}
+
+#[inline]
+fn uart_transmit_pending(uart: &RegisterBlock) -> u8 {
+    FIFO_DEPTH.saturating_sub(uart.fifo_config_1.read().transmit_available_bytes())
+}
+
+#[inline]
+fn uart_is_transfer_length_reached(uart: &RegisterBlock) -> bool {
+    uart.interrupt_state
+        .read()
+        .has_interrupt(Interrupt::TransmitEnd)
+}
+
+#[inline]
+fn uart_is_transmit_fifo_empty(uart: &RegisterBlock) -> bool {
+    uart.fifo_config_1.read().transmit_available_bytes() == 32
+}
+
+#[inline]
+fn uart_is_transmit_done(uart: &RegisterBlock) -> bool {
+    // An empty transmit FIFO queue does not mean the last byte has left the shift
+    // register; check bus state as well.
+    uart_is_transmit_fifo_empty(uart) && !uart.bus_state.read().transmit_busy()
+}
+
+#[inline]
+pub(crate) fn uart_wait_transmit_idle(uart: &RegisterBlock) {
+    while !uart_is_transmit_done(uart) {
+        core::hint::spin_loop();
+    }
+}
+
 #[inline]
 fn uart_flush_nb(uart: &RegisterBlock) -> nb::Result<(), Error> {
     if uart.fifo_config_1.read().transmit_available_bytes() != 32 {
@@ -138,7 +1139,75 @@ fn uart_flush_nb(uart: &RegisterBlock) -> nb::Result<(), Error> {
 }
 
 #[inline]
-fn uart_read(uart: &RegisterBlock, buf: &mut [u8]) -> Result<usize, Error> {
+fn uart_poll_idle(uart: &RegisterBlock, bits: u8) -> bool {
+    // Timeout event occurs after (value + 1) idle bit periods.
+    let value = bits.saturating_sub(1);
+    let config = uart.receive_timeout.read();
+    if config.timeout_value() != value {
+        unsafe {
+            uart.receive_timeout.write(config.set_timeout_value(value));
+            uart.interrupt_clear
+                .write(InterruptClear::default().clear_interrupt(Interrupt::ReceiveTimeout));
+        }
+        return false;
+    }
+    if uart
+        .interrupt_state
+        .read()
+        .has_interrupt(Interrupt::ReceiveTimeout)
+    {
+        unsafe {
+            uart.interrupt_clear
+                .write(InterruptClear::default().clear_interrupt(Interrupt::ReceiveTimeout))
+        };
+        return true;
+    }
+    false
+}
+
+#[inline]
+fn uart_break_received(uart: &RegisterBlock, peeked: &mut Option<u8>) -> bool {
+    // Peeked byte was received before any byte in queue, and has yet to be read.
+    if peeked.is_some() {
+        return false;
+    }
+    // Break is the last received byte; receiver cannot start another frame while line is low.
+    if uart.fifo_config_1.read().receive_available_bytes() != 1
+        || !uart.bus_state.read().receive_busy()
+    {
+        return false;
+    }
+    match uart.fifo_read.read() {
+        0 => true,
+        byte => {
+            *peeked = Some(byte);
+            false
+        }
+    }
+}
+
+#[inline]
+fn uart_read(
+    uart: &RegisterBlock,
+    peeked: &mut Option<u8>,
+    buf: &mut [u8],
+) -> Result<usize, Error> {
+    if let Some(byte) = *peeked
+        && let Some((first, rest)) = buf.split_first_mut()
+    {
+        // Return cached byte with whatever else is already received, without blocking.
+        *first = byte;
+        *peeked = None;
+        let len = core::cmp::min(
+            uart.fifo_config_1.read().receive_available_bytes() as usize,
+            rest.len(),
+        );
+        rest.iter_mut()
+            .take(len)
+            .for_each(|slot| *slot = uart.fifo_read.read());
+        return Ok(1 + len);
+    }
+    uart_check_receive_fifo(uart)?;
     while uart.fifo_config_1.read().receive_available_bytes() == 0 {
         core::hint::spin_loop();
     }
@@ -153,7 +1222,164 @@ fn uart_read(uart: &RegisterBlock, buf: &mut [u8]) -> Result<usize, Error> {
 }
 
 #[inline]
-fn uart_read_nb(uart: &RegisterBlock) -> nb::Result<u8, Error> {
+fn uart_transfer(
+    uart: &RegisterBlock,
+    peeked: &mut Option<u8>,
+    tx: &[u8],
+    rx: &mut [u8],
+) -> Result<(), Error> {
+    // Keeping no more bytes in flight than receive queue depth ensures every response fits.
+    let (mut sent, mut received, mut discarded) = (0, 0, 0);
+    while sent < tx.len() || received < rx.len() {
+        uart_check_receive_fifo(uart)?;
+        if received < rx.len()
+            && let Ok(byte) = uart_read_nb(uart, peeked)
+        {
+            rx[received] = byte;
+            received += 1;
+            continue;
+        }
+        let in_flight = sent.saturating_sub(received + discarded);
+        if sent < tx.len() && in_flight < FIFO_DEPTH as usize {
+            if uart_write_nb(uart, tx[sent]).is_ok() {
+                sent += 1;
+                continue;
+            }
+        } else if sent < tx.len() && uart_read_nb(uart, peeked).is_ok() {
+            // `rx` is full; drop the oldest extra response to make room for the next one.
+            discarded += 1;
+            continue;
+        }
+        core::hint::spin_loop();
+    }
+    Ok(())
+}
+
+#[inline]
+fn uart_write_checked(
+    uart: &RegisterBlock,
+    peeked: &mut Option<u8>,
+    buf: &[u8],
+) -> Result<(), Error> {
+    *peeked = None;
+    for _ in 0..uart.fifo_config_1.read().receive_available_bytes() {
+        let _ = uart.fifo_read.read();
+    }
+    for &byte in buf {
+        while uart.fifo_config_1.read().transmit_available_bytes() == 0 {
+            core::hint::spin_loop();
+        }
+        unsafe { uart.fifo_write.write(byte) };
+        // Wait for the byte to come back from bus before sending the next one.
+        while uart.fifo_config_1.read().receive_available_bytes() == 0 {
+            core::hint::spin_loop();
+        }
+        if uart.fifo_read.read() != byte {
+            return Err(Error::Collision);
+        }
+    }
+    Ok(())
+}
+
+#[inline]
+fn uart_write_with_checksum(
+    uart: &RegisterBlock,
+    payload: &[u8],
+    kind: ChecksumKind,
+) -> Result<(), Error> {
+    uart_write_all(uart, payload)?;
+    uart_write_all(uart, &[kind.compute(payload)])
+}
+
+#[inline]
+fn uart_read_and_verify(
+    uart: &RegisterBlock,
+    peeked: &mut Option<u8>,
+    buf: &mut [u8],
+    kind: ChecksumKind,
+) -> Result<(), Error> {
+    for slot in buf.iter_mut() {
+        *slot = nb::block!(uart_read_nb(uart, peeked))?;
+    }
+    let checksum = nb::block!(uart_read_nb(uart, peeked))?;
+    if checksum != kind.compute(buf) {
+        return Err(Error::Checksum);
+    }
+    Ok(())
+}
+
+#[inline]
+fn uart_read_7bit(
+    uart: &RegisterBlock,
+    peeked: &mut Option<u8>,
+    buf: &mut [u8],
+) -> Result<usize, Error> {
+    let len = uart_read(uart, peeked, buf)?;
+    buf[..len].iter_mut().for_each(|byte| *byte &= 0x7f);
+    if uart
+        .interrupt_state
+        .read()
+        .has_interrupt(Interrupt::ReceiveParityError)
+    {
+        unsafe {
+            uart.interrupt_clear
+                .write(InterruptClear::default().clear_interrupt(Interrupt::ReceiveParityError))
+        };
+        return Err(Error::Parity);
+    }
+    Ok(len)
+}
+
+#[inline]
+fn uart_read_until(
+    uart: &RegisterBlock,
+    peeked: &mut Option<u8>,
+    delim: u8,
+    keep_delim: bool,
+    buf: &mut [u8],
+) -> Result<usize, Error> {
+    let mut len = 0;
+    loop {
+        let byte = loop {
+            match uart_peek_nb(uart, peeked) {
+                Ok(byte) => break byte,
+                Err(nb::Error::WouldBlock) => core::hint::spin_loop(),
+                Err(nb::Error::Other(e)) => return Err(e),
+            }
+        };
+        if byte == delim && !keep_delim {
+            *peeked = None;
+            return Ok(len);
+        }
+        // Leave the byte in peek cache, so nothing is lost when buffer is full.
+        let Some(slot) = buf.get_mut(len) else {
+            return Err(Error::BufferFull);
+        };
+        *slot = byte;
+        *peeked = None;
+        len += 1;
+        if byte == delim {
+            return Ok(len);
+        }
+    }
+}
+
+#[inline]
+fn uart_peek_nb(uart: &RegisterBlock, peeked: &mut Option<u8>) -> nb::Result<u8, Error> {
+    if let Some(byte) = *peeked {
+        return Ok(byte);
+    }
+    let byte = uart_read_nb(uart, peeked)?;
+    *peeked = Some(byte);
+    Ok(byte)
+}
+
+#[inline]
+fn uart_read_nb(uart: &RegisterBlock, peeked: &mut Option<u8>) -> nb::Result<u8, Error> {
+    if let Some(byte) = peeked.take() {
+        return Ok(byte);
+    }
+    uart_check_receive_fifo(uart)?;
     if uart.fifo_config_1.read().receive_available_bytes() == 0 {
         return Err(nb::Error::WouldBlock);
     }
@@ -201,7 +1427,7 @@ impl<UART: Deref<Target = RegisterBlock>, PADS> embedded_hal_nb::serial::Write
 {
     #[inline]
     fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
-        uart_write_nb(&self.uart, word)
+        self.write_byte(word)
     }
     #[inline]
     fn flush(&mut self) -> nb::Result<(), Self::Error> {
@@ -212,7 +1438,7 @@ impl<UART: Deref<Target = RegisterBlock>, PADS> embedded_hal_nb::serial::Write
 impl<UART: Deref<Target = RegisterBlock>, PADS> embedded_io::Read for BlockingSerial<UART, PADS> {
     #[inline]
     fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
-        uart_read(&self.uart, buf)
+        uart_read(&self.uart, &mut self.peeked, buf)
     }
 }
 
@@ -221,7 +1447,7 @@ impl<UART: Deref<Target = RegisterBlock>, PADS> embedded_hal_nb::serial::Read
 {
     #[inline]
     fn read(&mut self) -> nb::Result<u8, Self::Error> {
-        uart_read_nb(&self.uart)
+        self.read_byte()
     }
 }
 
@@ -243,7 +1469,7 @@ impl<UART: Deref<Target = RegisterBlock>, PADS> embedded_hal_nb::serial::Write
 {
     #[inline]
     fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
-        uart_write_nb(&self.uart, word)
+        self.write_byte(word)
     }
     #[inline]
     fn flush(&mut self) -> nb::Result<(), Self::Error> {
@@ -256,7 +1482,7 @@ impl<UART: Deref<Target = RegisterBlock>, PADS> embedded_io::Read
 {
     #[inline]
     fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
-        uart_read(&self.uart, buf)
+        uart_read(&self.uart, &mut self.peeked, buf)
     }
 }
 
@@ -265,6 +1491,598 @@ impl<UART: Deref<Target = RegisterBlock>, PADS> embedded_hal_nb::serial::Read
 {
     #[inline]
     fn read(&mut self) -> nb::Result<u8, Self::Error> {
-        uart_read_nb(&self.uart)
+        self.read_byte()
+    }
+}
+
+impl<UART: Deref<Target = RegisterBlock>, PADS> ByteWriter for BlockingSerial<UART, PADS> {
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), Error> {
+        uart_write_all(&self.uart, buf)
+    }
+}
+
+impl<UART: Deref<Target = RegisterBlock>, PADS> ByteWriter for BlockingTransmitHalf<UART, PADS> {
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), Error> {
+        uart_write_all(&self.uart, buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BlockingReceiveHalf, BlockingSerial, BlockingTransmitHalf, Error, uart_reset_peripheral,
+    };
+    use crate::clocks::Clocks;
+    use crate::glb::v2::{ClockGate, UartClock};
+    use crate::mock::MockRegisters;
+    use crate::uart::{
+        BitOrder, ByteWriter, ChecksumKind, Config, ConfigError, Deglitch, Interrupt, LinConfig,
+        MockPads, MockUart, Parity, StopBits, WordLength,
+    };
+    use embedded_time::rate::{Baud, Hertz};
+
+    #[test]
+    fn reset_peripheral_clears_state() {
+        let mock = MockUart::filled(0xffff_ffff);
+        let uart = mock.registers();
+        uart_reset_peripheral(uart);
+
+        assert!(!uart.transmit_config.read().is_txd_enabled());
+        assert!(!uart.transmit_config.read().is_freerun_enabled());
+        assert!(!uart.receive_config.read().is_rxd_enabled());
+        let interrupt_enable = uart.interrupt_enable.read();
+        assert!(!interrupt_enable.is_interrupt_enabled(Interrupt::ReceiveFifoReady));
+        assert!(!interrupt_enable.is_interrupt_enabled(Interrupt::ReceiveFifoError));
+        let fifo_config_0 = uart.fifo_config_0.read();
+        assert!(!fifo_config_0.is_transmit_dma_enabled());
+        assert!(!fifo_config_0.is_receive_dma_enabled());
+        assert_eq!(mock.read_word(0x80) & 0b1100, 0b1100);
+        assert_eq!(mock.read_word(0x28) & 0xfff, 0xfff);
+    }
+
+    #[test]
+    fn byte_writer_trait_object() {
+        let mock = MockUart::filled(0xffff_ffff);
+        let mut tx = BlockingTransmitHalf {
+            uart: mock.registers(),
+            _pads: (),
+        };
+        let writer: &mut dyn ByteWriter = &mut tx;
+        writer.write_all(b"ok").unwrap();
+        assert_eq!(mock.read_word(0x88) as u8, b'k');
+    }
+
+    #[test]
+    fn frame_7bit() {
+        let mock = MockUart::filled(0xffff_ffff);
+        let (mut tx, mut rx) = (
+            BlockingTransmitHalf {
+                uart: mock.registers(),
+                _pads: (),
+            },
+            BlockingReceiveHalf {
+                uart: mock.registers(),
+                _pads: (),
+                peeked: None,
+            },
+        );
+        tx.write_frame_7bit(&[0xc1]);
+        assert_eq!(mock.read_word(0x88) as u8, 0x41);
+
+        // Leave queue error flags clear, so the read reaches the parity check.
+        mock.write_word(0x80, 0);
+        let mut buf = [0u8; 1];
+        assert!(matches!(rx.read_frame_7bit(&mut buf), Err(Error::Parity)));
+        mock.write_word(0x20, 0);
+        assert!(matches!(rx.read_frame_7bit(&mut buf), Ok(1)));
+        assert_eq!(buf, [0x7f]);
+    }
+
+    #[test]
+    fn break_received() {
+        let mock = MockUart::new();
+        let mut rx = BlockingReceiveHalf {
+            uart: mock.registers(),
+            _pads: (),
+            peeked: None,
+        };
+        assert!(!rx.break_received());
+        // One zero byte received, receive line still held low.
+        mock.write_word(0x84, 0x0000_0100);
+        mock.write_word(0x30, 0x2);
+        assert!(rx.break_received());
+        // Zero byte with valid stop bit releases the receiver.
+        mock.write_word(0x30, 0x0);
+        assert!(!rx.break_received());
+        // Nonzero byte is not a break even if receiver is busy; it is kept for reading.
+        mock.write_word(0x30, 0x2);
+        mock.write_word(0x8c, 0x55);
+        assert!(!rx.break_received());
+        assert_eq!(rx.peeked, Some(0x55));
+        // Peeked byte is read before any break is looked for.
+        mock.write_word(0x8c, 0x00);
+        assert!(!rx.break_received());
+        assert!(matches!(rx.read_byte(), Ok(0x55)));
+        assert!(rx.break_received());
+    }
+
+    #[test]
+    fn peek_then_read() {
+        let mock = MockUart::new();
+        let mut rx = BlockingReceiveHalf {
+            uart: mock.registers(),
+            _pads: (),
+            peeked: None,
+        };
+        assert!(matches!(rx.peek(), Err(nb::Error::WouldBlock)));
+        mock.write_word(0x84, 0x0000_0100);
+        mock.write_word(0x8c, 0x41);
+        assert_eq!(rx.peek().ok(), Some(0x41));
+        mock.write_word(0x8c, 0x42);
+        assert_eq!(rx.peek().ok(), Some(0x41));
+        assert_eq!(
+            embedded_hal_nb::serial::Read::read(&mut rx).ok(),
+            Some(0x41)
+        );
+        assert_eq!(
+            embedded_hal_nb::serial::Read::read(&mut rx).ok(),
+            Some(0x42)
+        );
+
+        assert_eq!(rx.peek().ok(), Some(0x42));
+        let mut buf = [0u8; 3];
+        assert_eq!(embedded_io::Read::read(&mut rx, &mut buf).ok(), Some(2));
+        assert_eq!(buf, [0x42, 0x42, 0x00]);
+    }
+
+    #[test]
+    fn transfer_interleaved() {
+        let mock = MockUart::new();
+        let mut serial = BlockingSerial {
+            uart: mock.registers(),
+            pads: MockPads,
+            peeked: None,
+            in_break: false,
+        };
+        // Transmit queue has space, and a response byte is always pending.
+        mock.write_word(0x84, 0x0000_0120);
+        mock.write_word(0x8c, 0x5a);
+        let mut rx = [0u8; 4];
+        assert!(serial.transfer(b"ab", &mut rx).is_ok());
+        assert_eq!(rx, [0x5a; 4]);
+        assert_eq!(mock.read_word(0x88) as u8, b'b');
+
+        // Transmit is much longer than receive buffer; extra responses must not stall sending.
+        let mut tx = [0u8; 40];
+        tx[39] = b'z';
+        let mut rx = [0u8; 2];
+        assert!(serial.transfer(&tx, &mut rx).is_ok());
+        assert_eq!(rx, [0x5a; 2]);
+        assert_eq!(mock.read_word(0x88) as u8, b'z');
+
+        let mut rx = [0u8; 4];
+        mock.write_word(0x80, 0x40);
+        assert!(matches!(
+            serial.transfer(b"c", &mut rx),
+            Err(Error::Overrun)
+        ));
+        assert_eq!(mock.read_word(0x80) & 0x8, 0x8);
+    }
+
+    #[test]
+    fn write_checked_collision() {
+        let mock = MockUart::new();
+        let mut serial = BlockingSerial {
+            uart: mock.registers(),
+            pads: MockPads,
+            peeked: Some(0x00),
+            in_break: false,
+        };
+        // Bus sense always reads back 0x55; receive count stays at 1 as the mock cannot drain it.
+        mock.write_word(0x84, 0x0000_0120);
+        mock.write_word(0x8c, 0x55);
+        assert!(serial.write_checked(&[0x55]).is_ok());
+        assert_eq!(serial.peeked, None);
+        assert!(matches!(
+            serial.write_checked(&[0x55, 0xaa]),
+            Err(Error::Collision)
+        ));
+        assert_eq!(mock.read_word(0x88) as u8, 0xaa);
+    }
+
+    #[test]
+    fn receive_fifo_free() {
+        let mock = MockUart::new();
+        let rx = BlockingReceiveHalf {
+            uart: mock.registers(),
+            _pads: (),
+            peeked: None,
+        };
+        assert_eq!(rx.receive_fifo_free(), 32);
+        mock.write_word(0x84, 0x0000_0520);
+        assert_eq!(rx.receive_fifo_free(), 27);
+        mock.write_word(0x84, 0x0000_2000);
+        assert_eq!(rx.receive_fifo_free(), 0);
+    }
+
+    #[test]
+    fn counted_transfer_progress() {
+        let mock = MockUart::new();
+        mock.write_word(0x00, 0x0000_8f05);
+        let mut tx = BlockingTransmitHalf {
+            uart: mock.registers(),
+            _pads: (),
+        };
+        tx.set_transfer_length(1000);
+        let transmit_config = tx.uart.transmit_config.read();
+        assert!(!transmit_config.is_freerun_enabled());
+        assert_eq!(transmit_config.transfer_length(), 1000);
+        assert_eq!(mock.read_word(0x28), 1 << 0);
+        assert!(!tx.is_transfer_length_reached());
+
+        mock.write_word(0x84, 0x0000_0014);
+        assert_eq!(tx.transmit_pending(), 12);
+        mock.write_word(0x84, 0x0000_0020);
+        assert_eq!(tx.transmit_pending(), 0);
+        mock.write_word(0x20, 1 << 0);
+        assert!(tx.is_transfer_length_reached());
+    }
+
+    #[test]
+    fn read_until_delimiter() {
+        let mock = MockUart::new();
+        mock.write_word(0x84, 0x0000_0100);
+        mock.write_word(0x8c, b'\n' as u32);
+        let mut rx = BlockingReceiveHalf {
+            uart: mock.registers(),
+            _pads: (),
+            peeked: Some(b'A'),
+        };
+        let mut buf = [0u8; 4];
+        assert_eq!(rx.read_until(b'\n', true, &mut buf).unwrap(), 2);
+        assert_eq!(&buf[..2], b"A\n");
+        rx.peeked = Some(b'B');
+        assert_eq!(rx.read_until(b'\n', false, &mut buf).unwrap(), 1);
+        assert_eq!(buf[0], b'B');
+        assert_eq!(rx.peeked, None);
+
+        mock.write_word(0x8c, b'x' as u32);
+        let res = rx.read_until(b'\n', true, &mut buf);
+        assert!(matches!(res, Err(Error::BufferFull)));
+        assert_eq!(&buf, b"xxxx");
+        assert_eq!(rx.peeked, Some(b'x'));
+    }
+
+    #[test]
+    fn clear_receive_fifo_only() {
+        let mock = MockUart::new();
+        mock.write_word(0x80, 1 << 0);
+        let mut rx = BlockingReceiveHalf {
+            uart: mock.registers(),
+            _pads: (),
+            peeked: Some(b'A'),
+        };
+        rx.clear_receive_fifo();
+        assert_eq!(rx.peeked, None);
+        assert_eq!(mock.read_word(0x80), (1 << 0) | (1 << 3));
+        assert_eq!(mock.read_word(0x28), (1 << 5) | (1 << 7) | (1 << 8));
+    }
+
+    #[test]
+    fn fifo_error_flags() {
+        let mock = MockUart::new();
+        let mut serial = BlockingSerial {
+            uart: mock.registers(),
+            pads: MockPads,
+            peeked: None,
+            in_break: false,
+        };
+        assert!(serial.check_fifo_errors().is_ok());
+
+        // Receive underflow fails the read path and clears receive queue only.
+        mock.write_word(0x80, 1 << 7);
+        let mut buf = [0u8; 1];
+        assert!(matches!(
+            embedded_io::Read::read(&mut serial, &mut buf),
+            Err(Error::FifoUnderflow)
+        ));
+        assert_eq!(mock.read_word(0x80), (1 << 7) | (1 << 3));
+
+        mock.write_word(0x80, 1 << 4);
+        assert!(matches!(
+            serial.check_fifo_errors(),
+            Err(Error::FifoOverflow)
+        ));
+        assert_eq!(mock.read_word(0x80), (1 << 4) | (1 << 2));
+
+        // A transmit half ignores receive flags.
+        mock.write_word(0x80, 1 << 6);
+        let mut tx = BlockingTransmitHalf {
+            uart: mock.registers(),
+            _pads: (),
+        };
+        assert!(tx.check_fifo_errors().is_ok());
+        assert!(matches!(serial.check_fifo_errors(), Err(Error::Overrun)));
+    }
+
+    #[test]
+    fn checksum_trailer() {
+        let mock = MockUart::new();
+        mock.write_word(0x84, 0x0000_0120);
+        let mut tx = BlockingTransmitHalf {
+            uart: mock.registers(),
+            _pads: (),
+        };
+        tx.write_with_checksum(&[0x12, 0x34, 0x56], ChecksumKind::Xor)
+            .unwrap();
+        assert_eq!(mock.read_word(0x88) as u8, 0x70);
+
+        mock.write_word(0x8c, 0x5a);
+        let mut rx = BlockingReceiveHalf {
+            uart: mock.registers(),
+            _pads: (),
+            peeked: None,
+        };
+        let mut buf = [0u8; 1];
+        rx.read_and_verify(&mut buf, ChecksumKind::Xor).unwrap();
+        assert_eq!(buf, [0x5a]);
+        let mut buf = [0u8; 2];
+        let res = rx.read_and_verify(&mut buf, ChecksumKind::Sum);
+        assert!(matches!(res, Err(Error::Checksum)));
+        assert_eq!(buf, [0x5a, 0x5a]);
+    }
+
+    #[test]
+    fn read_write_byte() {
+        let mock = MockUart::new();
+        let mut serial = BlockingSerial {
+            uart: mock.registers(),
+            pads: (),
+            peeked: None,
+            in_break: false,
+        };
+        assert!(matches!(serial.read_byte(), Err(nb::Error::WouldBlock)));
+        assert!(matches!(
+            serial.write_byte(0x42),
+            Err(nb::Error::WouldBlock)
+        ));
+        mock.write_word(0x84, 0x0000_0101);
+        mock.write_word(0x8c, 0x24);
+        assert_eq!(serial.read_byte().unwrap(), 0x24);
+        serial.write_byte(0x42).unwrap();
+        assert_eq!(mock.read_word(0x88) as u8, 0x42);
+    }
+
+    #[test]
+    fn uart_print_literal() {
+        let mock = MockUart::filled(0xffff_ffff);
+        let mut tx = BlockingTransmitHalf {
+            uart: mock.registers(),
+            _pads: (),
+        };
+        crate::uart_print!(tx, "hello, ", "world");
+        assert_eq!(mock.read_word(0x88) as u8, b'd');
+    }
+
+    #[test]
+    fn clocks_changed_keeps_baudrate() {
+        let clocks = Clocks::new(Hertz(40_000_000));
+        let mock = MockUart::new();
+        let config = Config::default().set_baudrate(Baud(2_000_000));
+        let mut serial =
+            BlockingSerial::freerun::<0>(mock.registers(), config, MockPads, &clocks).unwrap();
+        let new = clocks.clone().with_uart_clock::<0>(Hertz(40_000_000));
+        // Transmit queue empty and bus idle.
+        mock.write_word(0x84, 32);
+        assert_eq!(serial.clocks_changed::<0>(&clocks, &new), Ok(()));
+        let bit_period = mock.registers().bit_period.read();
+        assert_eq!(bit_period.transmit_time_interval(), 20);
+        assert_eq!(bit_period.receive_time_interval(), 20);
+    }
+
+    #[test]
+    fn freerun_writes_configuration() {
+        let clocks = Clocks::new(Hertz(40_000_000));
+        let mock = MockUart::new();
+        let config = Config::default().set_baudrate(Baud(2_000_000));
+        let serial = BlockingSerial::freerun::<0>(mock.registers(), config, MockPads, &clocks);
+        assert!(serial.is_ok());
+
+        let uart = mock.registers();
+        let bit_period = uart.bit_period.read();
+        assert_eq!(bit_period.transmit_time_interval(), 40);
+        assert_eq!(bit_period.receive_time_interval(), 40);
+        let transmit_config = uart.transmit_config.read();
+        assert!(transmit_config.is_txd_enabled());
+        assert!(transmit_config.is_cts_enabled());
+        assert!(transmit_config.is_freerun_enabled());
+        assert!(uart.receive_config.read().is_rxd_enabled());
+    }
+
+    #[test]
+    fn freerun_gated_switches_clock_gate() {
+        let clocks = Clocks::new(Hertz(40_000_000));
+        let mock = MockUart::new();
+        let glb = MockRegisters::<crate::glb::v2::RegisterBlock>::new();
+        let config = Config::default().set_baudrate(Baud(2_000_000));
+        let serial = BlockingSerial::freerun_gated::<0>(
+            mock.registers(),
+            config,
+            MockPads,
+            &clocks,
+            glb.registers(),
+        )
+        .unwrap();
+        assert_eq!(
+            UartClock::<0>::is_clock_enabled(glb.registers()),
+            Some(true)
+        );
+        let _ = serial.free_gated::<0>(glb.registers());
+        assert_eq!(
+            UartClock::<0>::is_clock_enabled(glb.registers()),
+            Some(false)
+        );
+
+        // Baudrate below the divider range leaves the clock gate disabled.
+        let config = Config::default().set_baudrate(Baud(1_000));
+        let serial = BlockingSerial::freerun_gated::<0>(
+            mock.registers(),
+            config,
+            MockPads,
+            &clocks,
+            glb.registers(),
+        );
+        assert!(serial.is_err());
+        assert_eq!(
+            UartClock::<0>::is_clock_enabled(glb.registers()),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn pads_released_and_restored() {
+        let mock = MockUart::new();
+        mock.write_word(0x00, 0x0000_8f05);
+        mock.write_word(0x04, 0x0000_0701);
+        mock.write_word(0x84, 0x0000_0020);
+        let mut serial = BlockingSerial {
+            uart: mock.registers(),
+            pads: MockPads,
+            peeked: None,
+            in_break: false,
+        };
+        let ans = serial.with_pads_released(|pads| {
+            assert_eq!(mock.read_word(0x00), 0x0000_8f04);
+            assert_eq!(mock.read_word(0x04), 0x0000_0700);
+            (pads, 42)
+        });
+        assert_eq!(ans, 42);
+        assert_eq!(mock.read_word(0x00), 0x0000_8f05);
+        assert_eq!(mock.read_word(0x04), 0x0000_0701);
+    }
+
+    #[test]
+    fn freerun_7n1_configuration() {
+        let clocks = Clocks::new(Hertz(40_000_000));
+        let mock = MockUart::new();
+        let config = Config::default()
+            .set_baudrate(Baud(2_000_000))
+            .set_word_length(WordLength::Seven)
+            .set_parity(Parity::None);
+        let mut serial =
+            BlockingSerial::freerun::<0>(mock.registers(), config, MockPads, &clocks).unwrap();
+
+        // Word length field holds bits minus one, parity enable bit is clear, one stop bit.
+        let transmit_config = mock.read_word(0x00);
+        assert_eq!((transmit_config >> 8) & 0x7, 6);
+        assert_eq!(transmit_config & (1 << 4), 0);
+        assert_eq!((transmit_config >> 11) & 0x3, 1);
+        let receive_config = mock.read_word(0x04);
+        assert_eq!((receive_config >> 8) & 0x7, 6);
+        assert_eq!(receive_config & (1 << 4), 0);
+
+        // A spurious eighth bit in receive queue entry is not reported.
+        mock.write_word(0x84, 0x0000_0100);
+        mock.write_word(0x8c, 0xd5);
+        let mut buf = [0u8; 1];
+        assert_eq!(serial.read_frame_7bit(&mut buf).unwrap(), 1);
+        assert_eq!(buf, [0x55]);
+    }
+
+    #[test]
+    fn borrowed_session() {
+        let clocks = Clocks::new(Hertz(40_000_000));
+        let mock = MockUart::new();
+        let uart = mock.registers();
+        mock.write_word(0x84, 0x0000_0020);
+        {
+            let mut serial =
+                BlockingSerial::borrow::<0, _>(&uart, Config::default(), MockPads, &clocks)
+                    .unwrap();
+            serial.write_frame_7bit(b"a");
+        }
+        // Configuration stays after the session ends, and the owner can use it again.
+        assert_eq!(mock.read_word(0x88) as u8, b'a');
+        assert!(uart.transmit_config.read().is_freerun_enabled());
+    }
+
+    #[test]
+    fn new_lin_registers() {
+        let clocks = Clocks::new(Hertz(40_000_000));
+        let mock = MockUart::new();
+        let config = Config::default().set_baudrate(Baud(19_200));
+        let lin = LinConfig {
+            break_bits: 5,
+            ..LinConfig::default()
+        };
+        let _serial =
+            BlockingSerial::new_lin::<0>(mock.registers(), config, lin, MockPads, &clocks).unwrap();
+        let transmit_config = mock.read_word(0x00);
+        assert_ne!(transmit_config & (1 << 3), 0);
+        assert_eq!((transmit_config >> 13) & 0x7, 5);
+        assert_ne!(mock.read_word(0x04) & (1 << 3), 0);
+
+        let lin = LinConfig {
+            break_bits: 8,
+            ..LinConfig::default()
+        };
+        assert!(matches!(
+            BlockingSerial::new_lin::<0>(mock.registers(), config, lin, MockPads, &clocks),
+            Err(ConfigError::LinBreakBitsTooMany)
+        ));
+    }
+
+    #[test]
+    fn config_read_back() {
+        let clocks = Clocks::new(Hertz(40_000_000));
+        let mock = MockUart::new();
+        let config = Config {
+            transmit_baudrate: Baud(2_000_000),
+            receive_baudrate: Baud(1_000_000),
+            bit_order: BitOrder::MsbFirst,
+            transmit_parity: Parity::Even,
+            receive_parity: Parity::Odd,
+            stop_bits: StopBits::Two,
+            transmit_word_length: WordLength::Seven,
+            receive_word_length: WordLength::Six,
+            receive_deglitch: Deglitch::Cycles(3),
+        };
+        let serial =
+            BlockingSerial::freerun::<0>(mock.registers(), config, MockPads, &clocks).unwrap();
+        assert_eq!(serial.config::<0>(&clocks), Ok(config));
+
+        // Rate produced by the divider is reported rather than the requested one.
+        let config = Config::default();
+        let serial =
+            BlockingSerial::freerun::<0>(mock.registers(), config, MockPads, &clocks).unwrap();
+        let read_back = serial.config::<0>(&clocks).unwrap();
+        assert_eq!(read_back.transmit_baudrate, Baud(115_274));
+        assert_eq!(read_back.set_baudrate(Baud(115_200)), config);
+    }
+
+    #[test]
+    fn new_checked_baud_error() {
+        let clocks = Clocks::new(Hertz(40_000_000));
+        let mock = MockUart::new();
+        // 80 MHz / 27 = 2962963 Bd, 1.23% slower than 3 Mbaud.
+        let config = Config::default().set_baudrate(Baud(3_000_000));
+        let (_serial, baud) =
+            BlockingSerial::new_checked::<0>(mock.registers(), config, MockPads, &clocks, 20_000)
+                .unwrap();
+        assert_eq!(baud, Baud(2_962_963));
+        assert_eq!(mock.read_word(0x08), 0x001b_001b);
+
+        let mock = MockUart::new();
+        let res =
+            BlockingSerial::new_checked::<0>(mock.registers(), config, MockPads, &clocks, 10_000);
+        assert!(matches!(res, Err(ConfigError::BaudrateErrorTooLarge)));
+        assert_eq!(mock.read_word(0x08), 0);
+
+        let config = config.set_baudrate(Baud(1_000));
+        let res =
+            BlockingSerial::new_checked::<0>(mock.registers(), config, MockPads, &clocks, 10_000);
+        assert!(matches!(res, Err(ConfigError::TransmitBaudrateTooLow)));
     }
 }
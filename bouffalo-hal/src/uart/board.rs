@@ -0,0 +1,41 @@
+use super::{BlockingSerial, Config, ConfigError, MuxRts, MuxRxd, MuxTxd, RegisterBlock, UartMux};
+use crate::clocks::Clocks;
+use crate::gpio::{Alternate, Disabled, IntoPadv2, Uart};
+use core::ops::Deref;
+
+/// Console pads of Sipeed M1s Dock.
+///
+/// GPIO14 transmits and GPIO15 receives through UART signals 2 and 3, which are wired
+/// to the on-board USB to serial converter.
+pub type ConsolePads<'a> = (
+    (Alternate<'a, 14, Uart>, UartMux<'a, 2, MuxTxd<0>>),
+    (Alternate<'a, 15, Uart>, UartMux<'a, 3, MuxRxd<0>>),
+);
+
+impl<'a, UART: Deref<Target = RegisterBlock>> BlockingSerial<UART, ConsolePads<'a>> {
+    /// Create serial console on standard pads of current board at 115200 8N1.
+    ///
+    /// It configures pads and signal multiplexers for UART0; use [`BlockingSerial::freerun`]
+    /// for other pads or settings.
+    ///
+    /// ```ignore
+    /// let mut serial = BlockingSerial::console_default(
+    ///     p.uart0,
+    ///     (p.gpio.io14, p.gpio.io15),
+    ///     (p.uart_muxes.sig2, p.uart_muxes.sig3),
+    ///     &c,
+    /// )
+    /// .unwrap();
+    /// ```
+    #[inline]
+    pub fn console_default(
+        uart: UART,
+        pads: (Disabled<'a, 14>, Disabled<'a, 15>),
+        muxes: (UartMux<'a, 2, MuxRts<0>>, UartMux<'a, 3, MuxRts<0>>),
+        clocks: &Clocks,
+    ) -> Result<Self, ConfigError> {
+        let tx = (pads.0.into_uart(), muxes.0.into_transmit::<0>());
+        let rx = (pads.1.into_uart(), muxes.1.into_receive::<0>());
+        BlockingSerial::freerun::<0>(uart, Config::default(), (tx, rx), clocks)
+    }
+}
@@ -0,0 +1,57 @@
+/// Checksum byte appended to a framed payload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChecksumKind {
+    /// Exclusive or of all payload bytes.
+    Xor,
+    /// Sum of all payload bytes, wrapping at 8 bits.
+    Sum,
+    /// CRC-8 with polynomial 0x07, zero initial value and no reflection.
+    Crc8,
+}
+
+impl ChecksumKind {
+    /// Compute checksum byte of `data`.
+    #[inline]
+    pub const fn compute(self, data: &[u8]) -> u8 {
+        let mut ans = 0u8;
+        let mut i = 0;
+        while i < data.len() {
+            ans = match self {
+                ChecksumKind::Xor => ans ^ data[i],
+                ChecksumKind::Sum => ans.wrapping_add(data[i]),
+                ChecksumKind::Crc8 => crc8_update(ans, data[i]),
+            };
+            i += 1;
+        }
+        ans
+    }
+}
+
+#[inline]
+const fn crc8_update(crc: u8, byte: u8) -> u8 {
+    let mut crc = crc ^ byte;
+    let mut bit = 0;
+    while bit < 8 {
+        crc = if crc & 0x80 != 0 {
+            (crc << 1) ^ 0x07
+        } else {
+            crc << 1
+        };
+        bit += 1;
+    }
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChecksumKind;
+
+    #[test]
+    fn checksum_kinds() {
+        assert_eq!(ChecksumKind::Xor.compute(&[0x12, 0x34, 0x56]), 0x70);
+        assert_eq!(ChecksumKind::Sum.compute(&[0x80, 0x90, 0x01]), 0x11);
+        // Check value of CRC-8/SMBUS.
+        assert_eq!(ChecksumKind::Crc8.compute(b"123456789"), 0xf4);
+        assert_eq!(ChecksumKind::Crc8.compute(&[]), 0x00);
+    }
+}
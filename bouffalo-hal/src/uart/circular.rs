@@ -0,0 +1,89 @@
+use super::RegisterBlock;
+use crate::dma::{self, Dma, LliPool, LliTransfer};
+use core::ops::Deref;
+
+/// Continuous serial receive into a ring buffer using circular DMA transfer.
+///
+/// Hardware writes received bytes into the buffer and wraps around at its end without
+/// processor intervention. Consume bytes behind the DMA write position with `read`;
+/// data is overwritten if it is not read before the buffer wraps around once more.
+pub struct CircularReceive<DMA: Deref<Target = dma::RegisterBlock>> {
+    channel: Dma<DMA>,
+    lli_pool: &'static mut [LliPool],
+    buf: &'static mut [u8],
+    read_position: usize,
+}
+
+impl<DMA: Deref<Target = dma::RegisterBlock>> CircularReceive<DMA> {
+    #[inline]
+    pub(crate) fn start(
+        uart: &RegisterBlock,
+        channel: Dma<DMA>,
+        lli_pool: &'static mut [LliPool],
+        buf: &'static mut [u8],
+    ) -> Self {
+        let mut transfer = [LliTransfer {
+            src_addr: &uart.fifo_read as *const _ as u32,
+            dst_addr: buf.as_mut_ptr() as u32,
+            nbytes: buf.len() as u32,
+        }];
+        let max_lli_count = lli_pool.len() as u32;
+        let count = channel.lli_reload(lli_pool, max_lli_count, &mut transfer, 1);
+        assert!(
+            count > 0,
+            "linked list item pool too small for receive buffer"
+        );
+        channel.lli_circular(lli_pool, count as usize);
+        // Request DMA on every received byte, so no data is left in queue.
+        unsafe {
+            uart.fifo_config_1
+                .modify(|val| val.set_receive_threshold(0));
+            uart.fifo_config_0
+                .modify(|val| val.enable_receive_dma().clear_receive_fifo());
+        }
+        channel.start();
+        Self {
+            channel,
+            lli_pool,
+            buf,
+            read_position: 0,
+        }
+    }
+    /// Position in buffer where DMA writes the next received byte.
+    #[inline]
+    pub fn write_position(&self) -> usize {
+        let offset = self
+            .channel
+            .destination_address()
+            .wrapping_sub(self.buf.as_ptr() as u32) as usize;
+        offset % self.buf.len()
+    }
+    /// Position in buffer of the next byte to be read.
+    #[inline]
+    pub fn read_position(&self) -> usize {
+        self.read_position
+    }
+    /// Number of received bytes not yet read.
+    #[inline]
+    pub fn available(&self) -> usize {
+        let len = self.buf.len();
+        (self.write_position() + len - self.read_position) % len
+    }
+    /// Copy received bytes into `out`, returning number of bytes copied.
+    #[inline]
+    pub fn read(&mut self, out: &mut [u8]) -> usize {
+        let count = self.available().min(out.len());
+        for byte in out[..count].iter_mut() {
+            // Buffer is written by hardware behind the compiler's back.
+            *byte = unsafe { core::ptr::read_volatile(&self.buf[self.read_position]) };
+            self.read_position = (self.read_position + 1) % self.buf.len();
+        }
+        count
+    }
+    /// Stop receiving and return DMA channel, linked list item pool and buffer.
+    #[inline]
+    pub fn free(self) -> (Dma<DMA>, &'static mut [LliPool], &'static mut [u8]) {
+        self.channel.stop();
+        (self.channel, self.lli_pool, self.buf)
+    }
+}
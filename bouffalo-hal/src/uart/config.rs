@@ -1,13 +1,24 @@
-use super::{BitPeriod, DataConfig, Pads, ReceiveConfig, TransmitConfig};
+use super::{BitPeriod, DataConfig, Pads, ReceiveConfig, RegisterBlock, TransmitConfig};
 use crate::clocks::Clocks;
-use embedded_time::rate::{Baud, Extensions};
+use embedded_time::rate::{Baud, Extensions, Hertz};
 
 /// Serial configuration.
+///
+/// # Sampling
+///
+/// The peripheral has no oversampling ratio or sampling point control. Bit period is
+/// counted directly in UART clock cycles, and the receiver always samples in the middle
+/// of each bit period, so resolution of the divider is one clock cycle rather than
+/// 1/16 of a bit. To follow a peer whose clock is off, set `receive_baudrate` to the
+/// rate the peer actually sends at, independently of `transmit_baudrate`; use
+/// `receive_deglitch` if the line also carries short spikes.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Config {
     /// Baudrate on the transmit half.
     pub transmit_baudrate: Baud,
     /// Baudrate on the receive half.
+    ///
+    /// It can differ from the transmit baudrate, e.g. to compensate clock offset of the peer.
     pub receive_baudrate: Baud,
     /// Data bit order.
     pub bit_order: BitOrder,
@@ -21,6 +32,8 @@ pub struct Config {
     pub transmit_word_length: WordLength,
     /// Data word length on the receive half.
     pub receive_word_length: WordLength,
+    /// Glitch filter on the receive line.
+    pub receive_deglitch: Deglitch,
 }
 
 impl Config {
@@ -53,6 +66,17 @@ impl Config {
             ..self
         }
     }
+    /// Set glitch filter width on the receive line in peripheral clock cycles.
+    ///
+    /// Pulses shorter than the filter width are ignored, which reduces framing errors
+    /// on noisy lines at high baudrates.
+    #[inline]
+    pub const fn set_receive_deglitch(self, cycles: u8) -> Self {
+        Self {
+            receive_deglitch: Deglitch::Cycles(cycles),
+            ..self
+        }
+    }
     #[inline]
     fn into_registers(self) -> (DataConfig, TransmitConfig, ReceiveConfig) {
         let data_config = DataConfig::default().set_bit_order(self.bit_order);
@@ -60,9 +84,12 @@ impl Config {
             .set_parity(self.transmit_parity)
             .set_stop_bits(self.stop_bits)
             .set_word_length(self.transmit_word_length);
-        let receive_config = ReceiveConfig::default()
+        let mut receive_config = ReceiveConfig::default()
             .set_parity(self.receive_parity)
             .set_word_length(self.receive_word_length);
+        if let Deglitch::Cycles(cycles) = self.receive_deglitch {
+            receive_config = receive_config.enable_deglitch().set_deglitch_cycles(cycles);
+        }
         (data_config, transmit_config, receive_config)
     }
 }
@@ -80,6 +107,7 @@ impl Default for Config {
             stop_bits: StopBits::One,
             transmit_word_length: WordLength::Eight,
             receive_word_length: WordLength::Eight,
+            receive_deglitch: Deglitch::Disabled,
         }
     }
 }
@@ -89,25 +117,29 @@ pub(crate) fn uart_config<const I: usize, PADS: Pads<I>>(
     config: Config,
     clocks: &Clocks,
 ) -> Result<(BitPeriod, DataConfig, TransmitConfig, ReceiveConfig), ConfigError> {
-    let uart_clock = match clocks.uart_clock::<I>() {
-        Some(freq) => freq,
-        None => return Err(ConfigError::ClockSource),
-    };
-    let transmit_interval = uart_clock.0 / config.transmit_baudrate.0;
-    let receive_interval = uart_clock.0 / config.receive_baudrate.0;
-    if transmit_interval > 65535 {
-        return Err(ConfigError::TransmitBaudrateTooLow);
-    } else if transmit_interval < 1 {
-        return Err(ConfigError::TransmitBaudrateTooHigh);
-    }
-    if receive_interval > 65535 {
-        return Err(ConfigError::ReceiveBaudrateTooLow);
-    } else if receive_interval < 1 {
-        return Err(ConfigError::ReceiveBaudrateTooHigh);
+    let transmit_interval = clocks
+        .baud_interval::<I>(config.transmit_baudrate)
+        .map_err(|e| match e {
+            ConfigError::BaudrateTooHigh => ConfigError::TransmitBaudrateTooHigh,
+            ConfigError::BaudrateTooLow => ConfigError::TransmitBaudrateTooLow,
+            e => e,
+        })?;
+    let receive_interval =
+        clocks
+            .baud_interval::<I>(config.receive_baudrate)
+            .map_err(|e| match e {
+                ConfigError::BaudrateTooHigh => ConfigError::ReceiveBaudrateTooHigh,
+                ConfigError::BaudrateTooLow => ConfigError::ReceiveBaudrateTooLow,
+                e => e,
+            })?;
+    if let Deglitch::Cycles(cycles) = config.receive_deglitch
+        && cycles > Deglitch::MAX_CYCLES
+    {
+        return Err(ConfigError::DeglitchCyclesTooMany);
     }
     let bit_period = BitPeriod::default()
-        .set_transmit_time_interval(transmit_interval as u16)
-        .set_receive_time_interval(receive_interval as u16);
+        .set_transmit_time_interval(transmit_interval)
+        .set_receive_time_interval(receive_interval);
     let (data_config, mut transmit_config, mut receive_config) = config.into_registers();
     if PADS::TXD {
         transmit_config = transmit_config.enable_txd();
@@ -121,6 +153,33 @@ pub(crate) fn uart_config<const I: usize, PADS: Pads<I>>(
     Ok((bit_period, data_config, transmit_config, receive_config))
 }
 
+#[inline]
+pub(crate) fn uart_read_config(uart: &RegisterBlock, uart_clock: Hertz) -> Config {
+    let transmit_config = uart.transmit_config.read();
+    let receive_config = uart.receive_config.read();
+    let bit_period = uart.bit_period.read();
+    let baud = |interval: u16| {
+        let interval = core::cmp::max(interval, 1) as u32;
+        Baud((uart_clock.0 + interval / 2) / interval)
+    };
+    let receive_deglitch = if receive_config.is_deglitch_enabled() {
+        Deglitch::Cycles(receive_config.deglitch_cycles())
+    } else {
+        Deglitch::Disabled
+    };
+    Config {
+        transmit_baudrate: baud(bit_period.transmit_time_interval()),
+        receive_baudrate: baud(bit_period.receive_time_interval()),
+        bit_order: uart.data_config.read().bit_order(),
+        transmit_parity: transmit_config.parity(),
+        receive_parity: receive_config.parity(),
+        stop_bits: transmit_config.stop_bits(),
+        transmit_word_length: transmit_config.word_length(),
+        receive_word_length: receive_config.word_length(),
+        receive_deglitch,
+    }
+}
+
 /// Errors on serial configuration.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ConfigError {
@@ -132,8 +191,31 @@ pub enum ConfigError {
     ReceiveBaudrateTooHigh,
     /// Impossibly low baudrate for current bus clock frequency.
     ReceiveBaudrateTooLow,
+    /// Impossibly high baudrate for current bus clock frequency, on either half.
+    BaudrateTooHigh,
+    /// Impossibly low baudrate for current bus clock frequency, on either half.
+    BaudrateTooLow,
     /// Clock source unavailable.
     ClockSource,
+    /// Receive glitch filter width exceeds hardware limit.
+    DeglitchCyclesTooMany,
+    /// Transmitted LIN break length exceeds hardware limit.
+    LinBreakBitsTooMany,
+    /// Achievable baudrate deviates from requested more than allowed, on either half.
+    BaudrateErrorTooLarge,
+}
+
+/// Glitch filter on the receive line.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Deglitch {
+    /// Filter disabled, every edge on the line is sampled.
+    Disabled,
+    /// Ignore pulses shorter than given peripheral clock cycles, up to 15 cycles.
+    Cycles(u8),
+}
+
+impl Deglitch {
+    const MAX_CYCLES: u8 = 15;
 }
 
 /// Order of the bits transmitted and received on the wire.
@@ -177,6 +259,10 @@ pub enum WordLength {
     /// Six bits per word.
     Six,
     /// Seven bits per word.
+    ///
+    /// Combined with [`Parity::None`] this forms the 7N1 frame of legacy terminals. Receive
+    /// queue entries are still 8 bits wide; use `read_frame_7bit` on the serial to get bytes
+    /// with the most significant bit cleared.
     Seven,
     /// Eight bits per word.
     Eight,
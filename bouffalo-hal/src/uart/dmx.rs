@@ -0,0 +1,120 @@
+use super::{
+    BlockingSerial, Config, ConfigError, Pads, RegisterBlock, StopBits, uart_wait_transmit_idle,
+    uart_write_iter,
+};
+use crate::clocks::Clocks;
+use core::ops::Deref;
+use embedded_time::rate::Baud;
+
+/// Number of channel slots in a DMX512 universe.
+pub const DMX_SLOTS: usize = 512;
+
+/// Length of break in microseconds, above 88µs minimum of DMX512 specification.
+const BREAK_MICROS: u64 = 92;
+
+/// DMX512 transmitter for stage lighting control.
+///
+/// Serial line runs at 250 kbaud, 8 data bits, no parity and 2 stop bits. Each packet
+/// starts with a break, sent as one zero byte at a lower baudrate so that the line is held
+/// low for at least 88µs; its two stop bits form the mark-after-break of about 20µs.
+/// Break and slot timings are derived from UART clock frequency.
+pub struct Dmx<UART, PADS> {
+    uart: UART,
+    pads: PADS,
+    slot_interval: u16,
+    break_interval: u16,
+}
+
+impl<UART: Deref<Target = RegisterBlock>, PADS> Dmx<UART, PADS> {
+    /// Creates a DMX512 transmitter on UART peripheral `I`.
+    #[inline]
+    pub fn new<const I: usize>(uart: UART, pads: PADS, clocks: &Clocks) -> Result<Self, ConfigError>
+    where
+        PADS: Pads<I>,
+    {
+        let uart_clock = match clocks.uart_clock::<I>() {
+            Some(freq) => freq,
+            None => return Err(ConfigError::ClockSource),
+        };
+        // Break consists of start bit and 8 zero data bits.
+        let break_interval = (uart_clock.0 as u64 * BREAK_MICROS).div_ceil(9 * 1_000_000);
+        if break_interval > 65535 {
+            return Err(ConfigError::BaudrateTooLow);
+        }
+        let config = Config {
+            stop_bits: StopBits::Two,
+            ..Config::default().set_baudrate(Baud(250_000))
+        };
+        let (uart, pads) = BlockingSerial::freerun(uart, config, pads, clocks)?.free();
+        let slot_interval = uart.bit_period.read().transmit_time_interval();
+        Ok(Self {
+            uart,
+            pads,
+            slot_interval,
+            break_interval: break_interval as u16,
+        })
+    }
+    /// Send one packet with null start code followed by all channel slots.
+    ///
+    /// Blocks until the last slot is queued for transmission.
+    #[inline]
+    pub fn send_universe(&mut self, slots: &[u8; DMX_SLOTS]) {
+        self.send_break();
+        uart_write_iter(
+            &self.uart,
+            core::iter::once(0x00).chain(slots.iter().copied()),
+        );
+    }
+    /// Send break and mark-after-break, blocking until they have left the bus.
+    #[inline]
+    fn send_break(&mut self) {
+        uart_wait_transmit_idle(&self.uart);
+        let bit_period = self.uart.bit_period.read();
+        unsafe {
+            self.uart
+                .bit_period
+                .write(bit_period.set_transmit_time_interval(self.break_interval));
+            self.uart.fifo_write.write(0x00);
+        }
+        uart_wait_transmit_idle(&self.uart);
+        unsafe {
+            self.uart
+                .bit_period
+                .write(bit_period.set_transmit_time_interval(self.slot_interval))
+        };
+    }
+    /// Release the UART peripheral and pads.
+    #[inline]
+    pub fn free(self) -> (UART, PADS) {
+        (self.uart, self.pads)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DMX_SLOTS, Dmx};
+    use crate::clocks::Clocks;
+    use crate::uart::{MockPads, MockUart, StopBits};
+    use embedded_time::rate::Hertz;
+
+    #[test]
+    fn dmx_send_universe() {
+        let clocks = Clocks::new(Hertz(40_000_000));
+        let mock = MockUart::new();
+        let mut dmx = Dmx::new::<0>(mock.registers(), MockPads, &clocks).unwrap();
+        // UART clock runs at 80MHz.
+        assert_eq!(dmx.slot_interval, 320);
+        // 92µs break is 7360 cycles over 9 bits.
+        assert_eq!(dmx.break_interval, 818);
+        let uart = mock.registers();
+        assert_eq!(uart.transmit_config.read().stop_bits(), StopBits::Two);
+
+        // Transmit queue empty and bus idle.
+        mock.write_word(0x84, 32);
+        let mut slots = [0u8; DMX_SLOTS];
+        slots[DMX_SLOTS - 1] = 0xff;
+        dmx.send_universe(&slots);
+        assert_eq!(mock.read_word(0x88) as u8, 0xff);
+        assert_eq!(uart.bit_period.read().transmit_time_interval(), 320);
+    }
+}
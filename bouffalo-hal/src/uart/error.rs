@@ -10,6 +10,16 @@ pub enum Error {
     Overrun,
     /// Parity check error.
     Parity,
+    /// Byte sensed on a shared bus differs from the byte transmitted, as another node was transmitting.
+    Collision,
+    /// Buffer is full before the expected delimiter is received.
+    BufferFull,
+    /// Received checksum byte does not match the payload.
+    Checksum,
+    /// Transmit queue overflowed, as more bytes were written than it can hold.
+    FifoOverflow,
+    /// Transmit or receive queue underflowed, e.g. DMA read from an empty receive queue.
+    FifoUnderflow,
 }
 
 impl embedded_io::Error for Error {
@@ -27,6 +37,11 @@ impl embedded_hal_nb::serial::Error for Error {
             Error::Noise => embedded_hal_nb::serial::ErrorKind::Noise,
             Error::Overrun => embedded_hal_nb::serial::ErrorKind::Overrun,
             Error::Parity => embedded_hal_nb::serial::ErrorKind::Parity,
+            Error::Collision => embedded_hal_nb::serial::ErrorKind::Other,
+            Error::BufferFull => embedded_hal_nb::serial::ErrorKind::Other,
+            Error::Checksum => embedded_hal_nb::serial::ErrorKind::Other,
+            Error::FifoOverflow => embedded_hal_nb::serial::ErrorKind::Overrun,
+            Error::FifoUnderflow => embedded_hal_nb::serial::ErrorKind::Other,
         }
     }
 }
@@ -0,0 +1,141 @@
+use super::{ByteWriter, Error};
+use core::fmt;
+
+/// Fixed size stack buffer for formatted output, flushed to a serial in one pass.
+///
+/// Formatting directly on a serial writes transmit queue once for every string piece;
+/// this buffer collects the whole line first and fills transmit queue as fast as it drains.
+///
+/// ```ignore
+/// let mut buf = FmtBuffer::<64>::new();
+/// write!(buf, "temperature: {} C\r\n", temp).ok();
+/// buf.flush_to(&mut serial)?;
+/// ```
+///
+/// Output longer than `N` bytes is truncated at a character boundary, in which case
+/// formatting returns `fmt::Error` and [`is_truncated`](Self::is_truncated) returns `true`.
+#[derive(Clone, Debug)]
+pub struct FmtBuffer<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+    truncated: bool,
+}
+
+impl<const N: usize> FmtBuffer<N> {
+    /// Creates an empty buffer.
+    #[inline]
+    pub const fn new() -> Self {
+        FmtBuffer {
+            buf: [0; N],
+            len: 0,
+            truncated: false,
+        }
+    }
+    /// Get bytes formatted so far.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+    /// Number of bytes formatted so far.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+    /// Check if nothing has been formatted.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Check if output has been truncated since last flush or clear.
+    #[inline]
+    pub const fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+    /// Discard contents of the buffer and clear truncated flag.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.truncated = false;
+    }
+    /// Transmit contents of the buffer on `serial`, then clear the buffer.
+    ///
+    /// Contents are kept if `serial` returns an error.
+    #[inline]
+    pub fn flush_to<W: ByteWriter + ?Sized>(&mut self, serial: &mut W) -> Result<(), Error> {
+        serial.write_all(self.as_bytes())?;
+        self.clear();
+        Ok(())
+    }
+}
+
+impl<const N: usize> Default for FmtBuffer<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> fmt::Write for FmtBuffer<N> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let free = N - self.len;
+        let mut end = core::cmp::min(free, s.len());
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.buf[self.len..self.len + end].copy_from_slice(&s.as_bytes()[..end]);
+        self.len += end;
+        if end < s.len() {
+            self.truncated = true;
+            return Err(fmt::Error);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FmtBuffer;
+    use crate::uart::{ByteWriter, Error};
+    use core::fmt::Write;
+
+    struct MockWriter {
+        buf: [u8; 16],
+        len: usize,
+        calls: usize,
+    }
+
+    impl ByteWriter for MockWriter {
+        fn write_all(&mut self, buf: &[u8]) -> Result<(), Error> {
+            self.buf[self.len..self.len + buf.len()].copy_from_slice(buf);
+            self.len += buf.len();
+            self.calls += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn fmt_buffer_flush_and_truncate() {
+        let mut serial = MockWriter {
+            buf: [0; 16],
+            len: 0,
+            calls: 0,
+        };
+        let mut buf = FmtBuffer::<8>::new();
+        write!(buf, "{}+{}={}", 1, 2, 3).unwrap();
+        assert_eq!(buf.as_bytes(), b"1+2=3");
+        buf.flush_to(&mut serial).unwrap();
+        assert!(buf.is_empty());
+        assert_eq!(serial.calls, 1);
+        assert_eq!(&serial.buf[..serial.len], b"1+2=3");
+
+        let tail = "é€";
+        assert!(write!(buf, "abcdef{tail}").is_err());
+        assert!(buf.is_truncated());
+        assert_eq!(buf.as_bytes(), "abcdefé".as_bytes());
+        let logger: &mut dyn ByteWriter = &mut serial;
+        buf.flush_to(logger).unwrap();
+        assert!(!buf.is_truncated());
+        assert_eq!(serial.len, 13);
+    }
+}
@@ -0,0 +1,77 @@
+/// Local Interconnect Network (LIN) node configuration.
+///
+/// Combine it with a [`Config`](super::Config) holding the bus baudrate, and pass both
+/// to [`BlockingSerial::new_lin`](super::BlockingSerial::new_lin).
+///
+/// Receive break detection has no length threshold to configure, as it is fixed by
+/// hardware; breaks of at least 13 bits sent by a master are detected, so no
+/// `break_detect_bits` field is provided.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LinConfig {
+    /// Transmitted break length setting, 0 ~ 7, see `TransmitConfig::set_lin_break_bits`.
+    pub break_bits: u8,
+    /// Checksum model of frames on this bus.
+    pub checksum: LinChecksum,
+}
+
+impl LinConfig {
+    /// Largest value of `break_bits` supported by hardware.
+    pub const MAX_BREAK_BITS: u8 = 7;
+}
+
+impl Default for LinConfig {
+    /// LIN configuration defaults to enhanced checksum with shortest transmitted break.
+    #[inline]
+    fn default() -> Self {
+        LinConfig {
+            break_bits: 0,
+            checksum: LinChecksum::Enhanced,
+        }
+    }
+}
+
+/// Checksum model of LIN frames.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LinChecksum {
+    /// Checksum over data bytes only, as of LIN 1.x.
+    Classic,
+    /// Checksum over protected identifier and data bytes, as of LIN 2.x.
+    Enhanced,
+}
+
+impl LinChecksum {
+    /// Compute checksum byte of a frame with protected identifier `pid` and `data`.
+    ///
+    /// It is the inverted sum of bytes, where every carry is added back to the sum.
+    #[inline]
+    pub const fn compute(self, pid: u8, data: &[u8]) -> u8 {
+        let mut sum = match self {
+            LinChecksum::Classic => 0u16,
+            LinChecksum::Enhanced => pid as u16,
+        };
+        let mut i = 0;
+        while i < data.len() {
+            sum += data[i] as u16;
+            if sum > 0xff {
+                sum -= 0xff;
+            }
+            i += 1;
+        }
+        !(sum as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LinChecksum;
+
+    #[test]
+    fn lin_checksum_models() {
+        // Example frame of LIN specification.
+        let data = [0x4a, 0x55, 0x93, 0xe5];
+        assert_eq!(LinChecksum::Classic.compute(0x00, &data), 0xe6);
+        assert_eq!(LinChecksum::Enhanced.compute(0x00, &data), 0xe6);
+        assert_eq!(LinChecksum::Enhanced.compute(0x80, &data), 0x66);
+        assert_eq!(LinChecksum::Classic.compute(0x80, &data), 0xe6);
+    }
+}
@@ -0,0 +1,75 @@
+use super::{BlockingReceiveHalf, BlockingTransmitHalf, Pads, RegisterBlock};
+use core::cell::UnsafeCell;
+
+const WORDS: usize = size_of::<RegisterBlock>() / 4;
+
+/// UART register block backed by plain memory, for testing driver logic on host.
+///
+/// Registers read back the value last written. Hardware behaviors, such as shifting bytes
+/// through queues or updating state registers, do not happen; prepare them with `write_word`.
+#[repr(C, align(4))]
+pub struct MockUart {
+    memory: UnsafeCell<[u32; WORDS]>,
+}
+
+impl MockUart {
+    /// Create a register block with all registers cleared to zero.
+    #[inline]
+    pub const fn new() -> Self {
+        Self::filled(0)
+    }
+    /// Create a register block with every word set to `value`.
+    #[inline]
+    pub const fn filled(value: u32) -> Self {
+        Self {
+            memory: UnsafeCell::new([value; WORDS]),
+        }
+    }
+    /// Get register block to pass into serial constructors.
+    #[inline]
+    pub fn registers(&self) -> &RegisterBlock {
+        unsafe { &*(self.memory.get() as *const RegisterBlock) }
+    }
+    /// Read raw word at byte `offset` from start of register block.
+    #[inline]
+    pub fn read_word(&self, offset: usize) -> u32 {
+        unsafe { (*self.memory.get())[offset / 4] }
+    }
+    /// Write raw word at byte `offset`, including read-only registers.
+    #[inline]
+    pub fn write_word(&self, offset: usize, value: u32) {
+        unsafe { (*self.memory.get())[offset / 4] = value }
+    }
+}
+
+impl Default for MockUart {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Pads with every UART signal connected, for use with [`MockUart`].
+pub struct MockPads;
+
+impl<const U: usize> Pads<U> for MockPads {
+    const RTS: bool = true;
+    const CTS: bool = true;
+    const TXD: bool = true;
+    const RXD: bool = true;
+    type Split<T> = (BlockingTransmitHalf<T, ()>, BlockingReceiveHalf<T, ()>);
+    #[inline]
+    fn split<T>(self, uart: T) -> Self::Split<T> {
+        (
+            BlockingTransmitHalf {
+                uart: unsafe { core::ptr::read_volatile(&uart) },
+                _pads: (),
+            },
+            BlockingReceiveHalf {
+                uart,
+                _pads: (),
+                peeked: None,
+            },
+        )
+    }
+}
@@ -120,6 +120,54 @@ impl<'a, const N: usize, M> UartMux<'a, N, M> {
     }
 }
 
+impl<'a, const N: usize, M> UartMux<'a, N, M> {
+    /// Find another multiplexer routed to the same internal UART signal as this one.
+    ///
+    /// Returns signal number of that multiplexer, or `None` if there is no conflict.
+    #[inline]
+    pub fn conflict(&self) -> Option<usize> {
+        let signal = mux_signal(self.base, N);
+        (0..MUX_COUNT).find(|&n| n != N && mux_signal(self.base, n) == signal)
+    }
+}
+
+/// Two UART signal multiplexers routed to the same internal UART signal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MuxConflict {
+    /// Internal UART signal both multiplexers are routed to.
+    pub signal: UartSignal,
+    /// Signal numbers of the conflicting multiplexers, in ascending order.
+    pub muxes: (usize, usize),
+}
+
+/// Check all UART signal multiplexers for conflicting assignments.
+///
+/// Multiplexer types only track the signal each one is routed to, thus assigning one
+/// internal signal to two multiplexers is not rejected at compile time; hardware would
+/// connect one of them only. Call this after every UART in use is routed.
+///
+/// Multiplexers not taken by any UART keep their reset assignment, i.e. multiplexer `N`
+/// routed to signal number `N` in [`UartSignal`] order, and may conflict with a routed one.
+pub fn check_mux_conflicts(glb: &glb::v2::RegisterBlock) -> Result<(), MuxConflict> {
+    for first in 0..MUX_COUNT {
+        let signal = mux_signal(glb, first);
+        if let Some(second) = (first + 1..MUX_COUNT).find(|&n| mux_signal(glb, n) == signal) {
+            return Err(MuxConflict {
+                signal,
+                muxes: (first, second),
+            });
+        }
+    }
+    Ok(())
+}
+
+const MUX_COUNT: usize = 12;
+
+#[inline]
+fn mux_signal(glb: &glb::v2::RegisterBlock, n: usize) -> UartSignal {
+    glb.uart_mux_group[n >> 3].read().signal(n & 0x7)
+}
+
 /// Available UART signal multiplexers.
 pub struct UartMuxes<'a> {
     /// Multiplexer of UART signal 0.
@@ -180,3 +228,37 @@ impl<'a> UartMuxes<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{MuxConflict, UartMuxes, check_mux_conflicts};
+    use crate::glb::v2::{self, UartSignal};
+    use crate::mock::MockRegisters;
+
+    #[test]
+    fn mux_conflicts() {
+        let mock = MockRegisters::<v2::RegisterBlock>::new();
+        let glb = mock.registers();
+        // Reset assignment routes multiplexer N to signal N.
+        mock.write(|glb| &glb.uart_mux_group[0], 0x7654_3210);
+        mock.write(|glb| &glb.uart_mux_group[1], 0x0000_ba98);
+        assert_eq!(check_mux_conflicts(glb), Ok(()));
+
+        let muxes = UartMuxes::__uart_muxes_from_glb(glb);
+        let _tx = muxes.sig2.into_transmit::<0>();
+        assert_eq!(check_mux_conflicts(glb), Ok(()));
+        let rx = muxes.sig4.into_receive::<0>();
+        assert_eq!(rx.conflict(), Some(3));
+        assert_eq!(
+            check_mux_conflicts(glb),
+            Err(MuxConflict {
+                signal: UartSignal::Rxd0,
+                muxes: (3, 4),
+            })
+        );
+
+        let _rts = muxes.sig3.into_request_to_send::<1>();
+        assert_eq!(rx.conflict(), None);
+        assert_eq!(check_mux_conflicts(glb), Ok(()));
+    }
+}
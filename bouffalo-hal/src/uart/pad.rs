@@ -86,7 +86,11 @@ fn from_pads<T, TX, RX>(
             uart: unsafe { core::ptr::read_volatile(&uart) },
             _pads: tx,
         },
-        BlockingReceiveHalf { uart, _pads: rx },
+        BlockingReceiveHalf {
+            uart,
+            _pads: rx,
+            peeked: None,
+        },
     )
 }
 
@@ -109,6 +113,25 @@ where
     }
 }
 
+impl<'a, 'b, const I: usize, const U: usize, const N: usize> Pads<U>
+    for (Alternate<'a, N, Uart>, UartMux<'b, I, MuxRxd<U>>)
+where
+    Alternate<'a, N, Uart>: HasUartSignal<I>,
+{
+    const RTS: bool = false;
+    const CTS: bool = false;
+    const TXD: bool = false;
+    const RXD: bool = true;
+    type Split<T> = (
+        BlockingTransmitHalf<T, ()>,
+        BlockingReceiveHalf<T, (Alternate<'a, N, Uart>, UartMux<'b, I, MuxRxd<U>>)>,
+    );
+    #[inline]
+    fn split<T>(self, uart: T) -> Self::Split<T> {
+        from_pads(uart, (), self)
+    }
+}
+
 impl<
     'a,
     'b,
@@ -178,6 +201,91 @@ where
     }
 }
 
+impl<
+    'a,
+    'b,
+    'c,
+    'd,
+    const I1: usize,
+    const I2: usize,
+    const U: usize,
+    const N1: usize,
+    const N2: usize,
+> Pads<U>
+    for (
+        (Alternate<'a, N1, Uart>, UartMux<'b, I1, MuxRxd<U>>),
+        (Alternate<'c, N2, Uart>, UartMux<'d, I2, MuxRts<U>>),
+    )
+where
+    Alternate<'a, N1, Uart>: HasUartSignal<I1>,
+    Alternate<'c, N2, Uart>: HasUartSignal<I2>,
+{
+    const RTS: bool = true;
+    const CTS: bool = false;
+    const TXD: bool = false;
+    const RXD: bool = true;
+    type Split<T> = BlockingReceiveHalf<
+        T,
+        (
+            (Alternate<'a, N1, Uart>, UartMux<'b, I1, MuxRxd<U>>),
+            (Alternate<'c, N2, Uart>, UartMux<'d, I2, MuxRts<U>>),
+        ),
+    >;
+    #[inline]
+    fn split<T>(self, uart: T) -> Self::Split<T> {
+        BlockingReceiveHalf {
+            uart,
+            _pads: self,
+            peeked: None,
+        }
+    }
+}
+
+impl<
+    'a,
+    'b,
+    'c,
+    'd,
+    'e,
+    'f,
+    const I1: usize,
+    const I2: usize,
+    const I3: usize,
+    const U: usize,
+    const N1: usize,
+    const N2: usize,
+    const N3: usize,
+> Pads<U>
+    for (
+        (Alternate<'a, N1, Uart>, UartMux<'b, I1, MuxTxd<U>>),
+        (Alternate<'c, N2, Uart>, UartMux<'d, I2, MuxRxd<U>>),
+        (Alternate<'e, N3, Uart>, UartMux<'f, I3, MuxRts<U>>),
+    )
+where
+    Alternate<'a, N1, Uart>: HasUartSignal<I1>,
+    Alternate<'c, N2, Uart>: HasUartSignal<I2>,
+    Alternate<'e, N3, Uart>: HasUartSignal<I3>,
+{
+    const RTS: bool = true;
+    const CTS: bool = false;
+    const TXD: bool = true;
+    const RXD: bool = true;
+    type Split<T> = (
+        BlockingTransmitHalf<T, (Alternate<'a, N1, Uart>, UartMux<'b, I1, MuxTxd<U>>)>,
+        BlockingReceiveHalf<
+            T,
+            (
+                (Alternate<'c, N2, Uart>, UartMux<'d, I2, MuxRxd<U>>),
+                (Alternate<'e, N3, Uart>, UartMux<'f, I3, MuxRts<U>>),
+            ),
+        >,
+    );
+    #[inline]
+    fn split<T>(self, uart: T) -> Self::Split<T> {
+        from_pads(uart, self.0, (self.1, self.2))
+    }
+}
+
 impl<
     'a,
     'b,
@@ -0,0 +1,143 @@
+use super::{Error, Interrupt, RegisterBlock};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Interrupt driven transmission state, shared by a serial and its interrupt handler.
+///
+/// Place it in a `static`, start a transmission with [`BlockingSerial::start_transmit`] and call
+/// [`on_interrupt`](Self::on_interrupt) from the UART interrupt handler. Transmit queue is
+/// refilled on the transmit FIFO ready interrupt, without processor time spent waiting.
+///
+/// [`BlockingSerial::start_transmit`]: super::BlockingSerial::start_transmit
+#[derive(Debug)]
+pub struct TransmitQueue {
+    ref_to_serial: AtomicUsize,
+    buf: AtomicUsize,
+    len: AtomicUsize,
+    position: AtomicUsize,
+}
+
+impl TransmitQueue {
+    /// Creates an idle transmit queue.
+    #[inline]
+    pub const fn new() -> TransmitQueue {
+        TransmitQueue {
+            ref_to_serial: AtomicUsize::new(0),
+            buf: AtomicUsize::new(0),
+            len: AtomicUsize::new(0),
+            position: AtomicUsize::new(0),
+        }
+    }
+    /// Check if all bytes of the last transmission are written into transmit queue.
+    ///
+    /// Bytes may still be shifting out; see `is_transmit_done` on the serial to wait for the bus.
+    #[inline]
+    pub fn is_transmit_complete(&self) -> bool {
+        self.position.load(Ordering::Acquire) == self.len.load(Ordering::Acquire)
+    }
+    /// Use this transmit queue to handle interrupt.
+    ///
+    /// Returns `true` once when the last byte is written, so that a completion callback
+    /// can be invoked or a task woken up from the interrupt handler.
+    #[inline]
+    pub fn on_interrupt(&self) -> bool {
+        let ref_to_serial = self.ref_to_serial.load(Ordering::Acquire);
+        let len = self.len.load(Ordering::Acquire);
+        let position = self.position.load(Ordering::Acquire);
+        if ref_to_serial == 0 || position == len {
+            return false;
+        }
+        let uart = unsafe { &*(ref_to_serial as *const RegisterBlock) };
+        let buf = unsafe {
+            core::slice::from_raw_parts(self.buf.load(Ordering::Acquire) as *const u8, len)
+        };
+        let count = core::cmp::min(
+            uart.fifo_config_1.read().transmit_available_bytes() as usize,
+            len - position,
+        );
+        buf[position..position + count]
+            .iter()
+            .for_each(|&word| unsafe { uart.fifo_write.write(word) });
+        if position + count == len {
+            unsafe {
+                uart.interrupt_enable
+                    .modify(|val| val.disable_interrupt(Interrupt::TransmitFifoReady))
+            };
+        }
+        self.position.store(position + count, Ordering::Release);
+        position + count == len
+    }
+}
+
+impl Default for TransmitQueue {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[inline]
+pub(crate) fn uart_start_transmit(
+    uart: &RegisterBlock,
+    buf: &'static [u8],
+    queue: &'static TransmitQueue,
+) -> nb::Result<(), Error> {
+    if !queue.is_transmit_complete() {
+        return Err(nb::Error::WouldBlock);
+    }
+    // Interrupt is disabled while idle, so the handler does not race with these stores.
+    queue
+        .ref_to_serial
+        .store(uart as *const _ as usize, Ordering::Release);
+    queue.buf.store(buf.as_ptr() as usize, Ordering::Release);
+    queue.position.store(0, Ordering::Release);
+    queue.len.store(buf.len(), Ordering::Release);
+    if !buf.is_empty() {
+        unsafe {
+            uart.interrupt_enable
+                .modify(|val| val.enable_interrupt(Interrupt::TransmitFifoReady))
+        };
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TransmitQueue, uart_start_transmit};
+    use crate::uart::{Interrupt, MockUart};
+
+    #[test]
+    fn transmit_queue_on_interrupt() {
+        static QUEUE: TransmitQueue = TransmitQueue::new();
+        static DATA: [u8; 40] = [0x5a; 40];
+        let mock = MockUart::new();
+        let uart = mock.registers();
+        assert!(QUEUE.is_transmit_complete());
+        assert!(!QUEUE.on_interrupt());
+
+        uart_start_transmit(uart, &DATA, &QUEUE).unwrap();
+        assert!(!QUEUE.is_transmit_complete());
+        assert!(
+            uart.interrupt_enable
+                .read()
+                .is_interrupt_enabled(Interrupt::TransmitFifoReady)
+        );
+        assert!(matches!(
+            uart_start_transmit(uart, &DATA, &QUEUE),
+            Err(nb::Error::WouldBlock)
+        ));
+
+        mock.write_word(0x84, 0x0000_0020);
+        assert!(!QUEUE.on_interrupt());
+        assert!(!QUEUE.is_transmit_complete());
+        assert_eq!(mock.read_word(0x88) as u8, 0x5a);
+        assert!(QUEUE.on_interrupt());
+        assert!(QUEUE.is_transmit_complete());
+        assert!(
+            !uart
+                .interrupt_enable
+                .read()
+                .is_interrupt_enabled(Interrupt::TransmitFifoReady)
+        );
+        assert!(!QUEUE.on_interrupt());
+    }
+}
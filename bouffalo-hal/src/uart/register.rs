@@ -12,7 +12,10 @@ pub struct RegisterBlock {
     pub bit_period: RW<BitPeriod>,
     /// Data format configuration.
     pub data_config: RW<DataConfig>,
-    _reserved1: [u8; 0x10],
+    _reserved1: [u8; 0x8],
+    /// Receive timeout configuration.
+    pub receive_timeout: RW<ReceiveTimeout>,
+    _reserved2: [u8; 0x4],
     /// Interrupt state register.
     pub interrupt_state: RO<InterruptState>,
     /// Interrupt mask register.
@@ -23,15 +26,20 @@ pub struct RegisterBlock {
     pub interrupt_enable: RW<InterruptEnable>,
     /// Bus state.
     pub bus_state: RO<BusState>,
-    _reserved2: [u8; 0x4c],
+    _reserved3: [u8; 0x4c],
     /// First-in first-out queue configuration 0.
     pub fifo_config_0: RW<FifoConfig0>,
     /// First-in first-out queue configuration 1.
     pub fifo_config_1: RW<FifoConfig1>,
     /// Write data into first-in first-out queue.
+    ///
+    /// Queue is one byte wide; each access pushes one byte, and wider writes do not pack
+    /// more bytes into the queue. Use transmit DMA to lower processor overhead at high baudrates.
     pub fifo_write: WO<u8>,
-    _reserved3: [u8; 0x3],
+    _reserved4: [u8; 0x3],
     /// Read data from first-in first-out queue.
+    ///
+    /// Each access pops one byte; see `fifo_write` for access width.
     pub fifo_read: RO<u8>,
 }
 
@@ -168,6 +176,9 @@ impl TransmitConfig {
         self.0 & Self::IR_TRANSMIT != 0
     }
     /// Invert transmit signal output in IR mode.
+    ///
+    /// It has no effect unless IR transmission is enabled; the peripheral cannot
+    /// invert a standard serial signal, so idle-low devices need an external inverter.
     #[inline]
     pub const fn enable_ir_inverse(self) -> Self {
         Self(self.0 | Self::IR_INVERSE)
@@ -320,6 +331,15 @@ impl ReceiveConfig {
         self.0 & Self::ABR != 0
     }
     /// Enable LIN protocol receive.
+    ///
+    /// Receiver detects the break field and checks sync byte `0x55` in hardware, raising
+    /// `ReceiveSyncError` on mismatch. Break length threshold is fixed by hardware and not
+    /// configurable, unlike transmitted break length (`TransmitConfig::set_lin_break_bits`);
+    /// breaks longer than 13 bits from the master are accepted at any baudrate drift the
+    /// receiver can sample. Enable auto baudrate together to measure bit period from the
+    /// sync byte, reported by `ReceiveAutoBaudrateByFiveFive`. The zero byte of a break is
+    /// framed without stop bit; it may still reach receive queue, so LIN frame parsers
+    /// should discard bytes before the sync byte rather than treat them as framing errors.
     #[inline]
     pub const fn enable_lin_receive(self) -> Self {
         Self(self.0 | Self::LIN_RECEIVE)
@@ -381,6 +401,9 @@ impl ReceiveConfig {
         self.0 & Self::IR_RECEIVE != 0
     }
     /// Invert receive signal output in IR mode.
+    ///
+    /// It has no effect unless IR receive is enabled; see also
+    /// [`TransmitConfig::enable_ir_inverse`].
     #[inline]
     pub const fn enable_ir_inverse(self) -> Self {
         Self(self.0 | Self::IR_INVERSE)
@@ -535,6 +558,36 @@ impl Default for DataConfig {
     }
 }
 
+/// Receive timeout configuration register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct ReceiveTimeout(u32);
+
+impl ReceiveTimeout {
+    const VALUE: u32 = 0xff;
+
+    /// Set receive timeout value.
+    ///
+    /// Timeout event occurs when receive line is idle for `val + 1` bit periods
+    /// after the last received byte.
+    #[inline]
+    pub const fn set_timeout_value(self, val: u8) -> Self {
+        Self(self.0 & !Self::VALUE | val as u32)
+    }
+    /// Get receive timeout value.
+    #[inline]
+    pub const fn timeout_value(self) -> u8 {
+        (self.0 & Self::VALUE) as u8
+    }
+}
+
+impl Default for ReceiveTimeout {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_000f)
+    }
+}
+
 /// Interrupt event.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u8)]
@@ -595,11 +648,18 @@ impl InterruptMask {
 pub struct InterruptClear(u32);
 
 impl InterruptClear {
+    const ALL: u32 = 0xfff;
+
     /// Clear interrupt.
     #[inline]
     pub const fn clear_interrupt(self, val: Interrupt) -> Self {
         Self(self.0 | (1 << (val as u32)))
     }
+    /// Clear all interrupts.
+    #[inline]
+    pub const fn clear_all(self) -> Self {
+        Self(self.0 | Self::ALL)
+    }
 }
 
 /// Interrupt enable register.
@@ -771,23 +831,25 @@ mod tests {
     use crate::uart::{StopBits, WordLength};
 
     use super::{BitPeriod, Parity, ReceiveConfig, RegisterBlock, TransmitConfig};
-    use core::mem::offset_of;
 
     #[test]
     fn struct_register_block_offset() {
-        assert_eq!(offset_of!(RegisterBlock, transmit_config), 0x0);
-        assert_eq!(offset_of!(RegisterBlock, receive_config), 0x4);
-        assert_eq!(offset_of!(RegisterBlock, bit_period), 0x08);
-        assert_eq!(offset_of!(RegisterBlock, data_config), 0x0c);
-        assert_eq!(offset_of!(RegisterBlock, interrupt_state), 0x20);
-        assert_eq!(offset_of!(RegisterBlock, interrupt_mask), 0x24);
-        assert_eq!(offset_of!(RegisterBlock, interrupt_clear), 0x28);
-        assert_eq!(offset_of!(RegisterBlock, interrupt_enable), 0x2c);
-        assert_eq!(offset_of!(RegisterBlock, bus_state), 0x30);
-        assert_eq!(offset_of!(RegisterBlock, fifo_config_0), 0x80);
-        assert_eq!(offset_of!(RegisterBlock, fifo_config_1), 0x84);
-        assert_eq!(offset_of!(RegisterBlock, fifo_write), 0x88);
-        assert_eq!(offset_of!(RegisterBlock, fifo_read), 0x8c);
+        assert_offsets!(RegisterBlock {
+            transmit_config: 0x0,
+            receive_config: 0x4,
+            bit_period: 0x08,
+            data_config: 0x0c,
+            receive_timeout: 0x18,
+            interrupt_state: 0x20,
+            interrupt_mask: 0x24,
+            interrupt_clear: 0x28,
+            interrupt_enable: 0x2c,
+            bus_state: 0x30,
+            fifo_config_0: 0x80,
+            fifo_config_1: 0x84,
+            fifo_write: 0x88,
+            fifo_read: 0x8c,
+        });
     }
 
     #[test]
@@ -1042,6 +1104,21 @@ mod tests {
         assert_eq!(default.bit_order(), super::BitOrder::LsbFirst);
     }
 
+    #[test]
+    fn struct_receive_timeout_functions() {
+        let mut val: super::ReceiveTimeout = super::ReceiveTimeout(0x0);
+
+        val = val.set_timeout_value(0xff);
+        assert_eq!(val.0, 0x000000ff);
+        assert_eq!(val.timeout_value(), 0xff);
+        val = val.set_timeout_value(0x25);
+        assert_eq!(val.0, 0x00000025);
+        assert_eq!(val.timeout_value(), 0x25);
+
+        let default = super::ReceiveTimeout::default();
+        assert_eq!(default.timeout_value(), 0x0f);
+    }
+
     #[test]
     fn struct_interrupt_state_functions() {
         let val: super::InterruptState = super::InterruptState(0x0);
@@ -1116,6 +1193,9 @@ mod tests {
             val = val.clear_interrupt(interrupt);
             assert_eq!(val.0 & (1 << i), 1 << i);
         }
+
+        val = super::InterruptClear(0x0).clear_all();
+        assert_eq!(val.0, 0x0000_0fff);
     }
 
     #[test]
@@ -0,0 +1,106 @@
+use core::cell::RefCell;
+use critical_section::Mutex;
+
+/// Serial structure shared between main program and interrupt handlers.
+///
+/// It is usually placed in a `static` and initialized once the serial is created.
+/// Every access runs inside a critical section, thus no other context can use the serial
+/// at the same time.
+///
+/// ```ignore
+/// static CONSOLE: SharedSerial<BlockingSerial<UART0, Pads>> = SharedSerial::new();
+///
+/// CONSOLE.init(serial);
+/// writeln!(CONSOLE, "Hello world!").ok();
+/// ```
+pub struct SharedSerial<S> {
+    inner: Mutex<RefCell<Option<S>>>,
+}
+
+impl<S> SharedSerial<S> {
+    /// Creates an empty shared serial handle.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            inner: Mutex::new(RefCell::new(None)),
+        }
+    }
+    /// Place a serial structure into this handle, returning the previous one if any.
+    #[inline]
+    pub fn init(&self, serial: S) -> Option<S> {
+        critical_section::with(|cs| self.inner.borrow_ref_mut(cs).replace(serial))
+    }
+    /// Take the serial structure out of this handle.
+    #[inline]
+    pub fn take(&self) -> Option<S> {
+        critical_section::with(|cs| self.inner.borrow_ref_mut(cs).take())
+    }
+    /// Run a closure on the serial structure inside a critical section.
+    ///
+    /// Returns `None` if the handle is not initialized.
+    #[inline]
+    pub fn with<R>(&self, f: impl FnOnce(&mut S) -> R) -> Option<R> {
+        critical_section::with(|cs| self.inner.borrow_ref_mut(cs).as_mut().map(f))
+    }
+}
+
+impl<S: embedded_io::Write> SharedSerial<S> {
+    /// Write formatted string to the serial, used by `write!` and `writeln!` macros.
+    ///
+    /// Returns error if the handle is not initialized or the serial failed to write.
+    #[inline]
+    pub fn write_fmt(&self, args: core::fmt::Arguments<'_>) -> core::fmt::Result {
+        match self.with(|serial| serial.write_fmt(args)) {
+            Some(Ok(())) => Ok(()),
+            _ => Err(core::fmt::Error),
+        }
+    }
+}
+
+impl<S> Default for SharedSerial<S> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SharedSerial;
+
+    struct MockSerial {
+        buf: [u8; 16],
+        len: usize,
+    }
+
+    impl embedded_io::ErrorType for MockSerial {
+        type Error = core::convert::Infallible;
+    }
+
+    impl embedded_io::Write for MockSerial {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            let len = buf.len().min(self.buf.len() - self.len);
+            self.buf[self.len..self.len + len].copy_from_slice(&buf[..len]);
+            self.len += len;
+            Ok(len)
+        }
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    static SHARED: SharedSerial<MockSerial> = SharedSerial::new();
+
+    #[test]
+    fn shared_serial_write() {
+        let _ = write!(SHARED, "ignored");
+        assert!(SHARED.with(|_| ()).is_none());
+        SHARED.init(MockSerial {
+            buf: [0; 16],
+            len: 0,
+        });
+        write!(SHARED, "x = {}", 42).unwrap();
+        let serial = SHARED.take().unwrap();
+        assert_eq!(&serial.buf[..serial.len], b"x = 42");
+    }
+}
@@ -0,0 +1,191 @@
+use super::{Interrupt, RegisterBlock};
+use core::fmt;
+use embedded_time::rate::{Baud, Hertz};
+
+/// Snapshot of serial peripheral state for diagnostics.
+///
+/// Hardware has no framing error flag; a framing error shows up as a parity error
+/// if parity is enabled, or as wrong data otherwise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SerialStatus {
+    /// Transmitter is enabled.
+    pub transmit_enabled: bool,
+    /// Receiver is enabled.
+    pub receive_enabled: bool,
+    /// A byte is being shifted out on transmit line.
+    pub transmit_busy: bool,
+    /// A byte is being shifted in on receive line.
+    pub receive_busy: bool,
+    /// Number of bytes waiting in transmit queue.
+    pub transmit_fifo_count: u8,
+    /// Number of bytes waiting in receive queue.
+    pub receive_fifo_count: u8,
+    /// Transmit queue threshold for DMA and interrupt requests.
+    pub transmit_threshold: u8,
+    /// Receive queue threshold for DMA and interrupt requests.
+    pub receive_threshold: u8,
+    /// Receive queue overflowed, and received bytes were lost.
+    pub receive_overrun: bool,
+    /// Parity mismatch has been detected in received data.
+    pub parity_error: bool,
+    /// A break has been detected by `break_received`, and receive line is still held low.
+    ///
+    /// Hardware has no break flag, thus breaks are only reported once checked for.
+    pub break_received: bool,
+    /// Baudrate on transmit half, or `None` if UART clock is unknown.
+    pub transmit_baudrate: Option<Baud>,
+    /// Baudrate on receive half, or `None` if UART clock is unknown.
+    pub receive_baudrate: Option<Baud>,
+}
+
+impl fmt::Display for SerialStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let enabled = |val| if val { "enabled" } else { "disabled" };
+        let busy = |val| if val { ", busy" } else { "" };
+        let baud = |f: &mut fmt::Formatter<'_>, val: Option<Baud>| match val {
+            Some(baud) => write!(f, "{} Bd", baud.0),
+            None => write!(f, "unknown baudrate"),
+        };
+        write!(
+            f,
+            "tx {}{}, ",
+            enabled(self.transmit_enabled),
+            busy(self.transmit_busy)
+        )?;
+        baud(f, self.transmit_baudrate)?;
+        writeln!(
+            f,
+            ", fifo {}/{} threshold {}",
+            self.transmit_fifo_count,
+            super::FIFO_DEPTH,
+            self.transmit_threshold
+        )?;
+        write!(
+            f,
+            "rx {}{}, ",
+            enabled(self.receive_enabled),
+            busy(self.receive_busy)
+        )?;
+        baud(f, self.receive_baudrate)?;
+        write!(
+            f,
+            ", fifo {}/{} threshold {}",
+            self.receive_fifo_count,
+            super::FIFO_DEPTH,
+            self.receive_threshold
+        )?;
+        if self.receive_overrun {
+            write!(f, ", overrun")?;
+        }
+        if self.parity_error {
+            write!(f, ", parity error")?;
+        }
+        if self.break_received {
+            write!(f, ", break")?;
+        }
+        Ok(())
+    }
+}
+
+#[inline]
+pub(crate) fn uart_status(
+    uart: &RegisterBlock,
+    uart_clock: Option<Hertz>,
+    in_break: bool,
+) -> SerialStatus {
+    let transmit_config = uart.transmit_config.read();
+    let receive_config = uart.receive_config.read();
+    let bit_period = uart.bit_period.read();
+    let bus_state = uart.bus_state.read();
+    let fifo_config_0 = uart.fifo_config_0.read();
+    let fifo_config_1 = uart.fifo_config_1.read();
+    let interrupt_state = uart.interrupt_state.read();
+    let baud = |interval: u16| {
+        let interval = interval as u32;
+        uart_clock.map(|clock| Baud((clock.0 + interval / 2) / interval))
+    };
+    SerialStatus {
+        transmit_enabled: transmit_config.is_txd_enabled(),
+        receive_enabled: receive_config.is_rxd_enabled(),
+        transmit_busy: bus_state.transmit_busy(),
+        receive_busy: bus_state.receive_busy(),
+        transmit_fifo_count: super::FIFO_DEPTH
+            .saturating_sub(fifo_config_1.transmit_available_bytes()),
+        receive_fifo_count: fifo_config_1.receive_available_bytes(),
+        transmit_threshold: fifo_config_1.transmit_threshold(),
+        receive_threshold: fifo_config_1.receive_threshold(),
+        receive_overrun: fifo_config_0.receive_fifo_overflow(),
+        parity_error: interrupt_state.has_interrupt(Interrupt::ReceiveParityError),
+        break_received: in_break && bus_state.receive_busy(),
+        transmit_baudrate: baud(bit_period.transmit_time_interval()),
+        receive_baudrate: baud(bit_period.receive_time_interval()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::uart_status;
+    use crate::uart::MockUart;
+    use core::fmt::Write;
+    use embedded_time::rate::{Baud, Hertz};
+
+    #[test]
+    fn serial_status_snapshot() {
+        let mock = MockUart::new();
+        mock.write_word(0x00, 0x0000_8f01);
+        mock.write_word(0x08, 0x02b6_02b6);
+        mock.write_word(0x20, 1 << 5);
+        mock.write_word(0x30, 1 << 0);
+        mock.write_word(0x80, 1 << 6);
+        mock.write_word(0x84, 0x0f00_051c);
+        let status = uart_status(mock.registers(), Some(Hertz(80_000_000)), false);
+        assert!(status.transmit_enabled);
+        assert!(!status.receive_enabled);
+        assert!(status.transmit_busy);
+        assert!(!status.receive_busy);
+        assert_eq!(status.transmit_fifo_count, 4);
+        assert_eq!(status.receive_fifo_count, 5);
+        assert_eq!(status.transmit_threshold, 0);
+        assert_eq!(status.receive_threshold, 15);
+        assert!(status.receive_overrun);
+        assert!(status.parity_error);
+        assert_eq!(status.transmit_baudrate, Some(Baud(115_274)));
+        assert!(!status.break_received);
+        assert_eq!(
+            uart_status(mock.registers(), None, false).receive_baudrate,
+            None
+        );
+        // Detected break is reported only while receive line is held low.
+        assert!(!uart_status(mock.registers(), None, true).break_received);
+        mock.write_word(0x30, 0b11);
+        assert!(uart_status(mock.registers(), None, true).break_received);
+        mock.write_word(0x30, 1 << 0);
+
+        let mut buf = [0u8; 160];
+        let mut writer = Writer {
+            buf: &mut buf,
+            len: 0,
+        };
+        write!(writer, "{status}").unwrap();
+        let len = writer.len;
+        assert_eq!(
+            core::str::from_utf8(&buf[..len]).unwrap(),
+            "tx enabled, busy, 115274 Bd, fifo 4/32 threshold 0\n\
+             rx disabled, 115274 Bd, fifo 5/32 threshold 15, overrun, parity error"
+        );
+    }
+
+    struct Writer<'a> {
+        buf: &'a mut [u8],
+        len: usize,
+    }
+
+    impl Write for Writer<'_> {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            let end = self.len + s.len();
+            self.buf[self.len..end].copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,320 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal_nb::serial::{Read, Write};
+
+const SOH: u8 = 0x01;
+const STX: u8 = 0x02;
+const EOT: u8 = 0x04;
+const ACK: u8 = 0x06;
+const NAK: u8 = 0x15;
+const CAN: u8 = 0x18;
+/// Requests a transfer with CRC-16 instead of arithmetic checksum.
+const CRC_MODE: u8 = b'C';
+
+/// Time to wait for each byte inside a block.
+const BYTE_TIMEOUT_MS: u32 = 1_000;
+/// Interval between transfer requests while sender is not started.
+const START_INTERVAL_MS: u32 = 3_000;
+/// Number of transfer requests, or of retransmissions of one block, before giving up.
+const MAX_RETRIES: u8 = 10;
+
+/// XMODEM receive error.
+#[derive(Debug)]
+pub enum XmodemError<E> {
+    /// Error from the underlying serial.
+    Serial(E),
+    /// Sender did not start the transfer.
+    Timeout,
+    /// Sender cancelled the transfer.
+    Cancelled,
+    /// Block sequence was lost, as a block arrived which is neither the next nor a repeat.
+    Sequence,
+    /// Too many blocks were corrupted or missing in a row.
+    RetriesExhausted,
+    /// Received data does not fit in buffer; transfer is cancelled.
+    BufferFull,
+}
+
+/// Receive a file using XMODEM-CRC into `buf`, returning number of bytes received.
+///
+/// Receiver requests transfer by sending `C`, then acknowledges every block with a valid
+/// CRC-16, and asks for retransmission of corrupted or incomplete blocks. Both 128-byte
+/// and 1-kilobyte blocks are accepted. The protocol carries no file length, so the
+/// returned length includes padding of the last block, usually `0x1A` bytes.
+///
+/// `delay` measures timeouts while the serial has no byte to read. A transfer which
+/// cannot complete is cancelled towards the sender before an error is returned.
+///
+/// ```ignore
+/// let mut image = [0u8; 64 * 1024];
+/// let len = xmodem_receive(&mut serial, &mut delay, &mut image)?;
+/// ```
+pub fn xmodem_receive<S, D>(
+    serial: &mut S,
+    delay: &mut D,
+    buf: &mut [u8],
+) -> Result<usize, XmodemError<S::Error>>
+where
+    S: Read<u8> + Write<u8>,
+    D: DelayNs,
+{
+    let mut len = 0;
+    let mut expected = 1u8;
+    let mut retries = 0;
+    let mut started = false;
+    let mut block = [0u8; 1024 + 4];
+    let mut reply = CRC_MODE;
+    loop {
+        if retries >= MAX_RETRIES {
+            cancel(serial)?;
+            return Err(if started {
+                XmodemError::RetriesExhausted
+            } else {
+                XmodemError::Timeout
+            });
+        }
+        send(serial, reply)?;
+        let timeout = if started {
+            BYTE_TIMEOUT_MS * MAX_RETRIES as u32
+        } else {
+            START_INTERVAL_MS
+        };
+        let Some(header) = receive(serial, delay, timeout)? else {
+            reply = if started { NAK } else { CRC_MODE };
+            retries += 1;
+            continue;
+        };
+        let size = match header {
+            SOH => 128,
+            STX => 1024,
+            EOT if started => {
+                send(serial, ACK)?;
+                return Ok(len);
+            }
+            CAN => return Err(XmodemError::Cancelled),
+            _ => {
+                purge(serial, delay)?;
+                reply = if started { NAK } else { CRC_MODE };
+                retries += 1;
+                continue;
+            }
+        };
+        started = true;
+        // Block number, its complement, data and CRC-16 in big endian.
+        let frame = &mut block[..size + 4];
+        let mut complete = true;
+        for slot in frame.iter_mut() {
+            match receive(serial, delay, BYTE_TIMEOUT_MS)? {
+                Some(byte) => *slot = byte,
+                None => {
+                    complete = false;
+                    break;
+                }
+            }
+        }
+        let data = &frame[2..size + 2];
+        let crc = u16::from_be_bytes([frame[size + 2], frame[size + 3]]);
+        if !complete || frame[0] != !frame[1] || crc16(data) != crc {
+            purge(serial, delay)?;
+            reply = NAK;
+            retries += 1;
+            continue;
+        }
+        retries = 0;
+        reply = ACK;
+        if frame[0] == expected.wrapping_sub(1) {
+            // Sender missed our acknowledgement and repeated the previous block.
+            continue;
+        }
+        if frame[0] != expected {
+            cancel(serial)?;
+            return Err(XmodemError::Sequence);
+        }
+        let Some(dest) = buf.get_mut(len..len + size) else {
+            cancel(serial)?;
+            return Err(XmodemError::BufferFull);
+        };
+        dest.copy_from_slice(data);
+        len += size;
+        expected = expected.wrapping_add(1);
+    }
+}
+
+/// CRC-16 with polynomial 0x1021 and zero initial value, as used by XMODEM.
+#[inline]
+const fn crc16(data: &[u8]) -> u16 {
+    let mut crc = 0u16;
+    let mut i = 0;
+    while i < data.len() {
+        crc ^= (data[i] as u16) << 8;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+        i += 1;
+    }
+    crc
+}
+
+#[inline]
+fn send<S: Write<u8>>(serial: &mut S, byte: u8) -> Result<(), XmodemError<S::Error>> {
+    nb::block!(serial.write(byte)).map_err(XmodemError::Serial)?;
+    nb::block!(serial.flush()).map_err(XmodemError::Serial)
+}
+
+#[inline]
+fn cancel<S: Write<u8>>(serial: &mut S) -> Result<(), XmodemError<S::Error>> {
+    send(serial, CAN)?;
+    send(serial, CAN)
+}
+
+/// Wait at most `timeout_ms` milliseconds for a byte.
+#[inline]
+fn receive<S: Read<u8>, D: DelayNs>(
+    serial: &mut S,
+    delay: &mut D,
+    timeout_ms: u32,
+) -> Result<Option<u8>, XmodemError<S::Error>> {
+    let mut waited = 0;
+    loop {
+        match serial.read() {
+            Ok(byte) => return Ok(Some(byte)),
+            Err(nb::Error::Other(e)) => return Err(XmodemError::Serial(e)),
+            Err(nb::Error::WouldBlock) if waited >= timeout_ms => return Ok(None),
+            Err(nb::Error::WouldBlock) => {
+                delay.delay_ms(1);
+                waited += 1;
+            }
+        }
+    }
+}
+
+/// Drop bytes until line is silent, so that the sender is ready for our reply.
+#[inline]
+fn purge<S: Read<u8>, D: DelayNs>(
+    serial: &mut S,
+    delay: &mut D,
+) -> Result<(), XmodemError<S::Error>> {
+    while receive(serial, delay, BYTE_TIMEOUT_MS)?.is_some() {}
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{XmodemError, crc16, xmodem_receive};
+    use core::convert::Infallible;
+    use embedded_hal::delay::DelayNs;
+    use embedded_hal_nb::serial::{ErrorType, Read, Write};
+
+    /// Plays back one chunk of sender bytes after each reply of the receiver.
+    struct Sender<'a> {
+        chunks: &'a [&'a [u8]],
+        released: usize,
+        position: usize,
+        replies: [u8; 16],
+        reply_len: usize,
+    }
+
+    impl<'a> Sender<'a> {
+        fn new(chunks: &'a [&'a [u8]]) -> Self {
+            Sender {
+                chunks,
+                released: 0,
+                position: 0,
+                replies: [0; 16],
+                reply_len: 0,
+            }
+        }
+        fn replies(&self) -> &[u8] {
+            &self.replies[..self.reply_len]
+        }
+    }
+
+    impl ErrorType for Sender<'_> {
+        type Error = Infallible;
+    }
+
+    impl Read<u8> for Sender<'_> {
+        fn read(&mut self) -> nb::Result<u8, Infallible> {
+            let chunk = match self.released {
+                0 => return Err(nb::Error::WouldBlock),
+                n => self.chunks.get(n - 1).ok_or(nb::Error::WouldBlock)?,
+            };
+            let byte = *chunk.get(self.position).ok_or(nb::Error::WouldBlock)?;
+            self.position += 1;
+            Ok(byte)
+        }
+    }
+
+    impl Write<u8> for Sender<'_> {
+        fn write(&mut self, word: u8) -> nb::Result<(), Infallible> {
+            self.replies[self.reply_len] = word;
+            self.reply_len += 1;
+            self.released += 1;
+            self.position = 0;
+            Ok(())
+        }
+        fn flush(&mut self) -> nb::Result<(), Infallible> {
+            Ok(())
+        }
+    }
+
+    struct NoDelay;
+
+    impl DelayNs for NoDelay {
+        fn delay_ns(&mut self, _ns: u32) {}
+    }
+
+    fn block(number: u8, fill: u8, corrupt: bool) -> [u8; 133] {
+        let mut frame = [fill; 133];
+        frame[0] = 0x01;
+        frame[1] = number;
+        frame[2] = !number;
+        let crc = crc16(&frame[3..131]) ^ corrupt as u16;
+        frame[131..].copy_from_slice(&crc.to_be_bytes());
+        frame
+    }
+
+    #[test]
+    fn xmodem_crc16_check_value() {
+        assert_eq!(crc16(b"123456789"), 0x31c3);
+    }
+
+    #[test]
+    fn xmodem_receive_blocks() {
+        // First block is corrupted and sent again, then repeated as if our ACK was lost.
+        let (bad, good, last) = (
+            block(1, 0xaa, true),
+            block(1, 0xaa, false),
+            block(2, 0x1a, false),
+        );
+        let chunks: [&[u8]; 5] = [&bad, &good, &good, &last, &[0x04]];
+        let mut sender = Sender::new(&chunks);
+        let mut buf = [0u8; 256];
+        let len = xmodem_receive(&mut sender, &mut NoDelay, &mut buf).unwrap();
+        assert_eq!(len, 256);
+        assert!(buf[..128].iter().all(|&byte| byte == 0xaa));
+        assert!(buf[128..].iter().all(|&byte| byte == 0x1a));
+        assert_eq!(sender.replies(), b"C\x15\x06\x06\x06\x06");
+    }
+
+    #[test]
+    fn xmodem_cancel() {
+        let first = block(1, 0x00, false);
+        let chunks: [&[u8]; 1] = [&first];
+        let mut sender = Sender::new(&chunks);
+        let mut buf = [0u8; 64];
+        let res = xmodem_receive(&mut sender, &mut NoDelay, &mut buf);
+        assert!(matches!(res, Err(XmodemError::BufferFull)));
+        assert_eq!(sender.replies(), b"C\x18\x18");
+
+        let chunks: [&[u8]; 1] = [&[0x18, 0x18]];
+        let mut sender = Sender::new(&chunks);
+        let res = xmodem_receive(&mut sender, &mut NoDelay, &mut buf);
+        assert!(matches!(res, Err(XmodemError::Cancelled)));
+    }
+}
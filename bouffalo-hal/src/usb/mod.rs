@@ -1,3 +1,6 @@
 //! Universal Serial Bus peripheral.
 
+pub mod serial;
 pub mod v1;
+
+pub use serial::UsbSerial;
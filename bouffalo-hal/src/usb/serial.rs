@@ -0,0 +1,127 @@
+//! Serial data stream over USB bulk endpoints.
+//!
+//! `UsbSerial` moves bytes through the FIFOs of one bulk-in and one bulk-out endpoint,
+//! and implements the same `embedded_io` traits as UART serial structures. Code written
+//! against `embedded_io::Write` or `Read` can switch between UART pads and USB
+//! without changes.
+//!
+//! This module only provides the data path of a CDC-ACM function. Device enumeration,
+//! descriptors and class requests on endpoint 0 are not handled here and should be
+//! done before data is transferred.
+use super::v1::RegisterBlock;
+use core::ops::Deref;
+
+/// Serial stream over a pair of USB bulk endpoints.
+pub struct UsbSerial<USB, const IN: usize, const OUT: usize> {
+    usb: USB,
+}
+
+impl<USB: Deref<Target = RegisterBlock>, const IN: usize, const OUT: usize>
+    UsbSerial<USB, IN, OUT>
+{
+    /// Creates a USB serial stream on bulk-in endpoint `IN` and bulk-out endpoint `OUT`.
+    ///
+    /// Clears both endpoint FIFOs on creation.
+    #[inline]
+    pub fn new(usb: USB) -> Self {
+        const {
+            assert!(IN >= 1 && IN < 8, "bulk-in endpoint must be one of 1..=7");
+            assert!(
+                OUT >= 1 && OUT < 8,
+                "bulk-out endpoint must be one of 1..=7"
+            );
+        }
+        unsafe {
+            usb.endpoint_fifo[IN]
+                .fifo_config
+                .modify(|val| val.clear_transmit_fifo());
+            usb.endpoint_fifo[OUT]
+                .fifo_config
+                .modify(|val| val.clear_receive_fifo());
+        }
+        Self { usb }
+    }
+
+    /// Release the USB serial instance and return its peripheral.
+    #[inline]
+    pub fn free(self) -> USB {
+        self.usb
+    }
+}
+
+impl<USB, const IN: usize, const OUT: usize> embedded_io::ErrorType for UsbSerial<USB, IN, OUT> {
+    type Error = core::convert::Infallible;
+}
+
+impl<USB: Deref<Target = RegisterBlock>, const IN: usize, const OUT: usize> embedded_io::Write
+    for UsbSerial<USB, IN, OUT>
+{
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let fifo = &self.usb.endpoint_fifo[IN];
+        while fifo.fifo_status.read().transmit_available_bytes() == 0 {
+            core::hint::spin_loop();
+        }
+        let len = core::cmp::min(
+            fifo.fifo_status.read().transmit_available_bytes() as usize,
+            buf.len(),
+        );
+        buf.iter()
+            .take(len)
+            .for_each(|&byte| unsafe { fifo.fifo_write.write(byte as u32) });
+        Ok(len)
+    }
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        let fifo = &self.usb.endpoint_fifo[IN];
+        while !fifo.fifo_status.read().is_transmit_fifo_empty() {
+            core::hint::spin_loop();
+        }
+        Ok(())
+    }
+}
+
+impl<USB: Deref<Target = RegisterBlock>, const IN: usize, const OUT: usize> embedded_io::Read
+    for UsbSerial<USB, IN, OUT>
+{
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let fifo = &self.usb.endpoint_fifo[OUT];
+        while fifo.fifo_status.read().receive_available_bytes() == 0 {
+            core::hint::spin_loop();
+        }
+        let len = core::cmp::min(
+            fifo.fifo_status.read().receive_available_bytes() as usize,
+            buf.len(),
+        );
+        buf.iter_mut()
+            .take(len)
+            .for_each(|slot| *slot = fifo.fifo_read.read() as u8);
+        Ok(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UsbSerial;
+    use crate::mock::MockRegisters;
+    use crate::usb::v1::RegisterBlock;
+    use embedded_io::{Read, Write};
+
+    #[test]
+    fn empty_buffer_returns_immediately() {
+        // No host attached: no FIFO space to transmit and no data to receive.
+        let mock = MockRegisters::<RegisterBlock>::new();
+        let usb = mock.registers();
+        let mut serial = UsbSerial::<_, 1, 2>::new(usb);
+        assert_eq!(serial.write(&[]), Ok(0));
+        assert_eq!(serial.read(&mut []), Ok(0));
+        assert_eq!(serial.write_all(&[]), Ok(()));
+    }
+}
@@ -103,11 +103,123 @@ pub struct EndpointFifo {
 #[repr(transparent)]
 pub struct FifoConfig(u32);
 
+impl FifoConfig {
+    const TRANSMIT_DMA: u32 = 1 << 0;
+    const RECEIVE_DMA: u32 = 1 << 1;
+    const TRANSMIT_FIFO_CLEAR: u32 = 1 << 2;
+    const RECEIVE_FIFO_CLEAR: u32 = 1 << 3;
+    const TRANSMIT_FIFO_OVERFLOW: u32 = 1 << 4;
+    const TRANSMIT_FIFO_UNDERFLOW: u32 = 1 << 5;
+    const RECEIVE_FIFO_OVERFLOW: u32 = 1 << 6;
+    const RECEIVE_FIFO_UNDERFLOW: u32 = 1 << 7;
+
+    /// Enable transmit DMA.
+    #[inline]
+    pub const fn enable_transmit_dma(self) -> Self {
+        Self(self.0 | Self::TRANSMIT_DMA)
+    }
+    /// Disable transmit DMA.
+    #[inline]
+    pub const fn disable_transmit_dma(self) -> Self {
+        Self(self.0 & !Self::TRANSMIT_DMA)
+    }
+    /// Check if transmit DMA is enabled.
+    #[inline]
+    pub const fn is_transmit_dma_enabled(self) -> bool {
+        self.0 & Self::TRANSMIT_DMA != 0
+    }
+    /// Enable receive DMA.
+    #[inline]
+    pub const fn enable_receive_dma(self) -> Self {
+        Self(self.0 | Self::RECEIVE_DMA)
+    }
+    /// Disable receive DMA.
+    #[inline]
+    pub const fn disable_receive_dma(self) -> Self {
+        Self(self.0 & !Self::RECEIVE_DMA)
+    }
+    /// Check if receive DMA is enabled.
+    #[inline]
+    pub const fn is_receive_dma_enabled(self) -> bool {
+        self.0 & Self::RECEIVE_DMA != 0
+    }
+    /// Clear transmit FIFO.
+    #[inline]
+    pub const fn clear_transmit_fifo(self) -> Self {
+        Self(self.0 | Self::TRANSMIT_FIFO_CLEAR)
+    }
+    /// Clear receive FIFO.
+    #[inline]
+    pub const fn clear_receive_fifo(self) -> Self {
+        Self(self.0 | Self::RECEIVE_FIFO_CLEAR)
+    }
+    /// Check if transmit FIFO overflowed.
+    #[inline]
+    pub const fn transmit_fifo_overflow(self) -> bool {
+        self.0 & Self::TRANSMIT_FIFO_OVERFLOW != 0
+    }
+    /// Check if transmit FIFO underflowed.
+    #[inline]
+    pub const fn transmit_fifo_underflow(self) -> bool {
+        self.0 & Self::TRANSMIT_FIFO_UNDERFLOW != 0
+    }
+    /// Check if receive FIFO overflowed.
+    #[inline]
+    pub const fn receive_fifo_overflow(self) -> bool {
+        self.0 & Self::RECEIVE_FIFO_OVERFLOW != 0
+    }
+    /// Check if receive FIFO underflowed.
+    #[inline]
+    pub const fn receive_fifo_underflow(self) -> bool {
+        self.0 & Self::RECEIVE_FIFO_UNDERFLOW != 0
+    }
+}
+
 /// Endpoint FIFO state register.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 #[repr(transparent)]
 pub struct FifoStatus(u32);
 
+impl FifoStatus {
+    const TRANSMIT_COUNT: u32 = 0x7f;
+    const TRANSMIT_EMPTY: u32 = 1 << 14;
+    const TRANSMIT_FULL: u32 = 1 << 15;
+    const RECEIVE_COUNT: u32 = 0x7f << 16;
+    const RECEIVE_EMPTY: u32 = 1 << 30;
+    const RECEIVE_FULL: u32 = 1 << 31;
+
+    /// Get number of free bytes in transmit FIFO.
+    #[inline]
+    pub const fn transmit_available_bytes(self) -> u8 {
+        (self.0 & Self::TRANSMIT_COUNT) as u8
+    }
+    /// Check if transmit FIFO is empty.
+    #[inline]
+    pub const fn is_transmit_fifo_empty(self) -> bool {
+        self.0 & Self::TRANSMIT_EMPTY != 0
+    }
+    /// Check if transmit FIFO is full.
+    #[inline]
+    pub const fn is_transmit_fifo_full(self) -> bool {
+        self.0 & Self::TRANSMIT_FULL != 0
+    }
+    /// Get number of received bytes in receive FIFO.
+    #[inline]
+    pub const fn receive_available_bytes(self) -> u8 {
+        ((self.0 & Self::RECEIVE_COUNT) >> 16) as u8
+    }
+    /// Check if receive FIFO is empty.
+    #[inline]
+    pub const fn is_receive_fifo_empty(self) -> bool {
+        self.0 & Self::RECEIVE_EMPTY != 0
+    }
+    /// Check if receive FIFO is full.
+    #[inline]
+    pub const fn is_receive_fifo_full(self) -> bool {
+        self.0 & Self::RECEIVE_FULL != 0
+    }
+}
+
 /// Transceiver interface configuration.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 #[repr(transparent)]
@@ -131,29 +243,65 @@ impl<T, const S: usize, const N: usize> ops::Index<usize> for ArrayProxy<T, S, N
 
 #[cfg(test)]
 mod tests {
-    use super::{EndpointFifo, RegisterBlock};
-    use core::mem::offset_of;
+    use super::{EndpointFifo, FifoConfig, FifoStatus, RegisterBlock};
 
     #[test]
     fn struct_register_block_offset() {
-        assert_eq!(offset_of!(RegisterBlock, usb_config), 0x00);
-        assert_eq!(offset_of!(RegisterBlock, usb_lpm_config), 0x04);
-        assert_eq!(offset_of!(RegisterBlock, usb_resume_config), 0x08);
-        assert_eq!(offset_of!(RegisterBlock, usb_frame_number), 0x18);
-        assert_eq!(offset_of!(RegisterBlock, usb_error), 0x1c);
-        assert_eq!(offset_of!(RegisterBlock, usb_interrupt_enable), 0x20);
-        assert_eq!(offset_of!(RegisterBlock, usb_interrupt_status), 0x24);
-        assert_eq!(offset_of!(RegisterBlock, usb_interrupt_mask), 0x28);
-        assert_eq!(offset_of!(RegisterBlock, usb_interrupt_clear), 0x2c);
-        assert_eq!(offset_of!(RegisterBlock, endpoint_config), 0x40);
-        assert_eq!(offset_of!(RegisterBlock, endpoint_fifo), 0x100);
+        assert_offsets!(RegisterBlock {
+            usb_config: 0x00,
+            usb_lpm_config: 0x04,
+            usb_resume_config: 0x08,
+            usb_frame_number: 0x18,
+            usb_error: 0x1c,
+            usb_interrupt_enable: 0x20,
+            usb_interrupt_status: 0x24,
+            usb_interrupt_mask: 0x28,
+            usb_interrupt_clear: 0x2c,
+            endpoint_config: 0x40,
+            endpoint_fifo: 0x100,
+        });
     }
 
     #[test]
     fn struct_endpoint_fifo_offset() {
-        assert_eq!(offset_of!(EndpointFifo, fifo_config), 0x00);
-        assert_eq!(offset_of!(EndpointFifo, fifo_status), 0x04);
-        assert_eq!(offset_of!(EndpointFifo, fifo_write), 0x08);
-        assert_eq!(offset_of!(EndpointFifo, fifo_read), 0x0c);
+        assert_offsets!(EndpointFifo {
+            fifo_config: 0x00,
+            fifo_status: 0x04,
+            fifo_write: 0x08,
+            fifo_read: 0x0c,
+        });
+    }
+
+    #[test]
+    fn struct_fifo_config_functions() {
+        let val = FifoConfig(0x0).enable_transmit_dma();
+        assert_eq!(val.0, 0x0000_0001);
+        assert!(val.is_transmit_dma_enabled());
+        let val = val.disable_transmit_dma();
+        assert_eq!(val.0, 0x0000_0000);
+        let val = FifoConfig(0x0).enable_receive_dma();
+        assert_eq!(val.0, 0x0000_0002);
+        assert!(val.is_receive_dma_enabled());
+        let val = FifoConfig(0x0).clear_transmit_fifo().clear_receive_fifo();
+        assert_eq!(val.0, 0x0000_000c);
+
+        let val = FifoConfig(0x0000_00f0);
+        assert!(val.transmit_fifo_overflow());
+        assert!(val.transmit_fifo_underflow());
+        assert!(val.receive_fifo_overflow());
+        assert!(val.receive_fifo_underflow());
+    }
+
+    #[test]
+    fn struct_fifo_status_functions() {
+        let val = FifoStatus(0x4040_0040);
+        assert_eq!(val.transmit_available_bytes(), 64);
+        assert_eq!(val.receive_available_bytes(), 64);
+        assert!(!val.is_transmit_fifo_empty());
+        assert!(val.is_receive_fifo_empty());
+        let val = FifoStatus(0x8000_c000);
+        assert!(val.is_transmit_fifo_empty());
+        assert!(val.is_transmit_fifo_full());
+        assert!(val.is_receive_fifo_full());
     }
 }
@@ -3,7 +3,9 @@
 #![no_std]
 #![no_main]
 
-use bouffalo_rt::{Clocks, Peripherals, TrapFrame, entry, exception, interrupt, prelude::*};
+#[cfg(feature = "bl808-dsp")]
+use bouffalo_rt::interrupt;
+use bouffalo_rt::{Clocks, Peripherals, TrapFrame, entry, exception, prelude::*};
 use embedded_hal::digital::OutputPin;
 use panic_halt as _;
 
@@ -18,6 +20,8 @@ fn main(p: Peripherals, _c: Clocks) -> ! {
     }
 }
 
+// Interrupt handlers are only dispatched on DSP core.
+#[cfg(feature = "bl808-dsp")]
 #[interrupt]
 fn uart3() {
     // TODO: interrupt handler content
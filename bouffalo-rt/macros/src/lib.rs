@@ -66,6 +66,30 @@ pub fn entry(args: TokenStream, input: TokenStream) -> TokenStream {
 }
 
 /// Interrupt handler function.
+///
+/// The function name selects the interrupt source, in lowercase as listed in the
+/// interrupt table of target chip, e.g. `uart3` on BL808 DSP core. The runtime claims
+/// the source from platform-local interrupt controller, calls the handler and completes
+/// the source afterwards. Sources without a handler fall back to a default handler.
+///
+/// The source should still be enabled in interrupt controller, and machine external
+/// interrupt should be enabled in `mie` and `mstatus` before the handler can be called.
+///
+/// ```ignore
+/// use bouffalo_rt::{interrupt, soc::bl808::DspInterrupt};
+///
+/// #[interrupt]
+/// fn uart3() {
+///     // handle UART3 events
+/// }
+///
+/// // in main function:
+/// p.plic.set_priority(DspInterrupt::Uart3, 1);
+/// p.plic.enable(DspInterrupt::Uart3, D0Machine);
+/// ```
+///
+/// Currently only BL808 DSP core has an interrupt dispatcher; using this attribute on
+/// other chips is a compile error, as the handler would never be called.
 #[proc_macro_attribute]
 pub fn interrupt(args: TokenStream, input: TokenStream) -> TokenStream {
     if !args.is_empty() {
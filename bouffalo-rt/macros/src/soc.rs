@@ -15,7 +15,18 @@ pub fn check_interrupt_name(ident: &Ident) -> Option<Error> {
         ));
     }
     // TODO: support for other chips and contexts
+    // Handlers would never be called on chips without an interrupt dispatcher.
     #[cfg(not(feature = "bl808-dsp"))]
-    let _ = ident;
+    if cfg!(any(
+        feature = "bl616",
+        feature = "bl702",
+        feature = "bl808-mcu",
+        feature = "bl808-lp"
+    )) {
+        return Some(Error::new(
+            ident.span(),
+            "`#[interrupt]` handlers are not dispatched on this chip yet, only BL808 DSP core is supported.",
+        ));
+    }
     None
 }
@@ -42,6 +42,11 @@ cfg_if::cfg_if! {
 #[unsafe(no_mangle)]
 pub extern "C" fn default_handler() {}
 
+/// Whether `Peripherals` has already been handed out.
+#[cfg(target_has_atomic = "8")]
+static PERIPHERALS_TAKEN: core::sync::atomic::AtomicBool =
+    core::sync::atomic::AtomicBool::new(false);
+
 /// Flash configuration in ROM header.
 #[repr(C)]
 pub struct HalFlashConfig {
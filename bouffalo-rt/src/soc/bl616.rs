@@ -234,6 +234,8 @@ pub struct Peripherals<'a> {
     pub i2c1: I2C1,
     /// Hibernation control peripheral.
     pub hbn: HBN,
+    /// Electronic fuse data, including chip identification.
+    pub efuse: EFUSE,
     /// Ethernet Media Access Control peripheral.
     pub emac: EMAC,
 }
@@ -255,10 +257,60 @@ soc! {
     pub struct I2C1 => 0x2000A900, bouffalo_hal::i2c::RegisterBlock;
    /// Hibernation control peripheral.
     pub struct HBN => 0x2000F000, bouffalo_hal::hbn::RegisterBlock;
+    /// Electronic fuse data.
+    pub struct EFUSE => 0x20056000, bouffalo_hal::efuse::RegisterBlock;
     /// Ethernet Media Access Control peripheral.
     pub struct EMAC => 0x20070000, bouffalo_hal::emac::RegisterBlock;
 }
 
+impl Peripherals<'static> {
+    /// Takes all peripherals, returning `None` if they were already taken.
+    ///
+    /// The `#[entry]` function receives peripherals on start, thus this function
+    /// returns `None` once program enters `main`.
+    #[cfg(target_has_atomic = "8")]
+    #[inline]
+    pub fn take() -> Option<Self> {
+        if crate::PERIPHERALS_TAKEN.swap(true, core::sync::atomic::Ordering::AcqRel) {
+            None
+        } else {
+            Some(unsafe { Self::steal() })
+        }
+    }
+
+    /// Unconditionally creates all peripherals.
+    ///
+    /// # Safety
+    ///
+    /// Caller must ensure that every peripheral created here is not owned elsewhere,
+    /// for example by peripherals passed into the `#[entry]` function.
+    #[allow(unreachable_code)]
+    #[inline]
+    pub unsafe fn steal() -> Self {
+        Peripherals {
+            glb: GLBv2 { _private: () },
+            gpio: match () {
+                #[cfg(feature = "bl616")]
+                () => bouffalo_hal::gpio::Pads::__pads_from_glb(&GLBv2 { _private: () }),
+                #[cfg(not(feature = "bl616"))]
+                () => unimplemented!(),
+            },
+            uart_muxes: bouffalo_hal::uart::UartMuxes::__uart_muxes_from_glb(&GLBv2 {
+                _private: (),
+            }),
+            uart0: UART0 { _private: () },
+            uart1: UART1 { _private: () },
+            spi: SPI { _private: () },
+            i2c0: I2C0 { _private: () },
+            pwm: PWM { _private: () },
+            i2c1: I2C1 { _private: () },
+            hbn: HBN { _private: () },
+            efuse: EFUSE { _private: () },
+            emac: EMAC { _private: () },
+        }
+    }
+}
+
 pub use bouffalo_hal::clocks::Clocks;
 
 // Used by macros only.
@@ -267,27 +319,10 @@ pub use bouffalo_hal::clocks::Clocks;
 #[inline(always)]
 pub fn __rom_init_params(xtal_hz: u32) -> (Peripherals<'static>, Clocks) {
     use embedded_time::rate::Hertz;
-    let peripherals = Peripherals {
-        glb: GLBv2 { _private: () },
-        gpio: match () {
-            #[cfg(feature = "bl616")]
-            () => bouffalo_hal::gpio::Pads::__pads_from_glb(&GLBv2 { _private: () }),
-            #[cfg(not(feature = "bl616"))]
-            () => unimplemented!(),
-        },
-        uart_muxes: bouffalo_hal::uart::UartMuxes::__uart_muxes_from_glb(&GLBv2 { _private: () }),
-        uart0: UART0 { _private: () },
-        uart1: UART1 { _private: () },
-        spi: SPI { _private: () },
-        i2c0: I2C0 { _private: () },
-        pwm: PWM { _private: () },
-        i2c1: I2C1 { _private: () },
-        hbn: HBN { _private: () },
-        emac: EMAC { _private: () },
-    };
-    let clocks = Clocks {
-        xtal: Hertz(xtal_hz),
-    };
+    #[cfg(target_has_atomic = "8")]
+    crate::PERIPHERALS_TAKEN.store(true, core::sync::atomic::Ordering::Release);
+    let peripherals = unsafe { Peripherals::steal() };
+    let clocks = Clocks::new(Hertz(xtal_hz));
     (peripherals, clocks)
 }
 
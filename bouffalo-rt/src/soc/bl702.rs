@@ -188,6 +188,8 @@ pub struct Peripherals {
     pub emac: EMAC,
     /// Hibernation control peripheral.
     pub hbn: HBN,
+    /// Electronic fuse data, including chip identification.
+    pub efuse: EFUSE,
     /// Universal Serial Bus peripheral.
     pub usb: USBv1,
 }
@@ -195,6 +197,8 @@ pub struct Peripherals {
 soc! {
     /// Global configuration peripheral.
     pub struct GLBv1 => 0x40000000, bouffalo_hal::glb::v1::RegisterBlock;
+    /// Electronic fuse data.
+    pub struct EFUSE => 0x40007000, bouffalo_hal::efuse::RegisterBlock;
     /// Universal Asynchronous Receiver/Transmitter 0 with fixed base address.
     pub struct UART0 => 0x4000A000, bouffalo_hal::uart::RegisterBlock;
     /// Universal Asynchronous Receiver/Transmitter 1 with fixed base address.
@@ -213,6 +217,44 @@ soc! {
     pub struct USBv1 => 0x4000D800, bouffalo_hal::usb::v1::RegisterBlock;
 }
 
+impl Peripherals {
+    /// Takes all peripherals, returning `None` if they were already taken.
+    ///
+    /// The `#[entry]` function receives peripherals on start, thus this function
+    /// returns `None` once program enters `main`.
+    #[cfg(target_has_atomic = "8")]
+    #[inline]
+    pub fn take() -> Option<Self> {
+        if crate::PERIPHERALS_TAKEN.swap(true, core::sync::atomic::Ordering::AcqRel) {
+            None
+        } else {
+            Some(unsafe { Self::steal() })
+        }
+    }
+
+    /// Unconditionally creates all peripherals.
+    ///
+    /// # Safety
+    ///
+    /// Caller must ensure that every peripheral created here is not owned elsewhere,
+    /// for example by peripherals passed into the `#[entry]` function.
+    #[inline]
+    pub unsafe fn steal() -> Self {
+        Peripherals {
+            glb: GLBv1 { _private: () },
+            uart0: UART0 { _private: () },
+            uart1: UART1 { _private: () },
+            spi: SPI { _private: () },
+            i2c: I2C { _private: () },
+            pwm: PWM { _private: () },
+            emac: EMAC { _private: () },
+            hbn: HBN { _private: () },
+            efuse: EFUSE { _private: () },
+            usb: USBv1 { _private: () },
+        }
+    }
+}
+
 pub use bouffalo_hal::clocks::Clocks;
 
 // TODO: BL702 clock tree configuration.
@@ -222,20 +264,10 @@ pub use bouffalo_hal::clocks::Clocks;
 #[inline(always)]
 pub fn __rom_init_params(xtal_hz: u32) -> (Peripherals, Clocks) {
     use embedded_time::rate::Hertz;
-    let peripherals = Peripherals {
-        glb: GLBv1 { _private: () },
-        uart0: UART0 { _private: () },
-        uart1: UART1 { _private: () },
-        spi: SPI { _private: () },
-        i2c: I2C { _private: () },
-        pwm: PWM { _private: () },
-        emac: EMAC { _private: () },
-        hbn: HBN { _private: () },
-        usb: USBv1 { _private: () },
-    };
-    let clocks = Clocks {
-        xtal: Hertz(xtal_hz),
-    };
+    #[cfg(target_has_atomic = "8")]
+    crate::PERIPHERALS_TAKEN.store(true, core::sync::atomic::Ordering::Release);
+    let peripherals = unsafe { Peripherals::steal() };
+    let clocks = Clocks::new(Hertz(xtal_hz));
     (peripherals, clocks)
 }
 
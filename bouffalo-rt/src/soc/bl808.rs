@@ -354,7 +354,7 @@ unsafe extern "C" fn machine_external_trampoline() -> ! {
 
 #[cfg(all(feature = "bl808-dsp", target_arch = "riscv64"))]
 fn rust_bl808_dsp_machine_external(_tf: &mut crate::arch::rvi::TrapFrame) {
-    let plic: PLIC = unsafe { core::mem::transmute(()) };
+    let plic = PLIC { _private: () };
     if let Some(source) = plic.claim(D0Machine) {
         let idx = source.get() as usize;
         if idx >= 16 && idx < 16 + 67 {
@@ -917,6 +917,8 @@ pub struct Peripherals<'a> {
     pub i2c0: I2C0,
     /// Pulse Width Modulation peripheral.
     pub pwm: PWM,
+    /// Timer and watchdog peripheral 0.
+    pub timer0: TIMER0,
     /// Inter-Integrated Circuit bus peripheral 1.
     pub i2c1: I2C1,
     /// Universal Asynchronous Receiver/Transmitter peripheral 2.
@@ -925,6 +927,8 @@ pub struct Peripherals<'a> {
     pub lz4d: LZ4D,
     /// Hibernation control peripheral.
     pub hbn: HBN,
+    /// Electronic fuse data, including chip identification.
+    pub efuse: EFUSE,
     /// Ethernet Media Access Control peripheral.
     pub emac: EMAC,
     /// Universal Asynchronous Receiver/Transmitter peripheral 3.
@@ -964,6 +968,8 @@ soc! {
     pub struct I2C0 => 0x2000A300, bouffalo_hal::i2c::RegisterBlock;
     /// Pulse Width Modulation peripheral.
     pub struct PWM => 0x2000A400, bouffalo_hal::pwm::RegisterBlock;
+    /// Timer and watchdog peripheral 0 with fixed base address.
+    pub struct TIMER0 => 0x2000A500, bouffalo_hal::timer::RegisterBlock;
     /// Inter-Integrated Circuit bus 1 with fixed base address.
     pub struct I2C1 => 0x2000A900, bouffalo_hal::i2c::RegisterBlock;
     /// Universal Asynchronous Receiver/Transmitter 2 with fixed base address.
@@ -974,6 +980,8 @@ soc! {
     pub struct DMA0 => 0x2000C000, bouffalo_hal::dma::RegisterBlock;
     /// Hibernation control peripheral.
     pub struct HBN => 0x2000F000, bouffalo_hal::hbn::RegisterBlock;
+    /// Electronic fuse data.
+    pub struct EFUSE => 0x20056000, bouffalo_hal::efuse::RegisterBlock;
     /// Secure Digital High Capacity peripheral.
     pub struct SDH => 0x20060000, bouffalo_hal::sdio::RegisterBlock;
     /// Ethernet Media Access Control peripheral.
@@ -998,6 +1006,72 @@ soc! {
     pub struct PLIC => 0xE0000000, xuantie_riscv::peripheral::plic::Plic;
 }
 
+impl Peripherals<'static> {
+    /// Takes all peripherals, returning `None` if they were already taken.
+    ///
+    /// The `#[entry]` function receives peripherals on start, thus this function
+    /// returns `None` once program enters `main`.
+    #[cfg(target_has_atomic = "8")]
+    #[inline]
+    pub fn take() -> Option<Self> {
+        if crate::PERIPHERALS_TAKEN.swap(true, core::sync::atomic::Ordering::AcqRel) {
+            None
+        } else {
+            Some(unsafe { Self::steal() })
+        }
+    }
+
+    /// Unconditionally creates all peripherals.
+    ///
+    /// # Safety
+    ///
+    /// Caller must ensure that every peripheral created here is not owned elsewhere,
+    /// for example by peripherals passed into the `#[entry]` function.
+    #[allow(unreachable_code)]
+    #[inline]
+    pub unsafe fn steal() -> Self {
+        Peripherals {
+            glb: GLBv2 { _private: () },
+            gpio: match () {
+                #[cfg(any(feature = "bl808-dsp", feature = "bl808-mcu", feature = "bl808-lp"))]
+                () => bouffalo_hal::gpio::Pads::__pads_from_glb(&GLBv2 { _private: () }),
+                #[cfg(not(any(
+                    feature = "bl808-dsp",
+                    feature = "bl808-mcu",
+                    feature = "bl808-lp"
+                )))]
+                () => unimplemented!(),
+            },
+            uart_muxes: bouffalo_hal::uart::UartMuxes::__uart_muxes_from_glb(&GLBv2 {
+                _private: (),
+            }),
+            uart0: UART0 { _private: () },
+            uart1: UART1 { _private: () },
+            spi0: SPI0 { _private: () },
+            i2c0: I2C0 { _private: () },
+            pwm: PWM { _private: () },
+            timer0: TIMER0 { _private: () },
+            i2c1: I2C1 { _private: () },
+            uart2: UART2 { _private: () },
+            lz4d: LZ4D { _private: () },
+            hbn: HBN { _private: () },
+            efuse: EFUSE { _private: () },
+            emac: EMAC { _private: () },
+            uart3: UART3 { _private: () },
+            i2c2: I2C2 { _private: () },
+            i2c3: I2C3 { _private: () },
+            spi1: SPI1 { _private: () },
+            plic: PLIC { _private: () },
+            mmglb: MMGLB { _private: () },
+            psram: PSRAM { _private: () },
+            sdh: SDH { _private: () },
+            dma0: DMA0 { _private: () },
+            dma1: DMA1 { _private: () },
+            dma2: DMA2 { _private: () },
+        }
+    }
+}
+
 pub use bouffalo_hal::clocks::Clocks;
 
 // Used by macros only.
@@ -1006,40 +1080,10 @@ pub use bouffalo_hal::clocks::Clocks;
 #[inline(always)]
 pub fn __rom_init_params(xtal_hz: u32) -> (Peripherals<'static>, Clocks) {
     use embedded_time::rate::Hertz;
-    let peripherals = Peripherals {
-        glb: GLBv2 { _private: () },
-        gpio: match () {
-            #[cfg(any(feature = "bl808-dsp", feature = "bl808-mcu", feature = "bl808-lp"))]
-            () => bouffalo_hal::gpio::Pads::__pads_from_glb(&GLBv2 { _private: () }),
-            #[cfg(not(any(feature = "bl808-dsp", feature = "bl808-mcu", feature = "bl808-lp")))]
-            () => unimplemented!(),
-        },
-        uart_muxes: bouffalo_hal::uart::UartMuxes::__uart_muxes_from_glb(&GLBv2 { _private: () }),
-        uart0: UART0 { _private: () },
-        uart1: UART1 { _private: () },
-        spi0: SPI0 { _private: () },
-        i2c0: I2C0 { _private: () },
-        pwm: PWM { _private: () },
-        i2c1: I2C1 { _private: () },
-        uart2: UART2 { _private: () },
-        lz4d: LZ4D { _private: () },
-        hbn: HBN { _private: () },
-        emac: EMAC { _private: () },
-        uart3: UART3 { _private: () },
-        i2c2: I2C2 { _private: () },
-        i2c3: I2C3 { _private: () },
-        spi1: SPI1 { _private: () },
-        plic: PLIC { _private: () },
-        mmglb: MMGLB { _private: () },
-        psram: PSRAM { _private: () },
-        sdh: SDH { _private: () },
-        dma0: DMA0 { _private: () },
-        dma1: DMA1 { _private: () },
-        dma2: DMA2 { _private: () },
-    };
-    let clocks = Clocks {
-        xtal: Hertz(xtal_hz),
-    };
+    #[cfg(target_has_atomic = "8")]
+    crate::PERIPHERALS_TAKEN.store(true, core::sync::atomic::Ordering::Release);
+    let peripherals = unsafe { Peripherals::steal() };
+    let clocks = Clocks::new(Hertz(xtal_hz));
     (peripherals, clocks)
 }
 
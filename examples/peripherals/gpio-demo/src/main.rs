@@ -1,12 +1,13 @@
 #![no_std]
 #![no_main]
 
-use bouffalo_hal::prelude::*;
+use bouffalo_hal::{delay::CycleDelay, prelude::*};
 use bouffalo_rt::{Clocks, Peripherals, entry};
 use panic_halt as _;
 
 #[entry]
-fn main(p: Peripherals, _c: Clocks) -> ! {
+fn main(p: Peripherals, c: Clocks) -> ! {
+    let mut delay = CycleDelay::new(&c);
     let mut led = p.gpio.io8.into_floating_output();
     let mut button_1 = p.gpio.io22.into_pull_up_input();
     let mut button_2 = p.gpio.io23.into_pull_up_input();
@@ -19,12 +20,12 @@ fn main(p: Peripherals, _c: Clocks) -> ! {
         if button_1_pressed && button_2_pressed {
             led.set_state(led_state).ok();
             led_state = !led_state;
-            riscv::asm::delay(10_000)
+            delay.delay_ms(10)
         } else if button_1_pressed {
             led.set_low().ok();
         } else if button_2_pressed {
             led.set_high().ok();
         }
-        riscv::asm::delay(100_000)
+        delay.delay_ms(100)
     }
 }
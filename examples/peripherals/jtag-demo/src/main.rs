@@ -1,12 +1,13 @@
 #![no_std]
 #![no_main]
 
-use bouffalo_hal::prelude::*;
+use bouffalo_hal::{delay::CycleDelay, prelude::*};
 use bouffalo_rt::{Clocks, Peripherals, entry};
 use panic_halt as _;
 
 #[entry]
-fn main(p: Peripherals, _c: Clocks) -> ! {
+fn main(p: Peripherals, c: Clocks) -> ! {
+    let mut delay = CycleDelay::new(&c);
     // enable jtag
     p.gpio.io0.into_jtag_d0();
     p.gpio.io1.into_jtag_d0();
@@ -16,8 +17,8 @@ fn main(p: Peripherals, _c: Clocks) -> ! {
     let mut led = p.gpio.io8.into_floating_output();
     loop {
         led.set_low().ok();
-        riscv::asm::delay(100_000);
+        delay.delay_ms(500);
         led.set_high().ok();
-        riscv::asm::delay(100_000);
+        delay.delay_ms(500);
     }
 }
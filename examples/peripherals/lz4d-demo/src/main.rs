@@ -1,7 +1,12 @@
 #![no_std]
 #![no_main]
 
-use bouffalo_hal::{clocks::Clocks, prelude::*, uart::Config};
+use bouffalo_hal::{
+    clocks::Clocks,
+    glb::v2::{ClockGate, Lz4dClock},
+    prelude::*,
+    uart::Config,
+};
 use bouffalo_rt::{Peripherals, entry};
 use core::pin::Pin;
 use embedded_time::rate::*;
@@ -21,7 +26,7 @@ fn main(p: Peripherals, c: Clocks) -> ! {
     let config = Config::default().set_baudrate(2000000.Bd());
     let mut serial = p.uart0.freerun(config, pads, &c).unwrap();
 
-    unsafe { p.glb.clock_config_1.modify(|v| v.enable_lz4d()) };
+    Lz4dClock::enable_clock(&p.glb);
 
     let decompress = p.lz4d.decompress(
         Pin::new(&LZ4_INPUT),
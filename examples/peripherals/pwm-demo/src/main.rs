@@ -2,6 +2,7 @@
 #![no_main]
 
 use bouffalo_hal::{
+    delay::CycleDelay,
     prelude::*,
     pwm::{ClockSource::Xclk, Pwm, SingleEnd},
 };
@@ -11,6 +12,7 @@ use panic_halt as _;
 
 #[entry]
 fn main(p: Peripherals, c: Clocks) -> ! {
+    let mut delay = CycleDelay::new(&c);
     let led = p.gpio.io8.into_pull_down_pwm::<0>();
 
     let mut pwm = Pwm::new(p.pwm, SingleEnd, SingleEnd, &p.glb);
@@ -22,17 +24,17 @@ fn main(p: Peripherals, c: Clocks) -> ! {
     loop {
         for duty in 0..100 {
             led.set_duty_cycle(duty).ok();
-            riscv::asm::delay(1_000);
+            delay.delay_ms(10);
         }
         led.set_high().ok();
-        riscv::asm::delay(100_000);
+        delay.delay_ms(500);
         led.enable_pwm_output();
         for duty in (0..100).rev() {
             led.set_duty_cycle(duty).ok();
-            riscv::asm::delay(1_000);
+            delay.delay_ms(10);
         }
         led.set_low().ok();
-        riscv::asm::delay(200_000);
+        delay.delay_ms(1000);
         led.enable_pwm_output();
     }
 }
@@ -1,21 +1,20 @@
 #![no_std]
 #![no_main]
 
-use bouffalo_hal::{prelude::*, uart::Config};
+use bouffalo_hal::{prelude::*, uart::BlockingSerial};
 use bouffalo_rt::{Clocks, Peripherals, entry};
-use embedded_time::rate::*;
 use panic_halt as _;
 
 #[entry]
 fn main(p: Peripherals, c: Clocks) -> ! {
-    let tx = p.gpio.io14.into_uart();
-    let rx = p.gpio.io15.into_uart();
-    let sig2 = p.uart_muxes.sig2.into_transmit::<0>();
-    let sig3 = p.uart_muxes.sig3.into_receive::<0>();
-    let pads = ((tx, sig2), (rx, sig3));
-
-    let config = Config::default().set_baudrate(2000000.Bd());
-    let mut serial = p.uart0.freerun(config, pads, &c).unwrap();
+    // Console on M1s Dock USB serial at 115200 8N1; see `uart-dual-demo` for manual pad setup.
+    let mut serial = BlockingSerial::console_default(
+        p.uart0,
+        (p.gpio.io14, p.gpio.io15),
+        (p.uart_muxes.sig2, p.uart_muxes.sig3),
+        &c,
+    )
+    .unwrap();
 
     let mut led = p.gpio.io8.into_floating_output();
     let mut led_state = PinState::Low;
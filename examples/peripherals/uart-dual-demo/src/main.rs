@@ -0,0 +1,43 @@
+#![no_std]
+#![no_main]
+
+use bouffalo_hal::{
+    glb::v2::{ClockGate, UartClock},
+    prelude::*,
+    uart::Config,
+};
+use bouffalo_rt::{Clocks, Peripherals, entry};
+use embedded_time::rate::*;
+use panic_halt as _;
+
+#[entry]
+fn main(p: Peripherals, c: Clocks) -> ! {
+    // Console on UART0.
+    let tx = p.gpio.io14.into_uart();
+    let rx = p.gpio.io15.into_uart();
+    let sig2 = p.uart_muxes.sig2.into_transmit::<0>();
+    let sig3 = p.uart_muxes.sig3.into_receive::<0>();
+    let pads = ((tx, sig2), (rx, sig3));
+
+    let config = Config::default().set_baudrate(2000000.Bd());
+    let mut console = p.uart0.freerun(config, pads, &c).unwrap();
+
+    // Second device on UART1, using signals 8 and 9 routed to IO20 and IO21.
+    UartClock::<1>::enable_clock(&p.glb);
+    let tx = p.gpio.io20.into_uart();
+    let rx = p.gpio.io21.into_uart();
+    let sig8 = p.uart_muxes.sig8.into_transmit::<1>();
+    let sig9 = p.uart_muxes.sig9.into_receive::<1>();
+    let pads = ((tx, sig8), (rx, sig9));
+
+    let config = Config::default().set_baudrate(9600.Bd());
+    let mut gps = p.uart1.freerun(config, pads, &c).unwrap();
+
+    writeln!(console, "Forwarding data from UART1 to console.").ok();
+
+    let mut buf = [0u8; 32];
+    loop {
+        let len = gps.read(&mut buf).unwrap();
+        console.write_all(&buf[..len]).ok();
+    }
+}